@@ -0,0 +1,4 @@
+-- Row versions used for optimistic concurrency control (ETag / If-Match)
+ALTER TABLE users ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
+
+ALTER TABLE products ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
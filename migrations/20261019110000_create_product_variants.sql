@@ -0,0 +1,25 @@
+-- Option axes (e.g. size, colour) and the sellable variants (SKUs) of a product
+CREATE TABLE product_options (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    position INTEGER NOT NULL DEFAULT 0,
+    values TEXT[] NOT NULL DEFAULT '{}',
+    UNIQUE (product_id, name)
+);
+
+CREATE TABLE product_variants (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    sku TEXT NOT NULL UNIQUE,
+    price DECIMAL,
+    barcode TEXT UNIQUE,
+    weight_grams INTEGER CHECK (weight_grams >= 0),
+    image_url TEXT,
+    options JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (product_id, options)
+);
+
+CREATE INDEX product_variants_product_id_idx ON product_variants (product_id);
@@ -0,0 +1,38 @@
+-- Stock per SKU and warehouse. The ledger is the source of truth; inventory_levels
+-- is a projection of it that can be rebuilt at any time.
+CREATE TYPE inventory_reason AS ENUM (
+    'receipt',
+    'sale',
+    'return',
+    'damage',
+    'correction',
+    'transfer'
+);
+
+CREATE TABLE warehouses (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE inventory_ledger (
+    id BIGSERIAL PRIMARY KEY,
+    variant_id UUID NOT NULL REFERENCES product_variants(id) ON DELETE CASCADE,
+    warehouse_id UUID NOT NULL REFERENCES warehouses(id) ON DELETE RESTRICT,
+    quantity_delta INTEGER NOT NULL CHECK (quantity_delta <> 0),
+    reason inventory_reason NOT NULL,
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX inventory_ledger_variant_warehouse_idx ON inventory_ledger (variant_id, warehouse_id);
+
+CREATE TABLE inventory_levels (
+    variant_id UUID NOT NULL REFERENCES product_variants(id) ON DELETE CASCADE,
+    warehouse_id UUID NOT NULL REFERENCES warehouses(id) ON DELETE RESTRICT,
+    on_hand INTEGER NOT NULL DEFAULT 0 CHECK (on_hand >= 0),
+    low_stock_threshold INTEGER NOT NULL DEFAULT 0 CHECK (low_stock_threshold >= 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (variant_id, warehouse_id)
+);
@@ -0,0 +1,24 @@
+-- Stock held by pending reservations. Available stock is on_hand - reserved.
+ALTER TABLE inventory_levels
+    ADD COLUMN reserved INTEGER NOT NULL DEFAULT 0 CHECK (reserved >= 0),
+    ADD CONSTRAINT inventory_levels_reserved_within_on_hand CHECK (reserved <= on_hand);
+
+CREATE TYPE reservation_status AS ENUM ('pending', 'confirmed', 'released', 'expired');
+
+CREATE TABLE reservations (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    status reservation_status NOT NULL DEFAULT 'pending',
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX reservations_pending_expires_at_idx ON reservations (expires_at) WHERE status = 'pending';
+
+CREATE TABLE reservation_items (
+    reservation_id UUID NOT NULL REFERENCES reservations(id) ON DELETE CASCADE,
+    variant_id UUID NOT NULL REFERENCES product_variants(id) ON DELETE RESTRICT,
+    warehouse_id UUID NOT NULL REFERENCES warehouses(id) ON DELETE RESTRICT,
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    PRIMARY KEY (reservation_id, variant_id, warehouse_id)
+);
@@ -0,0 +1,11 @@
+-- Full-text search over product names and descriptions. The text search
+-- configuration is stored per row so the generated column stays immutable while
+-- the language can still be chosen per deployment.
+ALTER TABLE products
+    ADD COLUMN search_language REGCONFIG NOT NULL DEFAULT 'english',
+    ADD COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (
+        setweight(to_tsvector(search_language, name), 'A') ||
+        setweight(to_tsvector(search_language, COALESCE(description, '')), 'B')
+    ) STORED;
+
+CREATE INDEX products_search_vector_idx ON products USING GIN (search_vector);
@@ -0,0 +1,16 @@
+-- Typo tolerant autocomplete over product and category names.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX products_name_trgm_idx ON products USING GIN (lower(name) gin_trgm_ops);
+CREATE INDEX categories_name_trgm_idx ON categories USING GIN (lower(name) gin_trgm_ops);
+
+-- Units sold per product over the last 90 days, used to weight suggestions.
+-- Refreshed periodically by the service.
+CREATE MATERIALIZED VIEW product_popularity AS
+SELECT v.product_id, SUM(-l.quantity_delta)::BIGINT AS score
+FROM inventory_ledger l
+JOIN product_variants v ON v.id = l.variant_id
+WHERE l.reason = 'sale' AND l.created_at >= NOW() - INTERVAL '90 days'
+GROUP BY v.product_id;
+
+CREATE UNIQUE INDEX product_popularity_product_id_idx ON product_popularity (product_id);
@@ -0,0 +1,22 @@
+-- Typed attribute schema attached to categories. Products inherit the
+-- definitions of their categories and of all their ancestors; values live in
+-- products.attributes keyed by definition name.
+CREATE TYPE attribute_type AS ENUM ('text', 'number', 'bool', 'enum');
+
+CREATE TABLE attribute_definitions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    category_id UUID NOT NULL REFERENCES categories(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    attribute_type attribute_type NOT NULL,
+    unit TEXT,
+    required BOOLEAN NOT NULL DEFAULT FALSE,
+    allowed_values TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (category_id, name),
+    CHECK ((attribute_type = 'enum') = (cardinality(allowed_values) > 0))
+);
+
+ALTER TABLE products ADD COLUMN attributes JSONB NOT NULL DEFAULT '{}';
+
+CREATE INDEX products_attributes_idx ON products USING GIN (attributes jsonb_path_ops);
@@ -0,0 +1,33 @@
+-- Every product price carries an ISO 4217 currency. The base price (and the
+-- variant price overrides) are in the product's currency; price lists add
+-- prices in other currencies or for customer groups, optionally limited to a
+-- validity window.
+ALTER TABLE products
+    ADD COLUMN currency TEXT NOT NULL DEFAULT 'USD' CHECK (currency ~ '^[A-Z]{3}$');
+
+CREATE TABLE price_lists (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name TEXT NOT NULL UNIQUE,
+    currency TEXT NOT NULL CHECK (currency ~ '^[A-Z]{3}$'),
+    -- NULL applies to every customer.
+    customer_group TEXT,
+    -- Among applicable lists a customer group list wins, then the highest priority.
+    priority INTEGER NOT NULL DEFAULT 0,
+    valid_from TIMESTAMPTZ,
+    valid_to TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (valid_from IS NULL OR valid_to IS NULL OR valid_from < valid_to)
+);
+
+CREATE INDEX price_lists_currency_idx ON price_lists (currency, customer_group);
+
+CREATE TABLE price_list_entries (
+    price_list_id UUID NOT NULL REFERENCES price_lists(id) ON DELETE CASCADE,
+    product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    price DECIMAL NOT NULL CHECK (price >= 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (price_list_id, product_id)
+);
+
+CREATE INDEX price_list_entries_product_idx ON price_list_entries (product_id);
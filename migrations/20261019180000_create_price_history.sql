@@ -0,0 +1,45 @@
+-- Scheduled base price changes. A change with an end is a sale: the price
+-- before it started is put back once it ends. Prices are in the product's
+-- currency at the time the change applies.
+CREATE TYPE scheduled_price_status AS ENUM ('scheduled', 'active', 'completed', 'cancelled');
+
+CREATE TABLE scheduled_price_changes (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    price DECIMAL NOT NULL CHECK (price >= 0),
+    starts_at TIMESTAMPTZ NOT NULL,
+    ends_at TIMESTAMPTZ,
+    note TEXT,
+    status scheduled_price_status NOT NULL DEFAULT 'scheduled',
+    -- Base price replaced when the change started; restored when it ends.
+    previous_price DECIMAL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (ends_at IS NULL OR starts_at < ends_at)
+);
+
+CREATE INDEX scheduled_price_changes_product_idx ON scheduled_price_changes (product_id, starts_at);
+CREATE INDEX scheduled_price_changes_due_idx ON scheduled_price_changes (starts_at)
+    WHERE status = 'scheduled';
+CREATE INDEX scheduled_price_changes_ending_idx ON scheduled_price_changes (ends_at)
+    WHERE status = 'active';
+
+-- Every base price a product has had, written alongside each change.
+CREATE TYPE price_change_source AS ENUM ('created', 'manual', 'scheduled', 'schedule_ended');
+
+CREATE TABLE price_history (
+    id BIGSERIAL PRIMARY KEY,
+    product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    price DECIMAL NOT NULL,
+    currency TEXT NOT NULL,
+    previous_price DECIMAL,
+    previous_currency TEXT,
+    source price_change_source NOT NULL,
+    scheduled_price_change_id UUID REFERENCES scheduled_price_changes(id) ON DELETE SET NULL,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX price_history_product_idx ON price_history (product_id, changed_at DESC);
+
+INSERT INTO price_history (product_id, price, currency, source, changed_at)
+SELECT id, price, currency, 'created', created_at FROM products;
@@ -0,0 +1,17 @@
+-- Uploaded product images, ordered by position. The files live in the
+-- configured object storage under storage_key; thumbnails lists the resized
+-- copies as {size, width, height, storage_key} objects.
+CREATE TABLE product_images (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL CHECK (position >= 0),
+    storage_key TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    byte_size BIGINT NOT NULL CHECK (byte_size > 0),
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    alt_text TEXT,
+    thumbnails JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (product_id, position) DEFERRABLE INITIALLY DEFERRED
+);
@@ -0,0 +1,30 @@
+-- Customer reviews. Only approved reviews are shown and count towards the
+-- product's rating, which is kept on the product so lists can sort by it.
+CREATE TYPE review_status AS ENUM ('pending', 'approved', 'rejected');
+
+CREATE TABLE product_reviews (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    author_id UUID NOT NULL,
+    rating SMALLINT NOT NULL CHECK (rating BETWEEN 1 AND 5),
+    title TEXT NOT NULL,
+    body TEXT,
+    verified_purchase BOOLEAN NOT NULL DEFAULT FALSE,
+    status review_status NOT NULL DEFAULT 'pending',
+    moderation_note TEXT,
+    moderated_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- One review per customer and product.
+    UNIQUE (product_id, author_id)
+);
+
+CREATE INDEX product_reviews_product_idx ON product_reviews (product_id, status, created_at DESC);
+CREATE INDEX product_reviews_status_idx ON product_reviews (status, created_at);
+
+-- Average of the approved ratings, 0 while there are none.
+ALTER TABLE products
+    ADD COLUMN rating NUMERIC(3, 2) NOT NULL DEFAULT 0,
+    ADD COLUMN rating_count INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX products_rating_idx ON products (rating, id);
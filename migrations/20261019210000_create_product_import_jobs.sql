@@ -0,0 +1,28 @@
+-- Identifier of the product in the merchandisers' own systems; imports
+-- match rows on it (or on a variant SKU).
+ALTER TABLE products ADD COLUMN external_id TEXT UNIQUE;
+
+ALTER TYPE price_change_source ADD VALUE 'import';
+
+-- Bulk product imports, run in the background. Rows that fail are reported
+-- in `errors` and do not stop the import.
+CREATE TYPE import_format AS ENUM ('csv', 'ndjson');
+CREATE TYPE import_job_status AS ENUM ('queued', 'running', 'completed', 'failed');
+
+CREATE TABLE product_import_jobs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    format import_format NOT NULL,
+    status import_job_status NOT NULL DEFAULT 'queued',
+    total_rows INTEGER NOT NULL DEFAULT 0,
+    processed_rows INTEGER NOT NULL DEFAULT 0,
+    created_rows INTEGER NOT NULL DEFAULT 0,
+    updated_rows INTEGER NOT NULL DEFAULT 0,
+    failed_rows INTEGER NOT NULL DEFAULT 0,
+    -- [{row, external_id, sku, message}], capped; `failed_rows` has the full count.
+    errors JSONB NOT NULL DEFAULT '[]',
+    -- Why the whole import failed, e.g. an unreadable file.
+    failure TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    finished_at TIMESTAMPTZ
+);
@@ -0,0 +1,18 @@
+-- Products start as drafts and are public once active and past their
+-- `published_at`. Deleting a product only stamps `deleted_at`, so it can be
+-- restored.
+CREATE TYPE product_status AS ENUM ('draft', 'active', 'archived');
+
+-- Products created before the workflow existed were already public.
+ALTER TABLE products
+    ADD COLUMN status product_status NOT NULL DEFAULT 'active',
+    ADD COLUMN published_at TIMESTAMPTZ,
+    ADD COLUMN deleted_at TIMESTAMPTZ;
+
+UPDATE products SET published_at = created_at;
+
+ALTER TABLE products ALTER COLUMN status SET DEFAULT 'draft';
+
+CREATE INDEX products_public_idx ON products (published_at)
+    WHERE status = 'active' AND deleted_at IS NULL;
+CREATE INDEX products_deleted_idx ON products (deleted_at) WHERE deleted_at IS NOT NULL;
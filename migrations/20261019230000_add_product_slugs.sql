@@ -0,0 +1,44 @@
+-- URL slugs generated from product names. A renamed product keeps its old
+-- slugs in `product_slug_history` so links to them redirect to the new one.
+ALTER TABLE products ADD COLUMN slug TEXT;
+
+-- Same rules as the service's `slugify`: lowercase letters and digits with
+-- single hyphens between words, numbered on collision.
+DO $$
+DECLARE
+    product RECORD;
+    base TEXT;
+    candidate TEXT;
+    n INTEGER;
+BEGIN
+    FOR product IN SELECT id, name FROM products ORDER BY created_at, id LOOP
+        base := regexp_replace(
+            lower(translate(product.name, '''’', '')), '[^[:alnum:]]+', '-', 'g'
+        );
+        base := trim(BOTH '-' FROM left(trim(BOTH '-' FROM base), 80));
+        IF base = '' THEN
+            base := 'product';
+        END IF;
+
+        candidate := base;
+        n := 1;
+        WHILE EXISTS (SELECT 1 FROM products WHERE slug = candidate) LOOP
+            n := n + 1;
+            candidate := base || '-' || n;
+        END LOOP;
+
+        UPDATE products SET slug = candidate WHERE id = product.id;
+    END LOOP;
+END $$;
+
+ALTER TABLE products
+    ALTER COLUMN slug SET NOT NULL,
+    ADD CONSTRAINT products_slug_key UNIQUE (slug);
+
+CREATE TABLE product_slug_history (
+    slug TEXT PRIMARY KEY,
+    product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX product_slug_history_product_idx ON product_slug_history (product_id);
@@ -0,0 +1,20 @@
+-- Name, description and slug of products in other locales than the default
+-- one, which stays in the `products` columns. Locales are lowercase language
+-- tags such as `de` or `de-at`.
+CREATE TABLE product_translations (
+    product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    locale TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    -- Unique across products like `products.slug`, checked by the service.
+    slug TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (product_id, locale)
+);
+
+CREATE INDEX product_translations_slug_idx ON product_translations (slug);
+
+-- Locale of the translation an old slug belonged to; `NULL` for the default
+-- locale. Old slugs redirect to the product's current slug in that locale.
+ALTER TABLE product_slug_history ADD COLUMN locale TEXT;
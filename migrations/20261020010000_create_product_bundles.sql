@@ -0,0 +1,23 @@
+-- Bundles sell several products as one. A bundle either has a price of its
+-- own or, with `discount_percent`, its components' prices less the discount,
+-- which the service keeps up to date in `products.price`. Bundles hold no
+-- stock; they are available as far as every component is.
+CREATE TABLE product_bundles (
+    product_id UUID PRIMARY KEY REFERENCES products(id) ON DELETE CASCADE,
+    discount_percent NUMERIC(5, 2) CHECK (discount_percent >= 0 AND discount_percent < 100),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE product_bundle_components (
+    bundle_id UUID NOT NULL REFERENCES product_bundles(product_id) ON DELETE CASCADE,
+    component_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    position INTEGER NOT NULL,
+    PRIMARY KEY (bundle_id, component_id),
+    CHECK (bundle_id <> component_id)
+);
+
+CREATE INDEX product_bundle_components_component_idx ON product_bundle_components (component_id);
+
+ALTER TYPE price_change_source ADD VALUE 'bundle';
@@ -0,0 +1,14 @@
+-- Merchandised links from a product to others, shown in order per type.
+CREATE TYPE product_link_type AS ENUM ('related', 'accessory', 'up_sell', 'replacement');
+
+CREATE TABLE product_links (
+    product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    linked_product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    link_type product_link_type NOT NULL,
+    position INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (product_id, link_type, linked_product_id),
+    CHECK (product_id <> linked_product_id)
+);
+
+CREATE INDEX product_links_linked_product_idx ON product_links (linked_product_id);
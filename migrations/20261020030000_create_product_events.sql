@@ -0,0 +1,20 @@
+-- Outbox of product change events for other services. Events are written in
+-- the transaction that changed the product and published by the relay in `id`
+-- order. `sequence` numbers the events of each product, so consumers can drop
+-- the duplicates at-least-once delivery brings and notice gaps. There is no
+-- foreign key: events outlive the products they are about.
+CREATE TYPE product_event_type AS ENUM ('created', 'updated', 'deleted', 'restored', 'price_changed');
+
+CREATE TABLE product_events (
+    id BIGSERIAL PRIMARY KEY,
+    product_id UUID NOT NULL,
+    sequence BIGINT NOT NULL,
+    event_type product_event_type NOT NULL,
+    payload JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    published_at TIMESTAMPTZ,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    UNIQUE (product_id, sequence)
+);
+
+CREATE INDEX product_events_unpublished_idx ON product_events (id) WHERE published_at IS NULL;
@@ -0,0 +1,6 @@
+-- The ledger is the audit trail of stock movements; deleting a variant must
+-- not erase it, so variants with ledger entries can no longer be deleted.
+ALTER TABLE inventory_ledger
+    DROP CONSTRAINT inventory_ledger_variant_id_fkey,
+    ADD CONSTRAINT inventory_ledger_variant_id_fkey
+        FOREIGN KEY (variant_id) REFERENCES product_variants(id) ON DELETE RESTRICT;
@@ -0,0 +1,5 @@
+-- Changes the scheduler could not apply are parked with the reason so they
+-- stop blocking the changes due after them.
+ALTER TYPE scheduled_price_status ADD VALUE 'failed';
+
+ALTER TABLE scheduled_price_changes ADD COLUMN failure TEXT;
@@ -0,0 +1,4 @@
+-- Slugs of one locale resolve to a single product. The service allocates
+-- them under a lock; this catches anything that slips past it.
+ALTER TABLE product_translations
+    ADD CONSTRAINT product_translations_locale_slug_key UNIQUE (locale, slug);
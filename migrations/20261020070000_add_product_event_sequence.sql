@@ -0,0 +1,12 @@
+-- Last event sequence number handed out per product. Kept on the product so
+-- purging published events cannot make a product's sequence start over.
+ALTER TABLE products ADD COLUMN event_sequence BIGINT NOT NULL DEFAULT 0;
+
+UPDATE products p
+SET event_sequence = e.sequence
+FROM (
+    SELECT product_id, MAX(sequence) AS sequence
+    FROM product_events
+    GROUP BY product_id
+) e
+WHERE e.product_id = p.id;
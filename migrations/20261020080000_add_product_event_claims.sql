@@ -0,0 +1,6 @@
+-- The relay claims a batch of events before publishing it outside of any
+-- transaction. A claim lapses at `claimed_until`, so a batch claimed by an
+-- instance that died is picked up again.
+ALTER TABLE product_events
+    ADD COLUMN claimed_by UUID,
+    ADD COLUMN claimed_until TIMESTAMPTZ;
@@ -0,0 +1,6 @@
+-- After a failed delivery, the events of the product wait until
+-- `next_attempt_at` so the relay can move on to the events of other products.
+ALTER TABLE product_events ADD COLUMN next_attempt_at TIMESTAMPTZ;
+
+CREATE INDEX product_events_backing_off_idx ON product_events (product_id)
+    WHERE published_at IS NULL AND next_attempt_at IS NOT NULL;
@@ -1,5 +1,29 @@
+use std::collections::HashMap;
+
 use serde::Deserialize;
 
+/// Where uploaded product images are stored.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageBackend {
+    /// Files below `storage_local_root`, served by the service at `/media`.
+    #[default]
+    Local,
+    /// A bucket of an S3 compatible service, configured by the `s3_*` settings.
+    S3,
+}
+
+/// Where the outbox relay publishes product change events.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventSinkBackend {
+    /// The service's own log; for development.
+    #[default]
+    Log,
+    /// `POST`ed one by one as JSON to `event_webhook_url`.
+    Webhook,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ConfigLoader {
     pub server_host: String,
@@ -8,4 +32,118 @@ pub struct ConfigLoader {
     pub server_password: String,
     pub server_db_name: String,
     pub server_db_schema: String,
+    #[serde(default)]
+    pub require_if_match: bool,
+    #[serde(default = "default_reservation_ttl_seconds")]
+    pub reservation_ttl_seconds: i64,
+    #[serde(default = "default_reservation_reaper_interval_seconds")]
+    pub reservation_reaper_interval_seconds: u64,
+    #[serde(default = "default_search_language")]
+    pub search_language: String,
+    #[serde(default = "default_currency")]
+    pub default_currency: String,
+    #[serde(default = "default_price_scheduler_interval_seconds")]
+    pub price_scheduler_interval_seconds: u64,
+    #[serde(default)]
+    pub storage_backend: StorageBackend,
+    #[serde(default = "default_storage_local_root")]
+    pub storage_local_root: String,
+    /// Base URL images are downloaded from; object keys are appended to it.
+    #[serde(default = "default_storage_public_url")]
+    pub storage_public_url: String,
+    pub s3_endpoint: Option<String>,
+    #[serde(default = "default_s3_region")]
+    pub s3_region: String,
+    pub s3_bucket: Option<String>,
+    pub s3_access_key: Option<String>,
+    pub s3_secret_key: Option<String>,
+    #[serde(default = "default_image_max_bytes")]
+    pub image_max_bytes: usize,
+    #[serde(default = "default_thumbnail_sizes")]
+    pub thumbnail_sizes: Vec<u32>,
+    #[serde(default = "default_import_max_bytes")]
+    pub import_max_bytes: usize,
+    #[serde(default = "default_suggest_timeout_ms")]
+    pub suggest_timeout_ms: u64,
+    #[serde(default = "default_popularity_refresh_interval_seconds")]
+    pub popularity_refresh_interval_seconds: u64,
+    /// Locale of the product columns; translations hold the other locales.
+    #[serde(default = "default_locale")]
+    pub default_locale: String,
+    /// Locales tried in order when a product is not translated into the
+    /// requested one, e.g. `fr-ch = ["fr", "de"]`.
+    #[serde(default)]
+    pub locale_fallbacks: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub event_sink: EventSinkBackend,
+    pub event_webhook_url: Option<String>,
+    #[serde(default = "default_outbox_relay_interval_seconds")]
+    pub outbox_relay_interval_seconds: u64,
+    /// Published events are kept this long before they are purged.
+    #[serde(default = "default_outbox_retention_days")]
+    pub outbox_retention_days: i32,
+}
+
+fn default_search_language() -> String {
+    "english".to_string()
+}
+
+fn default_currency() -> String {
+    "USD".to_string()
+}
+
+fn default_price_scheduler_interval_seconds() -> u64 {
+    30
+}
+
+fn default_storage_local_root() -> String {
+    "media".to_string()
+}
+
+fn default_storage_public_url() -> String {
+    "http://localhost:8081/media".to_string()
+}
+
+fn default_s3_region() -> String {
+    "us-east-1".to_string()
+}
+
+fn default_image_max_bytes() -> usize {
+    10 * 1024 * 1024
+}
+
+fn default_import_max_bytes() -> usize {
+    50 * 1024 * 1024
+}
+
+fn default_thumbnail_sizes() -> Vec<u32> {
+    vec![160, 480, 960]
+}
+
+fn default_suggest_timeout_ms() -> u64 {
+    150
+}
+
+fn default_popularity_refresh_interval_seconds() -> u64 {
+    300
+}
+
+fn default_locale() -> String {
+    "en".to_string()
+}
+
+fn default_outbox_relay_interval_seconds() -> u64 {
+    5
+}
+
+fn default_outbox_retention_days() -> i32 {
+    7
+}
+
+fn default_reservation_ttl_seconds() -> i64 {
+    900
+}
+
+fn default_reservation_reaper_interval_seconds() -> u64 {
+    30
 }
@@ -1 +1,15 @@
+pub mod attribute_controller;
+pub mod category_controller;
+pub mod inventory_controller;
+pub mod price_history_controller;
+pub mod price_list_controller;
+pub mod product_bundle_controller;
 pub mod product_controller;
+pub mod product_image_controller;
+pub mod product_import_controller;
+pub mod product_link_controller;
+pub mod product_review_controller;
+pub mod product_translation_controller;
+pub mod product_variant_controller;
+pub mod reservation_controller;
+pub mod suggestion_controller;
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    dtos::{
+        attribute_definition_dto::AttributeDefinitionDto,
+        create_attribute_definition_dto::CreateAttributeDefinitionDto,
+        update_attribute_definition_dto::UpdateAttributeDefinitionDto,
+    },
+    models::{app_error::AppError, app_state::AppState},
+    traits::to_dto::ToDto,
+};
+
+pub async fn get_category_attributes(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<AttributeDefinitionDto>>, AppError> {
+    app_state.category_repo.get_by_id(id).await?;
+
+    let definitions = app_state.attribute_repo.get_for_category(id).await?;
+
+    Ok(Json(definitions.iter().map(|d| d.to_dto()).collect()))
+}
+
+pub async fn create_category_attribute(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(create_attribute_definition_dto): Json<CreateAttributeDefinitionDto>,
+) -> Result<impl IntoResponse, AppError> {
+    app_state.category_repo.get_by_id(id).await?;
+
+    let definition = app_state
+        .attribute_repo
+        .create(id, &create_attribute_definition_dto)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(definition.to_dto())))
+}
+
+pub async fn update_category_attribute(
+    State(app_state): State<Arc<AppState>>,
+    Path((id, attribute_id)): Path<(Uuid, Uuid)>,
+    Json(update_attribute_definition_dto): Json<UpdateAttributeDefinitionDto>,
+) -> Result<Json<AttributeDefinitionDto>, AppError> {
+    let definition = app_state
+        .attribute_repo
+        .update(id, attribute_id, &update_attribute_definition_dto)
+        .await?;
+
+    Ok(Json(definition.to_dto()))
+}
+
+pub async fn delete_category_attribute(
+    State(app_state): State<Arc<AppState>>,
+    Path((id, attribute_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    app_state.attribute_repo.delete(id, attribute_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,137 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    dtos::{
+        category_dto::CategoryDto, category_tree_dto::CategoryTreeDto,
+        create_category_dto::CreateCategoryDto, move_category_dto::MoveCategoryDto,
+        product_categories_dto::ProductCategoriesDto, update_category_dto::UpdateCategoryDto,
+    },
+    models::{app_error::AppError, app_state::AppState, category::Category},
+    repos::repository_traits::Repository,
+    traits::to_dto::ToDto,
+};
+
+fn build_tree(
+    parent_id: Option<Uuid>,
+    children_by_parent: &HashMap<Option<Uuid>, Vec<&Category>>,
+) -> Vec<CategoryTreeDto> {
+    children_by_parent
+        .get(&parent_id)
+        .map(|children| {
+            children
+                .iter()
+                .map(|category| CategoryTreeDto {
+                    id: category.id,
+                    name: category.name.clone(),
+                    children: build_tree(Some(category.id), children_by_parent),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub async fn create_category(
+    State(app_state): State<Arc<AppState>>,
+    Json(create_category_dto): Json<CreateCategoryDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let category = app_state
+        .category_repo
+        .create(&create_category_dto)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(category.to_dto())))
+}
+
+pub async fn get_category_tree(
+    State(app_state): State<Arc<AppState>>,
+) -> Result<Json<Vec<CategoryTreeDto>>, AppError> {
+    let categories = app_state.category_repo.get_all().await?;
+
+    let mut children_by_parent: HashMap<Option<Uuid>, Vec<&Category>> = HashMap::new();
+    for category in &categories {
+        children_by_parent
+            .entry(category.parent_id)
+            .or_default()
+            .push(category);
+    }
+
+    Ok(Json(build_tree(None, &children_by_parent)))
+}
+
+pub async fn get_category(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<CategoryDto>, AppError> {
+    let category = app_state.category_repo.get_by_id(id).await?;
+
+    Ok(Json(category.to_dto()))
+}
+
+pub async fn update_category(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(update_category_dto): Json<UpdateCategoryDto>,
+) -> Result<Json<CategoryDto>, AppError> {
+    let category = app_state
+        .category_repo
+        .update(id, &update_category_dto)
+        .await?;
+
+    Ok(Json(category.to_dto()))
+}
+
+pub async fn move_category(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(move_category_dto): Json<MoveCategoryDto>,
+) -> Result<Json<CategoryDto>, AppError> {
+    let category = app_state
+        .category_repo
+        .move_subtree(id, move_category_dto.parent_id)
+        .await?;
+
+    Ok(Json(category.to_dto()))
+}
+
+pub async fn delete_category(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    app_state.category_repo.delete(id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_product_categories(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<CategoryDto>>, AppError> {
+    app_state.product_repo.get_by_id(id).await?;
+
+    let categories = app_state.category_repo.get_for_product(id).await?;
+
+    Ok(Json(categories.iter().map(|c| c.to_dto()).collect()))
+}
+
+pub async fn set_product_categories(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(product_categories_dto): Json<ProductCategoriesDto>,
+) -> Result<Json<Vec<CategoryDto>>, AppError> {
+    app_state.product_repo.get_by_id(id).await?;
+
+    let categories = app_state
+        .category_repo
+        .set_for_product(id, &product_categories_dto.category_ids)
+        .await?;
+
+    Ok(Json(categories.iter().map(|c| c.to_dto()).collect()))
+}
@@ -0,0 +1,175 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+
+use crate::{
+    dtos::{
+        create_warehouse_dto::CreateWarehouseDto, inventory_adjustment_dto::InventoryAdjustmentDto,
+        inventory_audit_dto::InventoryAuditDto,
+        inventory_ledger_entry_dto::InventoryLedgerEntryDto,
+        inventory_level_dto::InventoryLevelDto, low_stock_threshold_dto::LowStockThresholdDto,
+        sku_availability_dto::SkuAvailabilityDto, warehouse_dto::WarehouseDto,
+    },
+    models::{app_error::AppError, app_state::AppState, availability_query::AvailabilityQuery},
+    traits::to_dto::ToDto,
+};
+
+pub async fn get_warehouses(
+    State(app_state): State<Arc<AppState>>,
+) -> Result<Json<Vec<WarehouseDto>>, AppError> {
+    let warehouses = app_state.warehouse_repo.get_all().await?;
+
+    Ok(Json(warehouses.iter().map(|w| w.to_dto()).collect()))
+}
+
+pub async fn get_warehouse(
+    State(app_state): State<Arc<AppState>>,
+    Path(code): Path<String>,
+) -> Result<Json<WarehouseDto>, AppError> {
+    let warehouse = app_state.warehouse_repo.get_by_code(&code).await?;
+
+    Ok(Json(warehouse.to_dto()))
+}
+
+pub async fn create_warehouse(
+    State(app_state): State<Arc<AppState>>,
+    Json(create_warehouse_dto): Json<CreateWarehouseDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let warehouse = app_state
+        .warehouse_repo
+        .create(&create_warehouse_dto)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(warehouse.to_dto())))
+}
+
+pub async fn adjust_inventory(
+    State(app_state): State<Arc<AppState>>,
+    Json(inventory_adjustment_dto): Json<InventoryAdjustmentDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let level = app_state
+        .inventory_repo
+        .adjust(&inventory_adjustment_dto)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(level.to_dto())))
+}
+
+pub async fn get_availability(
+    State(app_state): State<Arc<AppState>>,
+    Query(availability_query): Query<AvailabilityQuery>,
+) -> Result<Json<Vec<SkuAvailabilityDto>>, AppError> {
+    let skus = availability_query.skus();
+    if skus.is_empty() {
+        return Err(AppError::Invalid(
+            "At least one SKU is required".to_string(),
+        ));
+    }
+
+    let existing = app_state.inventory_repo.existing_skus(&skus).await?;
+    if let Some(missing) = skus.iter().find(|sku| !existing.contains(sku)) {
+        return Err(AppError::NotFound(format!("SKU {} not found", missing)));
+    }
+
+    let levels = app_state.inventory_repo.get_levels(&skus).await?;
+
+    let mut levels_by_sku: BTreeMap<&str, Vec<InventoryLevelDto>> = BTreeMap::new();
+    for level in &levels {
+        levels_by_sku
+            .entry(level.sku.as_str())
+            .or_default()
+            .push(level.to_dto());
+    }
+
+    let availability = skus
+        .iter()
+        .map(|sku| {
+            let warehouses = levels_by_sku.remove(sku.as_str()).unwrap_or_default();
+            let on_hand = warehouses.iter().map(|w| w.on_hand as i64).sum();
+            let reserved = warehouses.iter().map(|w| w.reserved as i64).sum();
+            let available = on_hand - reserved;
+
+            SkuAvailabilityDto {
+                sku: sku.clone(),
+                on_hand,
+                reserved,
+                available,
+                in_stock: available > 0,
+                warehouses,
+            }
+        })
+        .collect();
+
+    Ok(Json(availability))
+}
+
+pub async fn get_low_stock(
+    State(app_state): State<Arc<AppState>>,
+) -> Result<Json<Vec<InventoryLevelDto>>, AppError> {
+    let levels = app_state.inventory_repo.get_low_stock().await?;
+
+    Ok(Json(levels.iter().map(|l| l.to_dto()).collect()))
+}
+
+pub async fn get_sku_ledger(
+    State(app_state): State<Arc<AppState>>,
+    Path(sku): Path<String>,
+) -> Result<Json<Vec<InventoryLedgerEntryDto>>, AppError> {
+    if app_state
+        .inventory_repo
+        .existing_skus(std::slice::from_ref(&sku))
+        .await?
+        .is_empty()
+    {
+        return Err(AppError::NotFound("SKU not found".to_string()));
+    }
+
+    let entries = app_state.inventory_repo.get_ledger(&sku).await?;
+
+    Ok(Json(entries.iter().map(|e| e.to_dto()).collect()))
+}
+
+pub async fn set_low_stock_threshold(
+    State(app_state): State<Arc<AppState>>,
+    Path((sku, warehouse_code)): Path<(String, String)>,
+    Json(low_stock_threshold_dto): Json<LowStockThresholdDto>,
+) -> Result<Json<InventoryLevelDto>, AppError> {
+    if low_stock_threshold_dto.low_stock_threshold < 0 {
+        return Err(AppError::Invalid(
+            "low_stock_threshold must not be negative".to_string(),
+        ));
+    }
+
+    let level = app_state
+        .inventory_repo
+        .set_low_stock_threshold(
+            &sku,
+            &warehouse_code,
+            low_stock_threshold_dto.low_stock_threshold,
+        )
+        .await?;
+
+    Ok(Json(level.to_dto()))
+}
+
+pub async fn get_inventory_audit(
+    State(app_state): State<Arc<AppState>>,
+) -> Result<Json<Vec<InventoryAuditDto>>, AppError> {
+    let discrepancies = app_state.inventory_repo.audit().await?;
+
+    Ok(Json(discrepancies))
+}
+
+pub async fn rebuild_inventory(
+    State(app_state): State<Arc<AppState>>,
+) -> Result<Json<Vec<InventoryAuditDto>>, AppError> {
+    let discrepancies = app_state.inventory_repo.audit().await?;
+    app_state.inventory_repo.rebuild().await?;
+
+    Ok(Json(discrepancies))
+}
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    dtos::{
+        create_scheduled_price_change_dto::CreateScheduledPriceChangeDto,
+        price_history_entry_dto::PriceHistoryEntryDto,
+        scheduled_price_change_dto::ScheduledPriceChangeDto,
+    },
+    models::{app_error::AppError, app_state::AppState, validated_json::ValidatedJson},
+    repos::repository_traits::Repository,
+    traits::to_dto::ToDto,
+};
+
+pub async fn get_price_history(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<PriceHistoryEntryDto>>, AppError> {
+    app_state.product_repo.get_by_id(id).await?;
+
+    let entries = app_state.price_history_repo.get_for_product(id).await?;
+
+    Ok(Json(entries.iter().map(|e| e.to_dto()).collect()))
+}
+
+pub async fn get_scheduled_prices(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<ScheduledPriceChangeDto>>, AppError> {
+    app_state.product_repo.get_by_id(id).await?;
+
+    let changes = app_state.scheduled_price_repo.get_for_product(id).await?;
+
+    Ok(Json(changes.iter().map(|c| c.to_dto()).collect()))
+}
+
+pub async fn create_scheduled_price(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(create_scheduled_price_change_dto): ValidatedJson<CreateScheduledPriceChangeDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let change = app_state
+        .scheduled_price_repo
+        .create(id, &create_scheduled_price_change_dto)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(change.to_dto())))
+}
+
+pub async fn cancel_scheduled_price(
+    State(app_state): State<Arc<AppState>>,
+    Path((id, change_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ScheduledPriceChangeDto>, AppError> {
+    let change = app_state.scheduled_price_repo.cancel(id, change_id).await?;
+
+    Ok(Json(change.to_dto()))
+}
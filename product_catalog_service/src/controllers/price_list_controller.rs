@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    dtos::{
+        create_price_list_dto::CreatePriceListDto, price_list_dto::PriceListDto,
+        price_list_entry_dto::PriceListEntryDto, set_price_dto::SetPriceDto,
+        update_price_list_dto::UpdatePriceListDto,
+    },
+    models::{app_error::AppError, app_state::AppState},
+    repos::repository_traits::Repository,
+    traits::to_dto::ToDto,
+};
+
+pub async fn get_price_lists(
+    State(app_state): State<Arc<AppState>>,
+) -> Result<Json<Vec<PriceListDto>>, AppError> {
+    let price_lists = app_state.price_list_repo.get_all().await?;
+
+    Ok(Json(price_lists.iter().map(|p| p.to_dto()).collect()))
+}
+
+pub async fn get_price_list(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<PriceListDto>, AppError> {
+    let price_list = app_state.price_list_repo.get_by_id(id).await?;
+
+    Ok(Json(price_list.to_dto()))
+}
+
+pub async fn create_price_list(
+    State(app_state): State<Arc<AppState>>,
+    Json(create_price_list_dto): Json<CreatePriceListDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let price_list = app_state
+        .price_list_repo
+        .create(&create_price_list_dto)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(price_list.to_dto())))
+}
+
+pub async fn update_price_list(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(update_price_list_dto): Json<UpdatePriceListDto>,
+) -> Result<Json<PriceListDto>, AppError> {
+    let price_list = app_state
+        .price_list_repo
+        .update(id, &update_price_list_dto)
+        .await?;
+
+    Ok(Json(price_list.to_dto()))
+}
+
+pub async fn delete_price_list(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    app_state.price_list_repo.delete(id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_price_list_prices(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<PriceListEntryDto>>, AppError> {
+    app_state.price_list_repo.get_by_id(id).await?;
+
+    let entries = app_state.price_list_repo.get_entries(id).await?;
+
+    Ok(Json(entries.iter().map(|e| e.to_dto()).collect()))
+}
+
+pub async fn set_price_list_price(
+    State(app_state): State<Arc<AppState>>,
+    Path((id, product_id)): Path<(Uuid, Uuid)>,
+    Json(set_price_dto): Json<SetPriceDto>,
+) -> Result<Json<PriceListEntryDto>, AppError> {
+    app_state.price_list_repo.get_by_id(id).await?;
+    app_state.product_repo.get_by_id(product_id).await?;
+
+    let entry = app_state
+        .price_list_repo
+        .set_price(id, product_id, &set_price_dto.price)
+        .await?;
+
+    Ok(Json(entry.to_dto()))
+}
+
+pub async fn delete_price_list_price(
+    State(app_state): State<Arc<AppState>>,
+    Path((id, product_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    app_state
+        .price_list_repo
+        .delete_price(id, product_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use uuid::Uuid;
+
+use crate::{
+    dtos::{product_bundle_dto::ProductBundleDto, set_product_bundle_dto::SetProductBundleDto},
+    models::{app_error::AppError, app_state::AppState, validated_json::ValidatedJson},
+};
+
+pub async fn get_product_bundle(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ProductBundleDto>, AppError> {
+    let bundle = app_state.product_bundle_repo.get_by_product(id).await?;
+    let components = app_state.product_bundle_repo.get_components(&[id]).await?;
+
+    Ok(Json(bundle.to_dto(&components)))
+}
+
+pub async fn set_product_bundle(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(set_product_bundle_dto): ValidatedJson<SetProductBundleDto>,
+) -> Result<Json<ProductBundleDto>, AppError> {
+    let bundle = app_state
+        .product_bundle_repo
+        .set(id, &set_product_bundle_dto)
+        .await?;
+    let components = app_state.product_bundle_repo.get_components(&[id]).await?;
+
+    Ok(Json(bundle.to_dto(&components)))
+}
+
+pub async fn delete_product_bundle(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    app_state.product_bundle_repo.delete(id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
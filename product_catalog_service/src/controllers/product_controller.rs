@@ -1,89 +1,423 @@
 use std::sync::Arc;
 
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{Path, Query, RawQuery, State},
+    http::{HeaderMap, StatusCode, header},
     Json,
-    response::IntoResponse
+    response::{IntoResponse, Response},
 };
 use uuid::Uuid;
 
 use crate::{
-    dtos::{create_product_dto::CreateProductDto, update_product_dto::UpdateProductDto, product_dto::ProductDto},
-    models::{app_state::AppState, pagination::Pagination, app_error::AppError, paginated_response::PaginatedResponse},
+    dtos::{create_product_dto::CreateProductDto, update_product_dto::UpdateProductDto, product_dto::ProductDto, product_search_match_dto::ProductSearchMatchDto, related_product_dto::RelatedProductDto},
+    models::{app_state::AppState, pagination::Pagination, app_error::AppError, paginated_response::PaginatedResponse, price_query::PriceQuery, product::Product, product_visibility::ProductVisibility, related_query::RelatedQuery, validated_json::ValidatedJson, visibility_query::VisibilityQuery},
     repos::repository_traits::Repository,
     traits::to_dto::ToDto,
+    utility::{
+        etag::{etag, if_match_version, if_none_match},
+        slug::encode_slug,
+    },
 };
 
+const DEFAULT_RELATED_LIMIT: i64 = 8;
+const MAX_RELATED_LIMIT: i64 = 50;
+
+/// Builds product DTOs with their option axes, variants, images and bundle
+/// components embedded.
+/// Name, description and slug come from the first of `locales` the product is
+/// translated into, else from the product itself.
+async fn to_product_dtos(
+    app_state: &AppState,
+    products: &[Product],
+    locales: &[String],
+) -> Result<Vec<ProductDto>, AppError> {
+    let ids: Vec<Uuid> = products.iter().map(|product| product.id).collect();
+
+    let translations = if locales.is_empty() {
+        Vec::new()
+    } else {
+        app_state.product_translation_repo.get_for_products(&ids, locales).await?
+    };
+
+    let options = app_state.product_variant_repo.get_options(&ids).await?;
+    let variants = app_state.product_variant_repo.get_variants(&ids).await?;
+    let images = app_state.product_image_repo.get_for_products(&ids).await?;
+    let bundles = app_state.product_bundle_repo.get_bundles(&ids).await?;
+    let bundle_components = if bundles.is_empty() {
+        Vec::new()
+    } else {
+        let bundle_ids: Vec<Uuid> = bundles.iter().map(|bundle| bundle.product_id).collect();
+        app_state.product_bundle_repo.get_components(&bundle_ids).await?
+    };
+
+    Ok(products
+        .iter()
+        .map(|product| {
+            let mut product_dto: ProductDto = product.to_dto();
+            let translation = locales.iter().find_map(|locale| {
+                translations
+                    .iter()
+                    .find(|t| t.product_id == product.id && t.locale == *locale)
+            });
+            if let Some(translation) = translation {
+                product_dto.name = translation.name.clone();
+                product_dto.slug = translation.slug.clone();
+                if translation.description.is_some() {
+                    product_dto.description = translation.description.clone();
+                }
+                product_dto.locale = Some(translation.locale.clone());
+            }
+            product_dto.options = options
+                .iter()
+                .filter(|option| option.product_id == product.id)
+                .map(|option| option.to_dto())
+                .collect();
+            product_dto.variants = variants
+                .iter()
+                .filter(|variant| variant.product_id == product.id)
+                .map(|variant| variant.to_dto())
+                .collect();
+            // Variant price overrides are in the base currency; a price list
+            // price applies to every variant.
+            if product.price_list_id.is_some() {
+                for variant in product_dto.variants.iter_mut() {
+                    variant.price = None;
+                }
+            }
+            product_dto.images = images
+                .iter()
+                .filter(|image| image.product_id == product.id)
+                .map(|image| image.to_dto(app_state.image_storage.as_ref()))
+                .collect();
+            // Uploaded images take over from a URL set by hand.
+            if let Some(first_image) = product_dto.images.first() {
+                product_dto.image_url = Some(first_image.url.clone());
+            }
+            product_dto.bundle = bundles
+                .iter()
+                .find(|bundle| bundle.product_id == product.id)
+                .map(|bundle| bundle.to_dto(&bundle_components));
+            product_dto
+        })
+        .collect())
+}
+
 pub async fn create_product(
     State(app_state): State<Arc<AppState>>,
-    Json(create_product_dto): Json<CreateProductDto>,
+    ValidatedJson(create_product_dto): ValidatedJson<CreateProductDto>,
 ) -> Result<impl IntoResponse, AppError> {
     let product = app_state
         .product_repo
         .create(&create_product_dto)
         .await?;
 
-    Ok((StatusCode::CREATED, Json(product.to_dto())))
+    let etag_header = [(header::ETAG, etag(product.version))];
+
+    Ok((StatusCode::CREATED, etag_header, Json(product.to_dto())))
 }
 
 pub async fn get_products(
     State(app_state): State<Arc<AppState>>,
-    pagination: Query<Pagination>,
-) -> Result<Json<PaginatedResponse<ProductDto>>, AppError> {
+    Query(pagination): Query<Pagination>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    products_response(&app_state, &pagination, &headers).await
+}
+
+/// Lists products like [`get_products`], drafts and deleted products too when
+/// `visibility` asks for them. Every product that is not deleted by default.
+pub async fn get_admin_products(
+    State(app_state): State<Arc<AppState>>,
+    Query(mut pagination): Query<Pagination>,
+    Query(visibility_query): Query<VisibilityQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    pagination.visibility = Some(visibility_query.visibility.unwrap_or(ProductVisibility::All));
+
+    products_response(&app_state, &pagination, &headers).await
+}
+
+/// A page of products, or of search hits when `q` is given, with facets.
+async fn products_response(
+    app_state: &AppState,
+    pagination: &Pagination,
+    headers: &HeaderMap,
+) -> Result<Response, AppError> {
+    let locales = app_state.locales.negotiate(headers);
+    let vary_header = [(header::VARY, "accept-language")];
+
+    // An unknown category is a mistake, not a category without products.
+    if let Some(category_id) = pagination.category {
+        app_state.category_repo.get_by_id(category_id).await?;
+    }
+
+    if pagination.q.is_some() {
+        let paginated_response = search_products(app_state, pagination, &locales).await?;
+        return Ok((vary_header, Json(paginated_response)).into_response());
+    }
+
     let paginated_response = app_state
         .product_repo
-        .get_all(&pagination)
+        .get_all(pagination)
         .await?;
 
-    let product_dtos = paginated_response
+    let product_dtos = to_product_dtos(app_state, &paginated_response.data, &locales).await?;
+    let facets = app_state.product_repo.facets(pagination).await?;
+
+    let paginated_response = PaginatedResponse {
+        page: paginated_response.page,
+        per_page: paginated_response.per_page,
+        total: paginated_response.total,
+        total_pages: paginated_response.total_pages,
+        next_cursor: paginated_response.next_cursor,
+        prev_cursor: paginated_response.prev_cursor,
+        facets: Some(facets),
+        data: product_dtos,
+    };
+
+    Ok((vary_header, Json(paginated_response)).into_response())
+}
+
+async fn search_products(
+    app_state: &AppState,
+    pagination: &Pagination,
+    locales: &[String],
+) -> Result<PaginatedResponse<ProductDto>, AppError> {
+    let paginated_response = app_state.product_repo.search(pagination).await?;
+
+    let (products, matches): (Vec<Product>, Vec<ProductSearchMatchDto>) = paginated_response
         .data
-        .iter()
-        .map(|product| product.to_dto())
-        .collect();
+        .into_iter()
+        .map(|hit| {
+            let search_match = hit.to_dto();
+            (hit.product, search_match)
+        })
+        .unzip();
 
-    Ok(Json(PaginatedResponse {
+    let mut product_dtos = to_product_dtos(app_state, &products, locales).await?;
+    for (product_dto, search_match) in product_dtos.iter_mut().zip(matches) {
+        product_dto.search = Some(search_match);
+    }
+
+    let facets = app_state.product_repo.facets(pagination).await?;
+
+    Ok(PaginatedResponse {
         page: paginated_response.page,
         per_page: paginated_response.per_page,
         total: paginated_response.total,
         total_pages: paginated_response.total_pages,
+        next_cursor: paginated_response.next_cursor,
+        prev_cursor: paginated_response.prev_cursor,
+        facets: Some(facets),
         data: product_dtos,
-    }))
+    })
 }
 
 pub async fn get_product(
     State(app_state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
-) -> Result<Json<ProductDto>, AppError> {
-    let product = app_state
+    Query(price_query): Query<PriceQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let locales = app_state.locales.negotiate(&headers);
+
+    product_response(
+        &app_state,
+        id,
+        ProductVisibility::Public,
+        &price_query,
+        &headers,
+        &locales,
+    )
+    .await
+}
+
+/// Answers like [`get_product`] for any product that is not deleted, whatever
+/// its status.
+pub async fn get_admin_product(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(price_query): Query<PriceQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let locales = app_state.locales.negotiate(&headers);
+
+    product_response(
+        &app_state,
+        id,
+        ProductVisibility::All,
+        &price_query,
+        &headers,
+        &locales,
+    )
+    .await
+}
+
+/// A single product in the requested locale, answering conditional requests.
+async fn product_response(
+    app_state: &AppState,
+    id: Uuid,
+    visibility: ProductVisibility,
+    price_query: &PriceQuery,
+    headers: &HeaderMap,
+    locales: &[String],
+) -> Result<Response, AppError> {
+    let is_priced = price_query.currency()?.is_some();
+
+    let product = if is_priced {
+        app_state.product_repo.get_priced(id, price_query, visibility).await?
+    } else {
+        app_state.product_repo.get_visible(id, visibility).await?
+    };
+
+    let etag_header = [(header::ETAG, etag(product.version))];
+
+    // Price list changes do not bump the product version, so priced reads
+    // are never answered from the client's cache.
+    if !is_priced && if_none_match(headers, product.version) {
+        return Ok((StatusCode::NOT_MODIFIED, etag_header).into_response());
+    }
+
+    let product_dto = to_product_dtos(app_state, &[product], locales).await?.remove(0);
+
+    let content_language = product_dto
+        .locale
+        .clone()
+        .unwrap_or_else(|| app_state.locales.default_locale.clone());
+    let language_headers = [
+        (header::CONTENT_LANGUAGE, content_language),
+        (header::VARY, "accept-language".to_string()),
+    ];
+
+    Ok((etag_header, language_headers, Json(product_dto)).into_response())
+}
+
+/// Answers like [`get_product`] for the product's current slug and redirects
+/// permanently to it from slugs the product had before a rename. A translated
+/// slug shows the product in that translation's locale.
+pub async fn get_product_by_slug(
+    State(app_state): State<Arc<AppState>>,
+    Path(slug): Path<String>,
+    Query(price_query): Query<PriceQuery>,
+    RawQuery(raw_query): RawQuery,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let slug_match = app_state.product_repo.resolve_slug(&slug).await?;
+
+    if let Some(current_slug) = slug_match.redirect_to {
+        let mut location = format!("/products/by-slug/{}", encode_slug(&current_slug));
+        if let Some(raw_query) = raw_query {
+            location.push('?');
+            location.push_str(&raw_query);
+        }
+        return Ok((StatusCode::MOVED_PERMANENTLY, [(header::LOCATION, location)]).into_response());
+    }
+
+    let mut locales = app_state.locales.negotiate(&headers);
+    if let Some(locale) = slug_match.locale {
+        locales.retain(|preferred| *preferred != locale);
+        locales.insert(0, locale);
+    }
+
+    product_response(
+        &app_state,
+        slug_match.product_id,
+        ProductVisibility::Public,
+        &price_query,
+        &headers,
+        &locales,
+    )
+    .await
+}
+
+/// Products to show with the product, see [`ProductRepo::get_related`].
+///
+/// [`ProductRepo::get_related`]: crate::repos::product_repo::ProductRepo::get_related
+pub async fn get_related_products(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(related_query): Query<RelatedQuery>,
+    Query(price_query): Query<PriceQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let limit = related_query
+        .limit
+        .unwrap_or(DEFAULT_RELATED_LIMIT)
+        .clamp(1, MAX_RELATED_LIMIT);
+
+    app_state
         .product_repo
-        .get_by_id(id)
+        .get_visible(id, ProductVisibility::Public)
         .await?;
 
-    Ok(Json(product.to_dto()))
+    let (products, link_types): (Vec<Product>, Vec<_>) = app_state
+        .product_repo
+        .get_related(id, related_query.link_type, &price_query, limit)
+        .await?
+        .into_iter()
+        .map(|related| (related.product, related.link_type))
+        .unzip();
+
+    let locales = app_state.locales.negotiate(&headers);
+    let product_dtos = to_product_dtos(&app_state, &products, &locales).await?;
+
+    let related_product_dtos: Vec<RelatedProductDto> = product_dtos
+        .into_iter()
+        .zip(link_types)
+        .map(|(product, link_type)| RelatedProductDto { link_type, product })
+        .collect();
+
+    Ok(([(header::VARY, "accept-language")], Json(related_product_dtos)).into_response())
 }
 
 pub async fn update_product(
     State(app_state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
-    Json(update_product_dto): Json<UpdateProductDto>,
-) -> Result<Json<ProductDto>, AppError> {
+    headers: HeaderMap,
+    ValidatedJson(update_product_dto): ValidatedJson<UpdateProductDto>,
+) -> Result<Response, AppError> {
+    let version = if_match_version(&headers, app_state.require_if_match)?;
+
     let product = app_state
         .product_repo
-        .update(id, &update_product_dto)
+        .update(id, &update_product_dto, version)
         .await?;
 
-    Ok(Json(product.to_dto()))
+    let etag_header = [(header::ETAG, etag(product.version))];
+    let product_dto = to_product_dtos(&app_state, &[product], &[]).await?.remove(0);
+
+    Ok((etag_header, Json(product_dto)).into_response())
 }
 
 pub async fn delete_product(
     State(app_state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
 ) -> Result<StatusCode, AppError> {
+    let version = if_match_version(&headers, app_state.require_if_match)?;
+
     app_state
         .product_repo
-        .delete(id)
+        .delete(id, version)
         .await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+pub async fn restore_product(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let version = if_match_version(&headers, app_state.require_if_match)?;
+
+    let product = app_state
+        .product_repo
+        .restore(id, version)
+        .await?;
+
+    let etag_header = [(header::ETAG, etag(product.version))];
+    let product_dto = to_product_dtos(&app_state, &[product], &[]).await?.remove(0);
+
+    Ok((etag_header, Json(product_dto)).into_response())
+}
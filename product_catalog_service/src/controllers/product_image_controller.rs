@@ -0,0 +1,224 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{
+        Multipart, Path, State,
+        multipart::{Field, MultipartError},
+    },
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    dtos::{
+        product_image_dto::ProductImageDto, reorder_product_images_dto::ReorderProductImagesDto,
+    },
+    models::{
+        app_error::AppError,
+        app_state::AppState,
+        product_image::{ImageThumbnail, NewProductImage},
+        product_visibility::ProductVisibility,
+    },
+    repos::repository_traits::Repository,
+    traits::object_storage::ObjectStorage,
+    utility::image_processing::process_image,
+};
+
+/// Longest alt text accepted, in bytes.
+const MAX_ALT_TEXT_BYTES: usize = 1000;
+
+fn multipart_error(err: MultipartError) -> AppError {
+    AppError::Invalid(err.body_text())
+}
+
+/// Reads a form field in chunks so an oversized one is cut off early instead
+/// of being buffered whole.
+async fn read_field(
+    field: &mut Field<'_>,
+    max_bytes: usize,
+    too_large: impl Fn() -> String,
+) -> Result<Vec<u8>, AppError> {
+    let mut bytes = Vec::new();
+
+    while let Some(chunk) = field.chunk().await.map_err(multipart_error)? {
+        if bytes.len() + chunk.len() > max_bytes {
+            return Err(AppError::PayloadTooLarge(too_large()));
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(bytes)
+}
+
+/// Deletes stored files, logging instead of failing; a leftover file only
+/// costs space.
+async fn remove_objects(storage: &dyn ObjectStorage, keys: &[String]) {
+    for key in keys {
+        if storage.delete(key).await.is_err() {
+            tracing::warn!(key, "left an orphaned object in storage");
+        }
+    }
+}
+
+pub async fn get_product_images(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<ProductImageDto>>, AppError> {
+    app_state
+        .product_repo
+        .get_visible(id, ProductVisibility::Public)
+        .await?;
+
+    let images = app_state.product_image_repo.get_for_products(&[id]).await?;
+
+    Ok(Json(
+        images
+            .iter()
+            .map(|image| image.to_dto(app_state.image_storage.as_ref()))
+            .collect(),
+    ))
+}
+
+/// Accepts a `multipart/form-data` body with the image in a `file` field and
+/// an optional `alt_text` field. The image is appended after the product's
+/// existing images.
+pub async fn upload_product_image(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, AppError> {
+    app_state.product_repo.get_by_id(id).await?;
+
+    let mut file: Option<(Vec<u8>, Option<String>)> = None;
+    let mut alt_text: Option<String> = None;
+
+    while let Some(mut field) = multipart.next_field().await.map_err(multipart_error)? {
+        match field.name() {
+            Some("file") => {
+                let content_type = field.content_type().map(str::to_string);
+                let bytes = read_field(&mut field, app_state.image_max_bytes, || {
+                    format!("Images may be at most {} bytes", app_state.image_max_bytes)
+                })
+                .await?;
+
+                file = Some((bytes, content_type));
+            }
+            Some("alt_text") => {
+                let bytes = read_field(&mut field, MAX_ALT_TEXT_BYTES, || {
+                    format!("`alt_text` may be at most {} bytes", MAX_ALT_TEXT_BYTES)
+                })
+                .await?;
+
+                alt_text =
+                    Some(String::from_utf8(bytes).map_err(|_| {
+                        AppError::Invalid("`alt_text` must be UTF-8 text".to_string())
+                    })?);
+            }
+            other => {
+                return Err(AppError::Invalid(format!(
+                    "Unexpected form field '{}'",
+                    other.unwrap_or_default()
+                )));
+            }
+        }
+    }
+
+    let (bytes, declared_content_type) =
+        file.filter(|(bytes, _)| !bytes.is_empty()).ok_or_else(|| {
+            AppError::Invalid("A `file` field with the image is required".to_string())
+        })?;
+
+    let sizes = app_state.thumbnail_sizes.clone();
+    let (bytes, processed) = tokio::task::spawn_blocking(move || {
+        let processed = process_image(&bytes, declared_content_type.as_deref(), &sizes);
+        (bytes, processed)
+    })
+    .await
+    .map_err(|err| {
+        tracing::error!(error = %err, "image processing task failed");
+        AppError::InternalServerError
+    })?;
+    let processed = processed?;
+
+    let image_id = Uuid::new_v4();
+    let prefix = format!("products/{}/images/{}", id, image_id);
+    let storage = app_state.image_storage.as_ref();
+
+    let mut new_image = NewProductImage {
+        id: image_id,
+        product_id: id,
+        storage_key: format!("{}/original.{}", prefix, processed.extension),
+        content_type: processed.content_type.to_string(),
+        byte_size: bytes.len() as i64,
+        width: processed.width as i32,
+        height: processed.height as i32,
+        alt_text: alt_text.filter(|text| !text.trim().is_empty()),
+        thumbnails: Vec::new(),
+    };
+
+    let mut stored_keys = Vec::new();
+    let stored = async {
+        storage
+            .put(&new_image.storage_key, processed.content_type, bytes)
+            .await?;
+        stored_keys.push(new_image.storage_key.clone());
+
+        for thumbnail in processed.thumbnails {
+            let key = format!("{}/{}.{}", prefix, thumbnail.size, thumbnail.extension);
+            storage
+                .put(&key, thumbnail.content_type, thumbnail.bytes)
+                .await?;
+            stored_keys.push(key.clone());
+
+            new_image.thumbnails.push(ImageThumbnail {
+                size: thumbnail.size as i32,
+                width: thumbnail.width as i32,
+                height: thumbnail.height as i32,
+                content_type: thumbnail.content_type.to_string(),
+                storage_key: key,
+            });
+        }
+
+        app_state.product_image_repo.create(&new_image).await
+    }
+    .await;
+
+    match stored {
+        Ok(image) => Ok((StatusCode::CREATED, Json(image.to_dto(storage)))),
+        Err(err) => {
+            remove_objects(storage, &stored_keys).await;
+            Err(err)
+        }
+    }
+}
+
+pub async fn reorder_product_images(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(reorder_product_images_dto): Json<ReorderProductImagesDto>,
+) -> Result<Json<Vec<ProductImageDto>>, AppError> {
+    let images = app_state
+        .product_image_repo
+        .reorder(id, &reorder_product_images_dto.image_ids)
+        .await?;
+
+    Ok(Json(
+        images
+            .iter()
+            .map(|image| image.to_dto(app_state.image_storage.as_ref()))
+            .collect(),
+    ))
+}
+
+pub async fn delete_product_image(
+    State(app_state): State<Arc<AppState>>,
+    Path((id, image_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    let image = app_state.product_image_repo.delete(id, image_id).await?;
+
+    remove_objects(app_state.image_storage.as_ref(), &image.storage_keys()).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    body::Body,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use futures_util::{StreamExt, stream};
+use uuid::Uuid;
+
+use crate::{
+    dtos::import_job_dto::ImportJobDto,
+    jobs::product_importer::run_product_import,
+    models::{
+        app_error::AppError, app_state::AppState, export_query::ExportQuery,
+        import_job::ImportFormat, product_export_row::ProductExportRow,
+    },
+    traits::to_dto::ToDto,
+};
+
+/// Products read per query while exporting.
+const EXPORT_BATCH_SIZE: i64 = 500;
+
+/// Accepts a CSV (`text/csv`) or NDJSON (`application/x-ndjson`) file of
+/// products and imports it in the background. Responds with the job to poll
+/// for progress and the error report.
+pub async fn import_products(
+    State(app_state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<impl IntoResponse, AppError> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let format = ImportFormat::from_content_type(content_type)?;
+
+    // Read in chunks so an oversized upload is cut off early.
+    let mut file = Vec::new();
+    let mut chunks = body.into_data_stream();
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk.map_err(|err| AppError::Invalid(err.to_string()))?;
+        if file.len() + chunk.len() > app_state.import_max_bytes {
+            return Err(AppError::PayloadTooLarge(format!(
+                "Import files may be at most {} bytes",
+                app_state.import_max_bytes
+            )));
+        }
+        file.extend_from_slice(&chunk);
+    }
+
+    if file.is_empty() {
+        return Err(AppError::Invalid("Import file is empty".to_string()));
+    }
+
+    let job = app_state.import_job_repo.create(format).await?;
+
+    tokio::spawn(run_product_import(
+        app_state.product_repo.clone(),
+        app_state.import_job_repo.clone(),
+        job.id,
+        format,
+        file.into(),
+    ));
+
+    let location = format!("/products/imports/{}", job.id);
+
+    Ok((
+        StatusCode::ACCEPTED,
+        [(header::LOCATION, location)],
+        Json(job.to_dto()),
+    ))
+}
+
+pub async fn get_product_import(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ImportJobDto>, AppError> {
+    let job = app_state.import_job_repo.get_by_id(id).await?;
+
+    Ok(Json(job.to_dto()))
+}
+
+/// Streams the whole catalog as CSV or NDJSON, in the shape imports accept.
+/// Products are read in batches, so the export is not a single snapshot.
+pub async fn export_products(
+    State(app_state): State<Arc<AppState>>,
+    Query(export_query): Query<ExportQuery>,
+) -> Result<Response, AppError> {
+    let format = export_query.format.unwrap_or(ImportFormat::Csv);
+
+    // The state is the id to continue after, `None` once every batch is out.
+    let batches = stream::try_unfold(Some((None::<Uuid>, true)), move |state| {
+        let app_state = app_state.clone();
+        async move {
+            let Some((after, first_batch)) = state else {
+                return Ok(None);
+            };
+
+            let rows = app_state
+                .product_repo
+                .export_batch(after, EXPORT_BATCH_SIZE)
+                .await?;
+            let bytes = ProductExportRow::encode(&rows, format, first_batch)?;
+
+            let next = match rows.last() {
+                Some(last) if rows.len() as i64 == EXPORT_BATCH_SIZE => {
+                    Some((Some(last.id), false))
+                }
+                _ => None,
+            };
+
+            Ok::<_, AppError>(Some((bytes, next)))
+        }
+    })
+    .map(|batch| {
+        batch.map_err(|err| {
+            tracing::error!(error = ?err, "product export failed");
+            std::io::Error::other("product export failed")
+        })
+    });
+
+    let disposition = format!("attachment; filename=\"products.{}\"", format.extension());
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        Body::from_stream(batches),
+    )
+        .into_response())
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use uuid::Uuid;
+
+use crate::{
+    dtos::{product_link_dto::ProductLinkDto, set_product_links_dto::SetProductLinksDto},
+    models::{
+        app_error::AppError, app_state::AppState, product_link::ProductLinkType,
+        validated_json::ValidatedJson,
+    },
+    repos::repository_traits::Repository,
+    traits::to_dto::ToDto,
+};
+
+pub async fn get_product_links(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<ProductLinkDto>>, AppError> {
+    app_state.product_repo.get_by_id(id).await?;
+
+    let links = app_state
+        .product_link_repo
+        .get_for_product(id, None)
+        .await?;
+
+    Ok(Json(links.iter().map(|link| link.to_dto()).collect()))
+}
+
+pub async fn set_product_links(
+    State(app_state): State<Arc<AppState>>,
+    Path((id, link_type)): Path<(Uuid, ProductLinkType)>,
+    ValidatedJson(set_product_links_dto): ValidatedJson<SetProductLinksDto>,
+) -> Result<Json<Vec<ProductLinkDto>>, AppError> {
+    let links = app_state
+        .product_link_repo
+        .set(id, link_type, &set_product_links_dto)
+        .await?;
+
+    Ok(Json(links.iter().map(|link| link.to_dto()).collect()))
+}
+
+pub async fn delete_product_link(
+    State(app_state): State<Arc<AppState>>,
+    Path((id, link_type, linked_product_id)): Path<(Uuid, ProductLinkType, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    app_state
+        .product_link_repo
+        .delete(id, link_type, linked_product_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
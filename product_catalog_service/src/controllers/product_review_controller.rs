@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    dtos::{
+        create_product_review_dto::CreateProductReviewDto, moderate_review_dto::ModerateReviewDto,
+        product_review_dto::ProductReviewDto,
+    },
+    models::{
+        app_error::AppError, app_state::AppState, product_review::ReviewStatus,
+        product_visibility::ProductVisibility, review_query::ReviewQuery,
+    },
+    traits::to_dto::ToDto,
+};
+
+/// Approved reviews of the product unless another `status` is asked for.
+pub async fn get_product_reviews(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(review_query): Query<ReviewQuery>,
+) -> Result<Json<Vec<ProductReviewDto>>, AppError> {
+    app_state
+        .product_repo
+        .get_visible(id, ProductVisibility::Public)
+        .await?;
+
+    let reviews = app_state
+        .product_review_repo
+        .get_for_product(id, review_query.status.unwrap_or(ReviewStatus::Approved))
+        .await?;
+
+    Ok(Json(reviews.iter().map(|r| r.to_dto()).collect()))
+}
+
+pub async fn create_product_review(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(create_product_review_dto): Json<CreateProductReviewDto>,
+) -> Result<impl IntoResponse, AppError> {
+    app_state
+        .product_repo
+        .get_visible(id, ProductVisibility::Public)
+        .await?;
+
+    let review = app_state
+        .product_review_repo
+        .create(id, &create_product_review_dto)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(review.to_dto())))
+}
+
+/// The moderation queue: pending reviews unless another `status` is asked for.
+pub async fn get_reviews(
+    State(app_state): State<Arc<AppState>>,
+    Query(review_query): Query<ReviewQuery>,
+) -> Result<Json<Vec<ProductReviewDto>>, AppError> {
+    let reviews = app_state
+        .product_review_repo
+        .get_by_status(review_query.status.unwrap_or(ReviewStatus::Pending))
+        .await?;
+
+    Ok(Json(reviews.iter().map(|r| r.to_dto()).collect()))
+}
+
+pub async fn get_review(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ProductReviewDto>, AppError> {
+    let review = app_state.product_review_repo.get_by_id(id).await?;
+
+    Ok(Json(review.to_dto()))
+}
+
+pub async fn moderate_review(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(moderate_review_dto): Json<ModerateReviewDto>,
+) -> Result<Json<ProductReviewDto>, AppError> {
+    let review = app_state
+        .product_review_repo
+        .moderate(id, &moderate_review_dto)
+        .await?;
+
+    Ok(Json(review.to_dto()))
+}
+
+pub async fn delete_review(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    app_state.product_review_repo.delete(id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
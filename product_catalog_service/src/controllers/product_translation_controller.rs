@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use uuid::Uuid;
+
+use crate::{
+    dtos::{
+        product_translation_dto::ProductTranslationDto,
+        set_product_translation_dto::SetProductTranslationDto,
+    },
+    models::{app_error::AppError, app_state::AppState, validated_json::ValidatedJson},
+    repos::repository_traits::Repository,
+    traits::to_dto::ToDto,
+    utility::locale::normalize_locale,
+};
+
+/// Checks a locale of the path and brings it into its stored form.
+fn translation_locale(app_state: &AppState, locale: &str) -> Result<String, AppError> {
+    let locale = normalize_locale(locale)
+        .ok_or_else(|| AppError::Invalid(format!("'{}' is not a language tag", locale)))?;
+
+    if locale == app_state.locales.default_locale {
+        return Err(AppError::Invalid(format!(
+            "'{}' is the default locale; change the product itself",
+            locale
+        )));
+    }
+
+    Ok(locale)
+}
+
+pub async fn get_product_translations(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<ProductTranslationDto>>, AppError> {
+    app_state.product_repo.get_by_id(id).await?;
+
+    let translations = app_state
+        .product_translation_repo
+        .get_for_product(id)
+        .await?;
+
+    Ok(Json(translations.iter().map(|t| t.to_dto()).collect()))
+}
+
+pub async fn set_product_translation(
+    State(app_state): State<Arc<AppState>>,
+    Path((id, locale)): Path<(Uuid, String)>,
+    ValidatedJson(set_product_translation_dto): ValidatedJson<SetProductTranslationDto>,
+) -> Result<Json<ProductTranslationDto>, AppError> {
+    let locale = translation_locale(&app_state, &locale)?;
+
+    let translation = app_state
+        .product_translation_repo
+        .set(id, &locale, &set_product_translation_dto)
+        .await?;
+
+    Ok(Json(translation.to_dto()))
+}
+
+pub async fn delete_product_translation(
+    State(app_state): State<Arc<AppState>>,
+    Path((id, locale)): Path<(Uuid, String)>,
+) -> Result<StatusCode, AppError> {
+    let locale = translation_locale(&app_state, &locale)?;
+
+    app_state
+        .product_translation_repo
+        .delete(id, &locale)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
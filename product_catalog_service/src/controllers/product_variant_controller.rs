@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    dtos::{
+        create_product_variant_dto::CreateProductVariantDto, product_option_dto::ProductOptionDto,
+        product_variant_dto::ProductVariantDto,
+        update_product_variant_dto::UpdateProductVariantDto,
+    },
+    models::{app_error::AppError, app_state::AppState},
+    repos::repository_traits::Repository,
+    traits::to_dto::ToDto,
+};
+
+pub async fn get_product_options(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<ProductOptionDto>>, AppError> {
+    app_state.product_repo.get_by_id(id).await?;
+
+    let options = app_state.product_variant_repo.get_options(&[id]).await?;
+
+    Ok(Json(options.iter().map(|o| o.to_dto()).collect()))
+}
+
+pub async fn set_product_options(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(product_option_dtos): Json<Vec<ProductOptionDto>>,
+) -> Result<Json<Vec<ProductOptionDto>>, AppError> {
+    let options = app_state
+        .product_variant_repo
+        .set_options(id, &product_option_dtos)
+        .await?;
+
+    Ok(Json(options.iter().map(|o| o.to_dto()).collect()))
+}
+
+pub async fn get_product_variants(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<ProductVariantDto>>, AppError> {
+    app_state.product_repo.get_by_id(id).await?;
+
+    let variants = app_state.product_variant_repo.get_variants(&[id]).await?;
+
+    Ok(Json(variants.iter().map(|v| v.to_dto()).collect()))
+}
+
+pub async fn get_product_variant(
+    State(app_state): State<Arc<AppState>>,
+    Path((id, variant_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ProductVariantDto>, AppError> {
+    let variant = app_state
+        .product_variant_repo
+        .get_variant(id, variant_id)
+        .await?;
+
+    Ok(Json(variant.to_dto()))
+}
+
+pub async fn create_product_variant(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(create_product_variant_dto): Json<CreateProductVariantDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let variant = app_state
+        .product_variant_repo
+        .create_variant(id, &create_product_variant_dto)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(variant.to_dto())))
+}
+
+pub async fn update_product_variant(
+    State(app_state): State<Arc<AppState>>,
+    Path((id, variant_id)): Path<(Uuid, Uuid)>,
+    Json(update_product_variant_dto): Json<UpdateProductVariantDto>,
+) -> Result<Json<ProductVariantDto>, AppError> {
+    let variant = app_state
+        .product_variant_repo
+        .update_variant(id, variant_id, &update_product_variant_dto)
+        .await?;
+
+    Ok(Json(variant.to_dto()))
+}
+
+pub async fn delete_product_variant(
+    State(app_state): State<Arc<AppState>>,
+    Path((id, variant_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    app_state
+        .product_variant_repo
+        .delete_variant(id, variant_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
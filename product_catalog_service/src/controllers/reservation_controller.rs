@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    dtos::{create_reservation_dto::CreateReservationDto, reservation_dto::ReservationDto},
+    models::{app_error::AppError, app_state::AppState, reservation::Reservation},
+    traits::to_dto::ToDto,
+};
+
+/// Longest lifetime a caller may ask for, one day.
+const MAX_TTL_SECONDS: i64 = 86_400;
+
+async fn to_reservation_dto(
+    app_state: &AppState,
+    reservation: &Reservation,
+) -> Result<ReservationDto, AppError> {
+    let items = app_state.reservation_repo.get_items(reservation.id).await?;
+
+    let mut reservation_dto: ReservationDto = reservation.to_dto();
+    reservation_dto.items = items.iter().map(|item| item.to_dto()).collect();
+
+    Ok(reservation_dto)
+}
+
+pub async fn create_reservation(
+    State(app_state): State<Arc<AppState>>,
+    Json(create_reservation_dto): Json<CreateReservationDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let ttl_seconds = create_reservation_dto
+        .ttl_seconds
+        .unwrap_or(app_state.reservation_ttl_seconds);
+    if !(1..=MAX_TTL_SECONDS).contains(&ttl_seconds) {
+        return Err(AppError::Invalid(format!(
+            "ttl_seconds must be between 1 and {}",
+            MAX_TTL_SECONDS
+        )));
+    }
+
+    let reservation = app_state
+        .reservation_repo
+        .reserve(&create_reservation_dto.items, ttl_seconds)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(to_reservation_dto(&app_state, &reservation).await?),
+    ))
+}
+
+pub async fn get_reservation(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ReservationDto>, AppError> {
+    let reservation = app_state.reservation_repo.get(id).await?;
+
+    Ok(Json(to_reservation_dto(&app_state, &reservation).await?))
+}
+
+pub async fn confirm_reservation(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ReservationDto>, AppError> {
+    let reservation = app_state.reservation_repo.confirm(id).await?;
+
+    Ok(Json(to_reservation_dto(&app_state, &reservation).await?))
+}
+
+pub async fn release_reservation(
+    State(app_state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ReservationDto>, AppError> {
+    let reservation = app_state.reservation_repo.release(id).await?;
+
+    Ok(Json(to_reservation_dto(&app_state, &reservation).await?))
+}
@@ -0,0 +1,68 @@
+use std::{sync::Arc, time::Duration};
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::header,
+    response::IntoResponse,
+};
+
+use crate::{
+    dtos::suggestions_dto::SuggestionsDto,
+    models::{app_error::AppError, app_state::AppState, suggest_query::SuggestQuery},
+    traits::to_dto::ToDto,
+};
+
+const DEFAULT_SUGGESTION_LIMIT: i64 = 8;
+const MAX_SUGGESTION_LIMIT: i64 = 20;
+
+/// Suggestions are called on every keystroke, so the lookup gets a fixed
+/// latency budget, enforced here and by the database. When it runs out an
+/// empty, `timed_out` answer is returned instead of an error so the search
+/// box keeps working.
+pub async fn suggest_products(
+    State(app_state): State<Arc<AppState>>,
+    Query(suggest_query): Query<SuggestQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let term = suggest_query.q.trim();
+    let limit = suggest_query
+        .limit
+        .unwrap_or(DEFAULT_SUGGESTION_LIMIT)
+        .clamp(1, MAX_SUGGESTION_LIMIT);
+
+    let mut suggestions_dto = SuggestionsDto {
+        products: Vec::new(),
+        categories: Vec::new(),
+        timed_out: false,
+    };
+
+    if !term.is_empty() {
+        let budget = Duration::from_millis(app_state.suggest_timeout_ms);
+
+        let suggestions =
+            app_state
+                .suggestion_repo
+                .suggest(term, limit, app_state.suggest_timeout_ms);
+
+        match tokio::time::timeout(budget, suggestions).await {
+            Ok(Ok(Some(suggestions))) => {
+                for suggestion in suggestions {
+                    match suggestion.kind.as_str() {
+                        "category" => suggestions_dto.categories.push(suggestion.to_dto()),
+                        _ => suggestions_dto.products.push(suggestion.to_dto()),
+                    }
+                }
+            }
+            Ok(Err(err)) => return Err(err),
+            Ok(Ok(None)) | Err(_) => {
+                tracing::warn!(term, "product suggestions exceeded their latency budget");
+                suggestions_dto.timed_out = true;
+            }
+        }
+    }
+
+    Ok((
+        [(header::CACHE_CONTROL, "public, max-age=60")],
+        Json(suggestions_dto),
+    ))
+}
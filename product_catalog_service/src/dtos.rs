@@ -1,3 +1,64 @@
+pub mod attribute_definition_dto;
+pub mod attribute_facet_dto;
+pub mod availability_facet_dto;
+pub mod bundle_component_dto;
+pub mod bundle_component_line_dto;
+pub mod category_dto;
+pub mod category_facet_dto;
+pub mod category_tree_dto;
+pub mod create_attribute_definition_dto;
+pub mod create_category_dto;
+pub mod create_price_list_dto;
 pub mod create_product_dto;
+pub mod create_product_review_dto;
+pub mod create_product_variant_dto;
+pub mod create_reservation_dto;
+pub mod create_scheduled_price_change_dto;
+pub mod create_warehouse_dto;
+pub mod created_facet_dto;
+pub mod facet_value_dto;
+pub mod import_job_dto;
+pub mod import_row_error_dto;
+pub mod inventory_adjustment_dto;
+pub mod inventory_audit_dto;
+pub mod inventory_ledger_entry_dto;
+pub mod inventory_level_dto;
+pub mod low_stock_threshold_dto;
+pub mod moderate_review_dto;
+pub mod move_category_dto;
+pub mod price_bucket_dto;
+pub mod price_facet_dto;
+pub mod price_history_entry_dto;
+pub mod price_list_dto;
+pub mod price_list_entry_dto;
+pub mod product_bundle_dto;
+pub mod product_categories_dto;
 pub mod product_dto;
+pub mod product_facets_dto;
+pub mod product_image_dto;
+pub mod product_link_dto;
+pub mod product_option_dto;
+pub mod product_review_dto;
+pub mod product_search_match_dto;
+pub mod product_translation_dto;
+pub mod product_variant_dto;
+pub mod related_product_dto;
+pub mod reorder_product_images_dto;
+pub mod reservation_dto;
+pub mod reservation_item_dto;
+pub mod reservation_line_dto;
+pub mod scheduled_price_change_dto;
+pub mod set_price_dto;
+pub mod set_product_bundle_dto;
+pub mod set_product_links_dto;
+pub mod set_product_translation_dto;
+pub mod sku_availability_dto;
+pub mod suggestion_dto;
+pub mod suggestions_dto;
+pub mod thumbnail_dto;
+pub mod update_attribute_definition_dto;
+pub mod update_category_dto;
+pub mod update_price_list_dto;
 pub mod update_product_dto;
+pub mod update_product_variant_dto;
+pub mod warehouse_dto;
@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::attribute_definition::AttributeType;
+
+#[derive(Debug, Serialize)]
+pub struct AttributeDefinitionDto {
+    pub id: Uuid,
+    /// Category the definition is attached to; may be an ancestor of the
+    /// category it was listed for.
+    pub category_id: Uuid,
+    pub name: String,
+    pub attribute_type: AttributeType,
+    pub unit: Option<String>,
+    pub required: bool,
+    pub allowed_values: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,9 @@
+use serde::Serialize;
+
+use crate::dtos::facet_value_dto::FacetValueDto;
+
+#[derive(Debug, Serialize)]
+pub struct AttributeFacetDto {
+    pub name: String,
+    pub values: Vec<FacetValueDto>,
+}
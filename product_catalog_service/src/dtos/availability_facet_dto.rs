@@ -0,0 +1,7 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct AvailabilityFacetDto {
+    pub in_stock: i64,
+    pub out_of_stock: i64,
+}
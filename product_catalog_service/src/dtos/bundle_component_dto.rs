@@ -0,0 +1,15 @@
+use bigdecimal::BigDecimal;
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct BundleComponentDto {
+    pub product_id: Uuid,
+    pub name: String,
+    pub slug: String,
+    pub price: BigDecimal,
+    pub currency: String,
+    /// Units of the component in one bundle.
+    pub quantity: i32,
+    pub available: i64,
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Serialize, Validate)]
+pub struct BundleComponentLineDto {
+    pub product_id: Uuid,
+    #[validate(range(min = 1))]
+    pub quantity: i32,
+}
@@ -0,0 +1,9 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct CategoryTreeDto {
+    pub id: Uuid,
+    pub name: String,
+    pub children: Vec<CategoryTreeDto>,
+}
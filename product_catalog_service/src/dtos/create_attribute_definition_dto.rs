@@ -0,0 +1,15 @@
+use serde::Deserialize;
+
+use crate::models::attribute_definition::AttributeType;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateAttributeDefinitionDto {
+    pub name: String,
+    pub attribute_type: AttributeType,
+    pub unit: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+    /// Values an `enum` attribute may take; must be empty for other types.
+    #[serde(default)]
+    pub allowed_values: Vec<String>,
+}
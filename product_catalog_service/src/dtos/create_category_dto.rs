@@ -0,0 +1,8 @@
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCategoryDto {
+    pub name: String,
+    pub parent_id: Option<Uuid>,
+}
@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct CreatePriceListDto {
+    pub name: String,
+    pub currency: String,
+    /// Leave out to offer the prices to every customer.
+    pub customer_group: Option<String>,
+    /// Breaks ties between lists that apply to the same read; highest wins.
+    #[serde(default)]
+    pub priority: i32,
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_to: Option<DateTime<Utc>>,
+}
@@ -1,10 +1,40 @@
+use std::collections::BTreeMap;
+
 use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
+use serde_json::Value;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::product::ProductStatus;
+use crate::utility::validation::{validate_not_blank, validate_price};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct CreateProductDto {
+    /// Identifier in the merchandisers' own systems; unique.
+    #[validate(length(min = 1, max = 255))]
+    pub external_id: Option<String>,
+    #[validate(length(max = 255), custom(function = "validate_not_blank"))]
     pub name: String,
+    #[validate(length(max = 5000))]
     pub description: Option<String>,
+    #[validate(custom(function = "validate_price"))]
     pub price: BigDecimal,
+    /// Currency of `price` and of the variant prices; defaults to the
+    /// configured `default_currency`.
+    pub currency: Option<String>,
+    #[validate(url)]
     pub image_url: Option<String>,
+    /// Values for the attributes defined by the product's categories.
+    #[serde(default)]
+    pub attributes: BTreeMap<String, Value>,
+    /// Categories to file the product under; their attribute schema applies.
+    #[serde(default)]
+    pub category_ids: Vec<Uuid>,
+    /// Defaults to `draft`.
+    pub status: Option<ProductStatus>,
+    /// When an active product goes public; now if left out. A later time
+    /// schedules the publication.
+    pub published_at: Option<DateTime<Utc>>,
 }
@@ -0,0 +1,15 @@
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateProductReviewDto {
+    /// User id of the customer writing the review.
+    pub author_id: Uuid,
+    /// Stars from 1 to 5.
+    pub rating: i16,
+    pub title: String,
+    pub body: Option<String>,
+    /// Set by the caller when the author is known to have bought the product.
+    #[serde(default)]
+    pub verified_purchase: bool,
+}
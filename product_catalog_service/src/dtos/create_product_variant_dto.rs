@@ -0,0 +1,15 @@
+use std::collections::BTreeMap;
+
+use bigdecimal::BigDecimal;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateProductVariantDto {
+    pub sku: String,
+    pub price: Option<BigDecimal>,
+    pub barcode: Option<String>,
+    pub weight_grams: Option<i32>,
+    pub image_url: Option<String>,
+    #[serde(default)]
+    pub options: BTreeMap<String, String>,
+}
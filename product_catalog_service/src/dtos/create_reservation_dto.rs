@@ -0,0 +1,10 @@
+use serde::Deserialize;
+
+use crate::dtos::reservation_line_dto::ReservationLineDto;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateReservationDto {
+    pub items: Vec<ReservationLineDto>,
+    /// Overrides the configured reservation lifetime.
+    pub ttl_seconds: Option<i64>,
+}
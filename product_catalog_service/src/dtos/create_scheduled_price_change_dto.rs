@@ -0,0 +1,17 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::utility::validation::validate_price;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateScheduledPriceChangeDto {
+    /// New base price, in the product's currency.
+    #[validate(custom(function = "validate_price"))]
+    pub price: BigDecimal,
+    pub starts_at: DateTime<Utc>,
+    /// Turns the change into a sale; the previous price returns at this time.
+    pub ends_at: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+}
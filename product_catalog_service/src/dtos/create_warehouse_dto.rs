@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWarehouseDto {
+    pub code: String,
+    pub name: String,
+}
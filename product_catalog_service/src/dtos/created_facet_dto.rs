@@ -0,0 +1,10 @@
+use serde::Serialize;
+
+/// Products created within the trailing day, week, month and year.
+#[derive(Debug, Serialize)]
+pub struct CreatedFacetDto {
+    pub last_day: i64,
+    pub last_week: i64,
+    pub last_month: i64,
+    pub last_year: i64,
+}
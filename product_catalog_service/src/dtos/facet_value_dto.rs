@@ -0,0 +1,7 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct FacetValueDto {
+    pub value: String,
+    pub count: i64,
+}
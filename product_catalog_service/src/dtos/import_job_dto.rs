@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::dtos::import_row_error_dto::ImportRowErrorDto;
+use crate::models::import_job::{ImportFormat, ImportJobStatus};
+
+#[derive(Debug, Serialize)]
+pub struct ImportJobDto {
+    pub id: Uuid,
+    pub format: ImportFormat,
+    pub status: ImportJobStatus,
+    pub total_rows: i32,
+    pub processed_rows: i32,
+    pub created_rows: i32,
+    pub updated_rows: i32,
+    pub failed_rows: i32,
+    /// Rows that were not applied; capped, `failed_rows` has the full count.
+    pub errors: Vec<ImportRowErrorDto>,
+    pub failure: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
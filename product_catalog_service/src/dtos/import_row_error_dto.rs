@@ -0,0 +1,10 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct ImportRowErrorDto {
+    /// Line of the row in the file, starting at 1.
+    pub row: i64,
+    pub external_id: Option<String>,
+    pub sku: Option<String>,
+    pub message: String,
+}
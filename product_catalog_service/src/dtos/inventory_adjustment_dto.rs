@@ -0,0 +1,12 @@
+use serde::Deserialize;
+
+use crate::models::inventory_ledger_entry::InventoryReason;
+
+#[derive(Debug, Deserialize)]
+pub struct InventoryAdjustmentDto {
+    pub sku: String,
+    pub warehouse_code: String,
+    pub quantity_delta: i32,
+    pub reason: InventoryReason,
+    pub note: Option<String>,
+}
@@ -0,0 +1,10 @@
+use serde::Serialize;
+
+/// A stock level that disagrees with the sum of its ledger entries.
+#[derive(Debug, Serialize)]
+pub struct InventoryAuditDto {
+    pub sku: String,
+    pub warehouse_code: String,
+    pub on_hand: i32,
+    pub ledger_on_hand: i64,
+}
@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::models::inventory_ledger_entry::InventoryReason;
+
+#[derive(Debug, Serialize)]
+pub struct InventoryLedgerEntryDto {
+    pub id: i64,
+    pub warehouse_code: String,
+    pub quantity_delta: i32,
+    pub reason: InventoryReason,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct InventoryLevelDto {
+    pub sku: String,
+    pub warehouse_code: String,
+    pub on_hand: i32,
+    pub reserved: i32,
+    pub available: i32,
+    pub low_stock_threshold: i32,
+    pub low_stock: bool,
+    pub updated_at: DateTime<Utc>,
+}
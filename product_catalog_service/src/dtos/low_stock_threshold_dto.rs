@@ -0,0 +1,6 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct LowStockThresholdDto {
+    pub low_stock_threshold: i32,
+}
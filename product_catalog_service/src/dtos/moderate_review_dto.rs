@@ -0,0 +1,10 @@
+use serde::Deserialize;
+
+use crate::models::product_review::ReviewStatus;
+
+#[derive(Debug, Deserialize)]
+pub struct ModerateReviewDto {
+    pub status: ReviewStatus,
+    /// Reason shown to the author, e.g. why a review was rejected.
+    pub moderation_note: Option<String>,
+}
@@ -0,0 +1,11 @@
+use bigdecimal::BigDecimal;
+use serde::Serialize;
+
+/// Products priced from `from` (inclusive) up to `to` (exclusive). A missing
+/// bound leaves that end of the bucket open.
+#[derive(Debug, Serialize)]
+pub struct PriceBucketDto {
+    pub from: Option<BigDecimal>,
+    pub to: Option<BigDecimal>,
+    pub count: i64,
+}
@@ -0,0 +1,12 @@
+use bigdecimal::BigDecimal;
+use serde::Serialize;
+
+use crate::dtos::price_bucket_dto::PriceBucketDto;
+
+#[derive(Debug, Serialize)]
+pub struct PriceFacetDto {
+    pub min: Option<BigDecimal>,
+    pub max: Option<BigDecimal>,
+    /// Only buckets holding at least one product are listed.
+    pub buckets: Vec<PriceBucketDto>,
+}
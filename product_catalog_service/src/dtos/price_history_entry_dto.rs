@@ -0,0 +1,17 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::price_history_entry::PriceChangeSource;
+
+#[derive(Debug, Serialize)]
+pub struct PriceHistoryEntryDto {
+    pub price: BigDecimal,
+    pub currency: String,
+    pub previous_price: Option<BigDecimal>,
+    pub previous_currency: Option<String>,
+    pub source: PriceChangeSource,
+    pub scheduled_price_change_id: Option<Uuid>,
+    pub changed_at: DateTime<Utc>,
+}
@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct PriceListDto {
+    pub id: Uuid,
+    pub name: String,
+    pub currency: String,
+    pub customer_group: Option<String>,
+    pub priority: i32,
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_to: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
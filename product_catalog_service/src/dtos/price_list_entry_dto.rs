@@ -0,0 +1,11 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct PriceListEntryDto {
+    pub product_id: Uuid,
+    pub price: BigDecimal,
+    pub updated_at: DateTime<Utc>,
+}
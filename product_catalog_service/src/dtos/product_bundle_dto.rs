@@ -0,0 +1,13 @@
+use bigdecimal::BigDecimal;
+use serde::Serialize;
+
+use crate::dtos::bundle_component_dto::BundleComponentDto;
+
+#[derive(Debug, Serialize)]
+pub struct ProductBundleDto {
+    /// Set when the bundle's price is derived from its components' prices.
+    pub discount_percent: Option<BigDecimal>,
+    /// Bundles that can be put together from the components' stock.
+    pub available_quantity: i64,
+    pub components: Vec<BundleComponentDto>,
+}
@@ -0,0 +1,7 @@
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct ProductCategoriesDto {
+    pub category_ids: Vec<Uuid>,
+}
@@ -1,15 +1,56 @@
+use std::collections::BTreeMap;
+
 use bigdecimal::BigDecimal;
 use chrono::{DateTime, Utc};
 use serde::Serialize;
+use serde_json::Value;
 use uuid::Uuid;
 
+use crate::dtos::{
+    product_bundle_dto::ProductBundleDto, product_image_dto::ProductImageDto,
+    product_option_dto::ProductOptionDto, product_search_match_dto::ProductSearchMatchDto,
+    product_variant_dto::ProductVariantDto,
+};
+use crate::models::product::ProductStatus;
+
 #[derive(Debug, Serialize)]
 pub struct ProductDto {
     pub id: Uuid,
+    pub external_id: Option<String>,
     pub name: String,
+    /// Canonical slug; slugs the product had before a rename redirect to it.
+    pub slug: String,
     pub description: Option<String>,
     pub price: BigDecimal,
+    pub currency: String,
+    /// Price list the price comes from; `None` for the product's base price.
+    pub price_list_id: Option<Uuid>,
+    /// URL of the first uploaded image, if there is one.
     pub image_url: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub version: i64,
+    pub attributes: BTreeMap<String, Value>,
+    /// Average of the approved review ratings, 0 while there are none.
+    pub rating: BigDecimal,
+    pub rating_count: i32,
+    pub status: ProductStatus,
+    /// When the product goes or went public.
+    pub published_at: Option<DateTime<Utc>>,
+    /// Only set on deleted products.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub options: Vec<ProductOptionDto>,
+    pub variants: Vec<ProductVariantDto>,
+    pub images: Vec<ProductImageDto>,
+    /// Components and derived availability; only present on bundles.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bundle: Option<ProductBundleDto>,
+    /// Locale of the translation `name`, `description` and `slug` come from;
+    /// left out when they are the product's own.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// Only present on search results.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<ProductSearchMatchDto>,
 }
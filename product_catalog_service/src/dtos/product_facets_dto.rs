@@ -0,0 +1,17 @@
+use serde::Serialize;
+
+use crate::dtos::{
+    attribute_facet_dto::AttributeFacetDto, availability_facet_dto::AvailabilityFacetDto,
+    category_facet_dto::CategoryFacetDto, created_facet_dto::CreatedFacetDto,
+    price_facet_dto::PriceFacetDto,
+};
+
+/// Product counts along each filter dimension of the product list.
+#[derive(Debug, Serialize)]
+pub struct ProductFacetsDto {
+    pub categories: Vec<CategoryFacetDto>,
+    pub attributes: Vec<AttributeFacetDto>,
+    pub price: PriceFacetDto,
+    pub availability: AvailabilityFacetDto,
+    pub created: CreatedFacetDto,
+}
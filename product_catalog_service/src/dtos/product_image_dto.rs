@@ -0,0 +1,19 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::dtos::thumbnail_dto::ThumbnailDto;
+
+#[derive(Debug, Serialize)]
+pub struct ProductImageDto {
+    pub id: Uuid,
+    pub position: i32,
+    pub url: String,
+    pub content_type: String,
+    pub byte_size: i64,
+    pub width: i32,
+    pub height: i32,
+    pub alt_text: Option<String>,
+    pub thumbnails: Vec<ThumbnailDto>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::product_link::ProductLinkType;
+
+#[derive(Debug, Serialize)]
+pub struct ProductLinkDto {
+    /// The linked product.
+    pub product_id: Uuid,
+    pub link_type: ProductLinkType,
+    pub position: i32,
+    pub name: String,
+    pub slug: String,
+    pub created_at: DateTime<Utc>,
+}
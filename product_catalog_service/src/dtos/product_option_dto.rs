@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProductOptionDto {
+    pub name: String,
+    pub values: Vec<String>,
+}
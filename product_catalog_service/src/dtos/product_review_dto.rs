@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::product_review::ReviewStatus;
+
+#[derive(Debug, Serialize)]
+pub struct ProductReviewDto {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub author_id: Uuid,
+    pub rating: i16,
+    pub title: String,
+    pub body: Option<String>,
+    pub verified_purchase: bool,
+    pub status: ReviewStatus,
+    pub moderation_note: Option<String>,
+    pub moderated_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,9 @@
+use serde::Serialize;
+
+/// Why a product matched a search. Highlighted terms are wrapped in `<mark>`.
+#[derive(Debug, Serialize)]
+pub struct ProductSearchMatchDto {
+    pub rank: f32,
+    pub name_highlight: String,
+    pub description_highlight: Option<String>,
+}
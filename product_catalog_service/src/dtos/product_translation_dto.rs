@@ -0,0 +1,12 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct ProductTranslationDto {
+    pub locale: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub slug: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
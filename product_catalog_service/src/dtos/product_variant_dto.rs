@@ -0,0 +1,20 @@
+use std::collections::BTreeMap;
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct ProductVariantDto {
+    pub id: Uuid,
+    pub sku: String,
+    /// Overrides the product price when set.
+    pub price: Option<BigDecimal>,
+    pub barcode: Option<String>,
+    pub weight_grams: Option<i32>,
+    pub image_url: Option<String>,
+    pub options: BTreeMap<String, String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,13 @@
+use serde::Serialize;
+
+use crate::dtos::product_dto::ProductDto;
+use crate::models::product_link::ProductLinkType;
+
+#[derive(Debug, Serialize)]
+pub struct RelatedProductDto {
+    /// Left out for products picked from the same categories because the
+    /// product has no links.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_type: Option<ProductLinkType>,
+    pub product: ProductDto,
+}
@@ -0,0 +1,8 @@
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct ReorderProductImagesDto {
+    /// Every image of the product, in the new order.
+    pub image_ids: Vec<Uuid>,
+}
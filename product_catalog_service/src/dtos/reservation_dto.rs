@@ -0,0 +1,17 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    dtos::reservation_item_dto::ReservationItemDto, models::reservation::ReservationStatus,
+};
+
+#[derive(Debug, Serialize)]
+pub struct ReservationDto {
+    pub id: Uuid,
+    pub status: ReservationStatus,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub items: Vec<ReservationItemDto>,
+}
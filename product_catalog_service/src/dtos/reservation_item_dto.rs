@@ -0,0 +1,8 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct ReservationItemDto {
+    pub sku: String,
+    pub warehouse_code: String,
+    pub quantity: i32,
+}
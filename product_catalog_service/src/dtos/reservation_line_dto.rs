@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct ReservationLineDto {
+    pub sku: String,
+    pub quantity: i32,
+}
@@ -0,0 +1,20 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::scheduled_price_change::ScheduledPriceStatus;
+
+#[derive(Debug, Serialize)]
+pub struct ScheduledPriceChangeDto {
+    pub id: Uuid,
+    pub price: BigDecimal,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+    pub status: ScheduledPriceStatus,
+    pub previous_price: Option<BigDecimal>,
+    pub failure: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,7 @@
+use bigdecimal::BigDecimal;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct SetPriceDto {
+    pub price: BigDecimal,
+}
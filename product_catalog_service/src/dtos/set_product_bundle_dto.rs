@@ -0,0 +1,17 @@
+use bigdecimal::BigDecimal;
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::dtos::bundle_component_line_dto::BundleComponentLineDto;
+use crate::utility::validation::validate_discount_percent;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetProductBundleDto {
+    /// Prices the bundle at its components' prices less this percentage.
+    /// Left out, the bundle keeps the price set on the product.
+    #[validate(custom(function = "validate_discount_percent"))]
+    pub discount_percent: Option<BigDecimal>,
+    /// In the order they are listed in.
+    #[validate(length(min = 1), nested)]
+    pub components: Vec<BundleComponentLineDto>,
+}
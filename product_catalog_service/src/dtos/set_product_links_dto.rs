@@ -0,0 +1,11 @@
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetProductLinksDto {
+    /// Linked products in the order they are shown in; empty removes every
+    /// link of the type.
+    #[validate(length(max = 100))]
+    pub product_ids: Vec<Uuid>,
+}
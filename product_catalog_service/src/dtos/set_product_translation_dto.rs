@@ -0,0 +1,13 @@
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::utility::validation::validate_not_blank;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetProductTranslationDto {
+    #[validate(length(max = 255), custom(function = "validate_not_blank"))]
+    pub name: String,
+    /// Left out, the product's own description is shown.
+    #[validate(length(max = 5000))]
+    pub description: Option<String>,
+}
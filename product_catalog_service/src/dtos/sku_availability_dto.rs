@@ -0,0 +1,13 @@
+use serde::Serialize;
+
+use crate::dtos::inventory_level_dto::InventoryLevelDto;
+
+#[derive(Debug, Serialize)]
+pub struct SkuAvailabilityDto {
+    pub sku: String,
+    pub on_hand: i64,
+    pub reserved: i64,
+    pub available: i64,
+    pub in_stock: bool,
+    pub warehouses: Vec<InventoryLevelDto>,
+}
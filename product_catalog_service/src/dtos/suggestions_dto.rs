@@ -0,0 +1,11 @@
+use serde::Serialize;
+
+use crate::dtos::suggestion_dto::SuggestionDto;
+
+#[derive(Debug, Serialize)]
+pub struct SuggestionsDto {
+    pub products: Vec<SuggestionDto>,
+    pub categories: Vec<SuggestionDto>,
+    /// Set when the lookup ran out of its latency budget and was cut short.
+    pub timed_out: bool,
+}
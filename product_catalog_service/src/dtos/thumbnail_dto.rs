@@ -0,0 +1,10 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct ThumbnailDto {
+    /// Bounding box in pixels the image was fitted into.
+    pub size: i32,
+    pub width: i32,
+    pub height: i32,
+    pub url: String,
+}
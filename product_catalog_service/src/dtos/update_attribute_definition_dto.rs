@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateAttributeDefinitionDto {
+    pub unit: Option<String>,
+    pub required: Option<bool>,
+    pub allowed_values: Option<Vec<String>>,
+}
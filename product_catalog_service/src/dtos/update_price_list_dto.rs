@@ -0,0 +1,10 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct UpdatePriceListDto {
+    pub name: Option<String>,
+    pub priority: Option<i32>,
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_to: Option<DateTime<Utc>>,
+}
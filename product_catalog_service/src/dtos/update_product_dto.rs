@@ -1,10 +1,31 @@
+use std::collections::BTreeMap;
+
 use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
+use serde_json::Value;
+use validator::Validate;
+
+use crate::models::product::ProductStatus;
+use crate::utility::validation::{validate_not_blank, validate_price};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct UpdateProductDto {
+    #[validate(length(min = 1, max = 255))]
+    pub external_id: Option<String>,
+    #[validate(length(max = 255), custom(function = "validate_not_blank"))]
     pub name: Option<String>,
+    #[validate(length(max = 5000))]
     pub description: Option<String>,
+    #[validate(custom(function = "validate_price"))]
     pub price: Option<BigDecimal>,
+    pub currency: Option<String>,
+    #[validate(url)]
     pub image_url: Option<String>,
+    /// Attribute values to change; `null` removes a value.
+    pub attributes: Option<BTreeMap<String, Value>>,
+    pub status: Option<ProductStatus>,
+    /// Reschedules the publication; activating a product without one
+    /// publishes it now.
+    pub published_at: Option<DateTime<Utc>>,
 }
@@ -0,0 +1,14 @@
+use std::collections::BTreeMap;
+
+use bigdecimal::BigDecimal;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateProductVariantDto {
+    pub sku: Option<String>,
+    pub price: Option<BigDecimal>,
+    pub barcode: Option<String>,
+    pub weight_grams: Option<i32>,
+    pub image_url: Option<String>,
+    pub options: Option<BTreeMap<String, String>>,
+}
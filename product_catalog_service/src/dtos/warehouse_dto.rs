@@ -0,0 +1,11 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct WarehouseDto {
+    pub id: Uuid,
+    pub code: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,2 @@
+pub mod log_event_sink;
+pub mod webhook_event_sink;
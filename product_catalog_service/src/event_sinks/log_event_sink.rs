@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+
+use crate::{
+    models::{app_error::AppError, product_event::ProductEvent},
+    traits::event_sink::EventSink,
+};
+
+/// Writes events to the service's log instead of publishing them.
+pub struct LogEventSink;
+
+#[async_trait]
+impl EventSink for LogEventSink {
+    async fn publish(&self, event: &ProductEvent) -> Result<(), AppError> {
+        tracing::info!(
+            event_id = event.id,
+            product_id = %event.product_id,
+            sequence = event.sequence,
+            event_type = ?event.event_type,
+            payload = %event.payload,
+            "product event"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use std::{error::Error, time::Duration};
+
+use async_trait::async_trait;
+use reqwest::Client;
+
+use crate::{
+    models::{app_error::AppError, product_event::ProductEvent},
+    traits::event_sink::EventSink,
+};
+
+/// How long the receiver has to acknowledge an event.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// `POST`s each event as JSON to a URL. Any 2xx response acknowledges it.
+pub struct WebhookEventSink {
+    client: Client,
+    url: String,
+}
+
+impl WebhookEventSink {
+    pub fn new(url: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(WebhookEventSink {
+            client: Client::builder().timeout(REQUEST_TIMEOUT).build()?,
+            url: url.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl EventSink for WebhookEventSink {
+    async fn publish(&self, event: &ProductEvent) -> Result<(), AppError> {
+        let response = self
+            .client
+            .post(&self.url)
+            .json(event)
+            .send()
+            .await
+            .map_err(|err| {
+                tracing::warn!(error = %err, event_id = event.id, "failed to deliver product event");
+                AppError::ServiceUnavailable
+            })?;
+
+        if !response.status().is_success() {
+            tracing::warn!(
+                status = %response.status(),
+                event_id = event.id,
+                "product event was not accepted"
+            );
+            return Err(AppError::ServiceUnavailable);
+        }
+
+        Ok(())
+    }
+}
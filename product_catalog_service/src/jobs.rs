@@ -0,0 +1,5 @@
+pub mod outbox_relay;
+pub mod popularity_refresher;
+pub mod price_scheduler;
+pub mod product_importer;
+pub mod reservation_reaper;
@@ -0,0 +1,33 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{repos::outbox_repo::OutboxRepo, traits::event_sink::EventSink};
+
+/// Periodically publishes the product events in the outbox to the sink and
+/// purges the ones published longer than `retention_days` ago.
+pub async fn run_outbox_relay(
+    outbox_repo: Arc<OutboxRepo>,
+    sink: Arc<dyn EventSink>,
+    interval: Duration,
+    retention_days: i32,
+) {
+    let mut ticker = tokio::time::interval(interval);
+
+    loop {
+        ticker.tick().await;
+
+        loop {
+            match outbox_repo.relay(sink.as_ref()).await {
+                Ok(0) => break,
+                Ok(count) => tracing::debug!(count, "published product events"),
+                Err(err) => {
+                    tracing::error!(error = ?err, "failed to relay product events");
+                    break;
+                }
+            }
+        }
+
+        if let Err(err) = outbox_repo.purge_published(retention_days).await {
+            tracing::error!(error = ?err, "failed to purge published product events");
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::repos::suggestion_repo::SuggestionRepo;
+
+/// Periodically recomputes the product popularity used to rank suggestions.
+pub async fn run_popularity_refresher(suggestion_repo: Arc<SuggestionRepo>, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+
+    loop {
+        ticker.tick().await;
+
+        if let Err(err) = suggestion_repo.refresh_popularity().await {
+            tracing::error!(error = ?err, "failed to refresh product popularity");
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::repos::scheduled_price_repo::ScheduledPriceRepo;
+
+/// Periodically applies scheduled price changes that are due and ends sales.
+pub async fn run_price_scheduler(
+    scheduled_price_repo: Arc<ScheduledPriceRepo>,
+    interval: Duration,
+) {
+    let mut ticker = tokio::time::interval(interval);
+
+    loop {
+        ticker.tick().await;
+
+        loop {
+            match scheduled_price_repo.apply_due().await {
+                Ok(0) => break,
+                Ok(count) => tracing::info!(count, "applied scheduled price changes"),
+                Err(err) => {
+                    tracing::error!(error = ?err, "failed to apply scheduled price changes");
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use uuid::Uuid;
+
+use crate::{
+    models::{
+        app_error::AppError,
+        import_job::{ImportFormat, ImportOutcome, ImportRowError},
+        product_import_row::ProductImportRow,
+    },
+    repos::{import_job_repo::ImportJobRepo, product_repo::ProductRepo},
+};
+
+/// Rows applied between two progress updates of the job.
+const PROGRESS_INTERVAL: usize = 50;
+
+/// Row errors kept in the report; later failures are only counted.
+const MAX_REPORTED_ERRORS: usize = 1000;
+
+fn row_error_message(err: AppError) -> String {
+    match err {
+        AppError::NotFound(msg)
+        | AppError::Invalid(msg)
+        | AppError::Conflict(msg)
+        | AppError::ConstraintViolation(msg) => msg,
+        AppError::ServiceUnavailable => "Database was unavailable".to_string(),
+        _ => "Internal Server Error".to_string(),
+    }
+}
+
+/// Tracks the rows applied since the last progress update.
+#[derive(Default)]
+struct Progress {
+    created: i32,
+    updated: i32,
+    failed: i32,
+    errors: Vec<ImportRowError>,
+    reported_errors: usize,
+}
+
+impl Progress {
+    fn pending(&self) -> usize {
+        (self.created + self.updated + self.failed) as usize
+    }
+
+    fn fail(&mut self, error: ImportRowError) {
+        self.failed += 1;
+        if self.reported_errors < MAX_REPORTED_ERRORS {
+            self.reported_errors += 1;
+            self.errors.push(error);
+        }
+    }
+
+    async fn flush(
+        &mut self,
+        import_job_repo: &ImportJobRepo,
+        job_id: Uuid,
+    ) -> Result<(), AppError> {
+        import_job_repo
+            .record_progress(
+                job_id,
+                self.created,
+                self.updated,
+                self.failed,
+                &self.errors,
+            )
+            .await?;
+
+        self.created = 0;
+        self.updated = 0;
+        self.failed = 0;
+        self.errors.clear();
+
+        Ok(())
+    }
+}
+
+/// Reads an uploaded file and applies its rows one by one, each in its own
+/// transaction, recording progress and row errors on the job as it goes.
+pub async fn run_product_import(
+    product_repo: Arc<ProductRepo>,
+    import_job_repo: Arc<ImportJobRepo>,
+    job_id: Uuid,
+    format: ImportFormat,
+    file: Bytes,
+) {
+    let rows = tokio::task::spawn_blocking(move || ProductImportRow::parse(format, &file)).await;
+
+    let rows = match rows {
+        Ok(Ok(rows)) => rows,
+        Ok(Err(err)) => {
+            let failure = row_error_message(err);
+            if let Err(err) = import_job_repo.finish(job_id, Some(&failure)).await {
+                tracing::error!(error = ?err, %job_id, "failed to record import failure");
+            }
+            return;
+        }
+        Err(err) => {
+            tracing::error!(error = %err, %job_id, "import parsing task failed");
+            if let Err(err) = import_job_repo
+                .finish(job_id, Some("The file could not be read"))
+                .await
+            {
+                tracing::error!(error = ?err, %job_id, "failed to record import failure");
+            }
+            return;
+        }
+    };
+
+    let result = async {
+        import_job_repo.start(job_id, rows.len() as i32).await?;
+
+        let mut progress = Progress::default();
+        for row in rows {
+            match row {
+                Ok(row) => match product_repo.import(&row).await {
+                    Ok(ImportOutcome::Created) => progress.created += 1,
+                    Ok(ImportOutcome::Updated) => progress.updated += 1,
+                    Err(err) => progress.fail(ImportRowError {
+                        row: row.line,
+                        external_id: row.product.external_id.clone(),
+                        sku: row.sku.clone(),
+                        message: row_error_message(err),
+                    }),
+                },
+                Err(invalid) => progress.fail(ImportRowError {
+                    row: invalid.line,
+                    external_id: invalid.external_id,
+                    sku: invalid.sku,
+                    message: invalid.message,
+                }),
+            }
+
+            if progress.pending() >= PROGRESS_INTERVAL {
+                progress.flush(&import_job_repo, job_id).await?;
+            }
+        }
+        progress.flush(&import_job_repo, job_id).await?;
+
+        import_job_repo.finish(job_id, None).await
+    }
+    .await;
+
+    match result {
+        Ok(()) => tracing::info!(%job_id, "product import finished"),
+        Err(err) => {
+            tracing::error!(error = ?err, %job_id, "product import failed");
+            if let Err(err) = import_job_repo
+                .finish(
+                    job_id,
+                    Some("The import was interrupted by a database error"),
+                )
+                .await
+            {
+                tracing::error!(error = ?err, %job_id, "failed to record import failure");
+            }
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::repos::reservation_repo::ReservationRepo;
+
+/// Periodically hands the stock of expired reservations back to inventory.
+pub async fn run_reservation_reaper(reservation_repo: Arc<ReservationRepo>, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+
+    loop {
+        ticker.tick().await;
+
+        loop {
+            match reservation_repo.expire_due().await {
+                Ok(0) => break,
+                Ok(count) => tracing::info!(count, "expired stock reservations"),
+                Err(err) => {
+                    tracing::error!(error = ?err, "failed to expire stock reservations");
+                    break;
+                }
+            }
+        }
+    }
+}
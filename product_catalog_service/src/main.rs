@@ -1,28 +1,53 @@
 mod config_utility;
 mod controllers;
 mod dtos;
+mod event_sinks;
+mod jobs;
 mod models;
 mod repos;
 mod routes;
 mod seeds;
+mod storage;
 mod traits;
 mod utility;
 
-use axum::{Router, extract::State, routing::get};
+use axum::{Router, extract::State, middleware, routing::get};
 use sqlx::PgPool;
-use std::sync::Arc;
+use std::{error::Error, sync::Arc, time::Duration};
 use tokio::net::TcpListener;
 
-use tower_http::trace::TraceLayer;
+use tower_http::{services::ServeDir, trace::TraceLayer};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use config_utility::load_config::load_config;
+use config_utility::{
+    config_loader::{EventSinkBackend, StorageBackend},
+    load_config::load_config,
+};
+use event_sinks::{log_event_sink::LogEventSink, webhook_event_sink::WebhookEventSink};
+use jobs::{
+    outbox_relay::run_outbox_relay, popularity_refresher::run_popularity_refresher,
+    price_scheduler::run_price_scheduler, reservation_reaper::run_reservation_reaper,
+};
 use seeds::product_seed::seeding_products_data;
+use storage::{local_storage::LocalStorage, s3_storage::S3Storage};
+use traits::{event_sink::EventSink, object_storage::ObjectStorage};
+use utility::{locale::LocaleSettings, request_id::propagate_request_id};
 
-use crate::{models::app_state::AppState, routes::product_routes::product_routes};
+use crate::{
+    models::app_state::AppState,
+    routes::{
+        admin_routes::admin_routes,
+        category_routes::category_routes,
+        inventory_routes::{inventory_routes, warehouse_routes},
+        price_list_routes::price_list_routes,
+        product_routes::product_routes,
+        reservation_routes::reservation_routes,
+        review_routes::review_routes,
+    },
+};
 
 #[tokio::main]
-async fn main() {
+async fn main() -> Result<(), Box<dyn Error>> {
     let env_filter = tracing_subscriber::EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| "product_catalog_service=debug,tower_http=debug".into());
 
@@ -50,7 +75,7 @@ async fn main() {
         Some(guard)
     };
 
-    let config = load_config().unwrap();
+    let config = load_config()?;
     let db_url = format!(
         "postgres://{}:{}@{}:{}/{}?options=-csearch_path={}",
         config.server_user,
@@ -63,21 +88,188 @@ async fn main() {
 
     let pg_pool = PgPool::connect(db_url.as_str()).await.unwrap();
 
-    seeding_products_data(&pg_pool).await.unwrap();
-
     let product_repo = Arc::new(repos::product_repo::ProductRepo {
         pool: pg_pool.clone(),
+        search_language: config.search_language.clone(),
+        default_currency: config.default_currency.clone(),
+    });
+
+    seeding_products_data(&product_repo).await.unwrap();
+
+    let reindexed = product_repo.sync_search_language().await.unwrap();
+    if reindexed > 0 {
+        tracing::info!(reindexed, "re-stemmed products for the configured search language");
+    }
+
+    let category_repo = Arc::new(repos::category_repo::CategoryRepo {
+        pool: pg_pool.clone(),
+    });
+
+    let attribute_repo = Arc::new(repos::attribute_repo::AttributeRepo {
+        pool: pg_pool.clone(),
+    });
+
+    let price_list_repo = Arc::new(repos::price_list_repo::PriceListRepo {
+        pool: pg_pool.clone(),
+    });
+
+    let price_history_repo = Arc::new(repos::price_history_repo::PriceHistoryRepo {
+        pool: pg_pool.clone(),
+    });
+
+    let scheduled_price_repo = Arc::new(repos::scheduled_price_repo::ScheduledPriceRepo {
+        pool: pg_pool.clone(),
+    });
+
+    tokio::spawn(run_price_scheduler(
+        scheduled_price_repo.clone(),
+        Duration::from_secs(config.price_scheduler_interval_seconds),
+    ));
+
+    let product_variant_repo = Arc::new(repos::product_variant_repo::ProductVariantRepo {
+        pool: pg_pool.clone(),
+    });
+
+    let product_image_repo = Arc::new(repos::product_image_repo::ProductImageRepo {
+        pool: pg_pool.clone(),
+    });
+
+    let image_storage: Arc<dyn ObjectStorage> = match config.storage_backend {
+        StorageBackend::Local => Arc::new(LocalStorage {
+            root: config.storage_local_root.clone().into(),
+            public_url: config.storage_public_url.clone(),
+        }),
+        StorageBackend::S3 => Arc::new(S3Storage::new(
+            config.s3_endpoint.as_deref().ok_or("s3_endpoint is not configured")?,
+            &config.s3_region,
+            config.s3_bucket.as_deref().ok_or("s3_bucket is not configured")?,
+            config.s3_access_key.as_deref().ok_or("s3_access_key is not configured")?,
+            config.s3_secret_key.as_deref().ok_or("s3_secret_key is not configured")?,
+            &config.storage_public_url,
+        )),
+    };
+
+    let product_review_repo = Arc::new(repos::product_review_repo::ProductReviewRepo {
+        pool: pg_pool.clone(),
+    });
+
+    let warehouse_repo = Arc::new(repos::warehouse_repo::WarehouseRepo {
+        pool: pg_pool.clone(),
     });
 
+    let inventory_repo = Arc::new(repos::inventory_repo::InventoryRepo {
+        pool: pg_pool.clone(),
+    });
+
+    let reservation_repo = Arc::new(repos::reservation_repo::ReservationRepo {
+        pool: pg_pool.clone(),
+    });
+
+    tokio::spawn(run_reservation_reaper(
+        reservation_repo.clone(),
+        Duration::from_secs(config.reservation_reaper_interval_seconds),
+    ));
+
+    let suggestion_repo = Arc::new(repos::suggestion_repo::SuggestionRepo {
+        pool: pg_pool.clone(),
+    });
+
+    tokio::spawn(run_popularity_refresher(
+        suggestion_repo.clone(),
+        Duration::from_secs(config.popularity_refresh_interval_seconds),
+    ));
+
+    let import_job_repo = Arc::new(repos::import_job_repo::ImportJobRepo {
+        pool: pg_pool.clone(),
+    });
+
+    let interrupted = import_job_repo.fail_interrupted().await.unwrap();
+    if interrupted > 0 {
+        tracing::warn!(interrupted, "failed product imports interrupted by the last shutdown");
+    }
+
+    let product_translation_repo =
+        Arc::new(repos::product_translation_repo::ProductTranslationRepo {
+            pool: pg_pool.clone(),
+        });
+
+    let product_bundle_repo = Arc::new(repos::product_bundle_repo::ProductBundleRepo {
+        pool: pg_pool.clone(),
+    });
+
+    let product_link_repo = Arc::new(repos::product_link_repo::ProductLinkRepo {
+        pool: pg_pool.clone(),
+    });
+
+    let event_sink: Arc<dyn EventSink> = match config.event_sink {
+        EventSinkBackend::Log => Arc::new(LogEventSink),
+        EventSinkBackend::Webhook => {
+            let url = config
+                .event_webhook_url
+                .as_deref()
+                .ok_or("event_webhook_url is not configured")?;
+            Arc::new(WebhookEventSink::new(url)?)
+        }
+    };
+
+    let outbox_repo = Arc::new(repos::outbox_repo::OutboxRepo {
+        pool: pg_pool.clone(),
+    });
+
+    tokio::spawn(run_outbox_relay(
+        outbox_repo,
+        event_sink,
+        Duration::from_secs(config.outbox_relay_interval_seconds),
+        config.outbox_retention_days,
+    ));
+
     let shared_state = Arc::new(AppState {
         product_repo,
+        category_repo,
+        attribute_repo,
+        price_list_repo,
+        price_history_repo,
+        scheduled_price_repo,
+        product_variant_repo,
+        product_image_repo,
+        image_storage,
+        product_review_repo,
+        warehouse_repo,
+        inventory_repo,
+        reservation_repo,
+        suggestion_repo,
+        import_job_repo,
+        product_translation_repo,
+        product_bundle_repo,
+        product_link_repo,
         db_pool: pg_pool.clone(),
+        require_if_match: config.require_if_match,
+        reservation_ttl_seconds: config.reservation_ttl_seconds,
+        suggest_timeout_ms: config.suggest_timeout_ms,
+        image_max_bytes: config.image_max_bytes,
+        thumbnail_sizes: config.thumbnail_sizes.clone(),
+        import_max_bytes: config.import_max_bytes,
+        locales: LocaleSettings::new(&config.default_locale, &config.locale_fallbacks),
     });
 
-    let app = Router::new()
+    let mut app = Router::new()
         .route("/health", get(health_check))
         .nest("/products", product_routes())
+        .nest("/categories", category_routes())
+        .nest("/price-lists", price_list_routes())
+        .nest("/warehouses", warehouse_routes())
+        .nest("/inventory", inventory_routes())
+        .nest("/reservations", reservation_routes())
+        .nest("/reviews", review_routes())
+        .nest("/admin", admin_routes());
+
+    if let StorageBackend::Local = config.storage_backend {
+        app = app.nest_service("/media", ServeDir::new(&config.storage_local_root));
+    }
+
+    let app = app
         .with_state(shared_state)
+        .layer(middleware::from_fn(propagate_request_id))
         .layer(TraceLayer::new_for_http());
 
     let listener = TcpListener::bind("0.0.0.0:8081").await.unwrap();
@@ -86,6 +278,8 @@ async fn main() {
         .with_graceful_shutdown(shutdown_signal())
         .await
         .unwrap();
+
+    Ok(())
 }
 
 async fn shutdown_signal() {
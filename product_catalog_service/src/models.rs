@@ -1,5 +1,43 @@
 pub mod app_error;
 pub mod app_state;
+pub mod attribute_definition;
+pub mod availability_query;
+pub mod category;
+pub mod cursor;
+pub mod export_query;
+pub mod facet_dimension;
+pub mod import_job;
+pub mod inventory_ledger_entry;
+pub mod inventory_level;
 pub mod paginated_response;
 pub mod pagination;
+pub mod price_history_entry;
+pub mod price_list;
+pub mod price_list_entry;
+pub mod price_query;
 pub mod product;
+pub mod product_bundle;
+pub mod product_event;
+pub mod product_export_row;
+pub mod product_image;
+pub mod product_import_row;
+pub mod product_link;
+pub mod product_option;
+pub mod product_review;
+pub mod product_search_hit;
+pub mod product_sort;
+pub mod product_translation;
+pub mod product_variant;
+pub mod product_visibility;
+pub mod related_product;
+pub mod related_query;
+pub mod reservation;
+pub mod reservation_item;
+pub mod review_query;
+pub mod scheduled_price_change;
+pub mod slug_match;
+pub mod suggest_query;
+pub mod suggestion;
+pub mod validated_json;
+pub mod visibility_query;
+pub mod warehouse;
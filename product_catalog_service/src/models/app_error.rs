@@ -1,28 +1,163 @@
 use axum::{
     Json,
-    http::StatusCode,
+    http::{StatusCode, header},
     response::{IntoResponse, Response},
 };
-use serde_json::json;
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::error::ErrorKind;
+use validator::ValidationErrors;
+
+use crate::utility::request_id::current_request_id;
 
 #[derive(Debug)]
 pub enum AppError {
     InternalServerError,
+    ServiceUnavailable,
     NotFound(String),
     Invalid(String),
+    Conflict(String),
+    ConstraintViolation(String),
+    PayloadTooLarge(String),
+    UnsupportedMediaType(String),
+    RequestPayloadNotValid(String),
+    ValidationFailed(ValidationErrors),
+    PreconditionFailed,
+    PreconditionRequired,
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(err: sqlx::Error) -> Self {
+        match &err {
+            sqlx::Error::RowNotFound => AppError::NotFound("Record not found".to_string()),
+            sqlx::Error::Database(db_err) => match db_err.kind() {
+                ErrorKind::UniqueViolation => {
+                    tracing::warn!(constraint = db_err.constraint(), "unique constraint violated");
+                    AppError::Conflict(match db_err.constraint() {
+                        Some(constraint) => {
+                            format!("Conflicts with an existing record ({})", constraint)
+                        }
+                        None => "Conflicts with an existing record".to_string(),
+                    })
+                }
+                ErrorKind::ForeignKeyViolation
+                | ErrorKind::NotNullViolation
+                | ErrorKind::CheckViolation => {
+                    tracing::warn!(constraint = db_err.constraint(), "constraint violated");
+                    AppError::ConstraintViolation(db_err.message().to_string())
+                }
+                _ => {
+                    tracing::error!(error = %err, "database error");
+                    AppError::InternalServerError
+                }
+            },
+            sqlx::Error::PoolTimedOut
+            | sqlx::Error::PoolClosed
+            | sqlx::Error::Io(_)
+            | sqlx::Error::Tls(_) => {
+                tracing::error!(error = %err, "database is unreachable");
+                AppError::ServiceUnavailable
+            }
+            _ => {
+                tracing::error!(error = %err, "database error");
+                AppError::InternalServerError
+            }
+        }
+    }
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        let (status, err_msg) = match self {
+        // Field-level details of failed validations; nested objects and
+        // lists nest their errors by field name or index.
+        let errors = match &self {
+            AppError::ValidationFailed(validation_errors) => {
+                serde_json::to_value(validation_errors).ok()
+            }
+            _ => None,
+        };
+
+        let (status, code, detail) = match self {
             AppError::InternalServerError => (
                 StatusCode::INTERNAL_SERVER_ERROR,
+                "InternalServerError",
                 String::from("Internal Server Error"),
             ),
-            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
-            AppError::Invalid(msg) => (StatusCode::BAD_REQUEST, msg),
+            AppError::ServiceUnavailable => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "ServiceUnavailable",
+                String::from("Database is unavailable, please retry later"),
+            ),
+            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, "NotFound", msg),
+            AppError::Invalid(msg) => (StatusCode::BAD_REQUEST, "Invalid", msg),
+            AppError::Conflict(msg) => (StatusCode::CONFLICT, "Conflict", msg),
+            AppError::ConstraintViolation(msg) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "ConstraintViolation",
+                msg,
+            ),
+            AppError::PayloadTooLarge(msg) => (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "PayloadTooLarge",
+                msg,
+            ),
+            AppError::UnsupportedMediaType(msg) => (
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "UnsupportedMediaType",
+                msg,
+            ),
+            AppError::RequestPayloadNotValid(msg) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "RequestPayloadNotValidUnprocessableEntity",
+                msg,
+            ),
+            AppError::ValidationFailed(_) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "ValidationFailed",
+                String::from("Request payload failed validation"),
+            ),
+            AppError::PreconditionFailed => (
+                StatusCode::PRECONDITION_FAILED,
+                "PreconditionFailed",
+                String::from("Product was modified by another request"),
+            ),
+            AppError::PreconditionRequired => (
+                StatusCode::PRECONDITION_REQUIRED,
+                "PreconditionRequired",
+                String::from("If-Match header is required"),
+            ),
         };
 
-        (status, Json(json!({ "message": err_msg }))).into_response()
+        let body = ProblemDetails {
+            problem_type: String::from("about:blank"),
+            title: status.canonical_reason().unwrap_or_default().to_string(),
+            status: status.as_u16(),
+            detail,
+            code: code.to_string(),
+            request_id: current_request_id(),
+            errors,
+        };
+
+        (
+            status,
+            [(header::CONTENT_TYPE, "application/problem+json")],
+            Json(body),
+        )
+            .into_response()
     }
 }
+
+/// RFC 7807 problem details body returned for every error.
+#[derive(Serialize)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Value>,
+}
@@ -2,9 +2,45 @@ use std::sync::Arc;
 
 use sqlx::PgPool;
 
-use crate::repos::product_repo::ProductRepo;
+use crate::repos::{
+    attribute_repo::AttributeRepo, category_repo::CategoryRepo, import_job_repo::ImportJobRepo,
+    inventory_repo::InventoryRepo, price_history_repo::PriceHistoryRepo,
+    price_list_repo::PriceListRepo, product_bundle_repo::ProductBundleRepo,
+    product_image_repo::ProductImageRepo, product_link_repo::ProductLinkRepo,
+    product_repo::ProductRepo, product_review_repo::ProductReviewRepo,
+    product_translation_repo::ProductTranslationRepo, product_variant_repo::ProductVariantRepo,
+    reservation_repo::ReservationRepo, scheduled_price_repo::ScheduledPriceRepo,
+    suggestion_repo::SuggestionRepo, warehouse_repo::WarehouseRepo,
+};
+use crate::traits::object_storage::ObjectStorage;
+use crate::utility::locale::LocaleSettings;
 
 pub struct AppState {
     pub db_pool: PgPool,
     pub product_repo: Arc<ProductRepo>,
+    pub category_repo: Arc<CategoryRepo>,
+    pub attribute_repo: Arc<AttributeRepo>,
+    pub price_list_repo: Arc<PriceListRepo>,
+    pub price_history_repo: Arc<PriceHistoryRepo>,
+    pub scheduled_price_repo: Arc<ScheduledPriceRepo>,
+    pub product_variant_repo: Arc<ProductVariantRepo>,
+    pub product_image_repo: Arc<ProductImageRepo>,
+    pub image_storage: Arc<dyn ObjectStorage>,
+    pub product_review_repo: Arc<ProductReviewRepo>,
+    pub warehouse_repo: Arc<WarehouseRepo>,
+    pub inventory_repo: Arc<InventoryRepo>,
+    pub reservation_repo: Arc<ReservationRepo>,
+    pub suggestion_repo: Arc<SuggestionRepo>,
+    pub import_job_repo: Arc<ImportJobRepo>,
+    pub product_translation_repo: Arc<ProductTranslationRepo>,
+    pub product_bundle_repo: Arc<ProductBundleRepo>,
+    pub product_link_repo: Arc<ProductLinkRepo>,
+    pub require_if_match: bool,
+    pub reservation_ttl_seconds: i64,
+    pub suggest_timeout_ms: u64,
+    pub image_max_bytes: usize,
+    /// Bounding boxes in pixels of the thumbnails rendered for every image.
+    pub thumbnail_sizes: Vec<u32>,
+    pub import_max_bytes: usize,
+    pub locales: LocaleSettings,
 }
@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::dtos::attribute_definition_dto::AttributeDefinitionDto;
+use crate::models::app_error::AppError;
+use crate::traits::to_dto::ToDto;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "attribute_type", rename_all = "snake_case")]
+pub enum AttributeType {
+    Text,
+    Number,
+    Bool,
+    Enum,
+}
+
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct AttributeDefinition {
+    pub id: Uuid,
+    pub category_id: Uuid,
+    pub name: String,
+    pub attribute_type: AttributeType,
+    pub unit: Option<String>,
+    pub required: bool,
+    pub allowed_values: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AttributeDefinition {
+    fn accepts(&self, value: &Value) -> bool {
+        match (self.attribute_type, value) {
+            (AttributeType::Text, Value::String(_)) => true,
+            (AttributeType::Number, Value::Number(_)) => true,
+            (AttributeType::Bool, Value::Bool(_)) => true,
+            (AttributeType::Enum, Value::String(value)) => self.allowed_values.contains(value),
+            _ => false,
+        }
+    }
+}
+
+/// Checks product attribute values against the schema of its categories:
+/// every value must be defined and of the right type, and every required
+/// attribute must be present.
+pub fn validate_attribute_values(
+    definitions: &[AttributeDefinition],
+    values: &BTreeMap<String, Value>,
+) -> Result<(), AppError> {
+    for (name, value) in values {
+        match definitions
+            .iter()
+            .find(|definition| &definition.name == name)
+        {
+            Some(definition) if definition.accepts(value) => {}
+            Some(definition) if definition.attribute_type == AttributeType::Enum => {
+                return Err(AppError::Invalid(format!(
+                    "Attribute '{}' must be one of: {}",
+                    name,
+                    definition.allowed_values.join(", ")
+                )));
+            }
+            Some(definition) => {
+                return Err(AppError::Invalid(format!(
+                    "Attribute '{}' must be a {}",
+                    name,
+                    match definition.attribute_type {
+                        AttributeType::Number => "number",
+                        AttributeType::Bool => "boolean",
+                        _ => "string",
+                    }
+                )));
+            }
+            None => {
+                return Err(AppError::Invalid(format!(
+                    "No category of the product defines attribute '{}'",
+                    name
+                )));
+            }
+        }
+    }
+
+    if let Some(missing) = definitions
+        .iter()
+        .find(|definition| definition.required && !values.contains_key(&definition.name))
+    {
+        return Err(AppError::Invalid(format!(
+            "Attribute '{}' is required",
+            missing.name
+        )));
+    }
+
+    Ok(())
+}
+
+impl ToDto<AttributeDefinitionDto> for AttributeDefinition {
+    fn to_dto(&self) -> AttributeDefinitionDto {
+        AttributeDefinitionDto {
+            id: self.id,
+            category_id: self.category_id,
+            name: self.name.clone(),
+            attribute_type: self.attribute_type,
+            unit: self.unit.clone(),
+            required: self.required,
+            allowed_values: self.allowed_values.clone(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn definition(
+        name: &str,
+        attribute_type: AttributeType,
+        required: bool,
+        allowed_values: &[&str],
+    ) -> AttributeDefinition {
+        AttributeDefinition {
+            id: Uuid::new_v4(),
+            category_id: Uuid::new_v4(),
+            name: name.to_string(),
+            attribute_type,
+            unit: None,
+            required,
+            allowed_values: allowed_values
+                .iter()
+                .map(|value| value.to_string())
+                .collect(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn schema() -> Vec<AttributeDefinition> {
+        vec![
+            definition("brand", AttributeType::Text, true, &[]),
+            definition("ram_gb", AttributeType::Number, false, &[]),
+            definition("wireless", AttributeType::Bool, false, &[]),
+            definition("color", AttributeType::Enum, false, &["red", "blue"]),
+        ]
+    }
+
+    fn values(values: serde_json::Value) -> BTreeMap<String, Value> {
+        serde_json::from_value(values).unwrap()
+    }
+
+    fn error_message(result: Result<(), AppError>) -> String {
+        match result {
+            Err(AppError::Invalid(message)) => message,
+            other => panic!("expected an invalid attribute, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn accepts_values_of_the_defined_types() {
+        let values = values(json!({
+            "brand": "Acme",
+            "ram_gb": 16,
+            "wireless": true,
+            "color": "blue",
+        }));
+
+        assert!(validate_attribute_values(&schema(), &values).is_ok());
+    }
+
+    #[test]
+    fn rejects_values_of_the_wrong_type() {
+        let values = values(json!({ "brand": "Acme", "ram_gb": "16" }));
+
+        assert_eq!(
+            error_message(validate_attribute_values(&schema(), &values)),
+            "Attribute 'ram_gb' must be a number"
+        );
+    }
+
+    #[test]
+    fn rejects_enum_values_that_are_not_allowed() {
+        let values = values(json!({ "brand": "Acme", "color": "green" }));
+
+        assert_eq!(
+            error_message(validate_attribute_values(&schema(), &values)),
+            "Attribute 'color' must be one of: red, blue"
+        );
+    }
+
+    #[test]
+    fn rejects_undefined_attributes() {
+        let values = values(json!({ "brand": "Acme", "weight_kg": 2 }));
+
+        assert_eq!(
+            error_message(validate_attribute_values(&schema(), &values)),
+            "No category of the product defines attribute 'weight_kg'"
+        );
+    }
+
+    #[test]
+    fn rejects_missing_required_attributes() {
+        let values = values(json!({ "wireless": false }));
+
+        assert_eq!(
+            error_message(validate_attribute_values(&schema(), &values)),
+            "Attribute 'brand' is required"
+        );
+    }
+}
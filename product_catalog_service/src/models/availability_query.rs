@@ -0,0 +1,18 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct AvailabilityQuery {
+    /// Comma separated list of SKUs.
+    pub skus: String,
+}
+
+impl AvailabilityQuery {
+    pub fn skus(&self) -> Vec<String> {
+        self.skus
+            .split(',')
+            .map(str::trim)
+            .filter(|sku| !sku.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+}
@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::dtos::category_dto::CategoryDto;
+use crate::traits::to_dto::ToDto;
+
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct Category {
+    pub id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub name: String,
+    pub path: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Category {
+    /// Number of ancestors above this category; roots have depth 0.
+    pub fn depth(&self) -> usize {
+        self.path.matches('/').count().saturating_sub(2)
+    }
+}
+
+impl ToDto<CategoryDto> for Category {
+    fn to_dto(&self) -> CategoryDto {
+        CategoryDto {
+            id: self.id,
+            parent_id: self.parent_id,
+            name: self.name.clone(),
+            depth: self.depth(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
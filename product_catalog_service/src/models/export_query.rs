@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+use crate::models::import_job::ImportFormat;
+
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    /// Defaults to CSV.
+    pub format: Option<ImportFormat>,
+}
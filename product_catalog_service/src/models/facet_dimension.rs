@@ -0,0 +1,12 @@
+/// Filter dimensions of the product list that facet counts are reported for.
+///
+/// Each facet is counted with every filter applied except its own, so the
+/// counts show what selecting another value in that dimension would return.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FacetDimension {
+    Category,
+    Attributes,
+    Price,
+    InStock,
+    Created,
+}
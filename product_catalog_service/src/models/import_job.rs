@@ -0,0 +1,123 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, types::Json};
+use uuid::Uuid;
+
+use crate::dtos::{import_job_dto::ImportJobDto, import_row_error_dto::ImportRowErrorDto};
+use crate::models::app_error::AppError;
+use crate::traits::to_dto::ToDto;
+
+/// Format of product import and export files.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "import_format", rename_all = "snake_case")]
+pub enum ImportFormat {
+    Csv,
+    /// One JSON object per line.
+    Ndjson,
+}
+
+impl ImportFormat {
+    /// Picks the format of an uploaded file from its `Content-Type`.
+    pub fn from_content_type(content_type: &str) -> Result<ImportFormat, AppError> {
+        match content_type.split(';').next().unwrap_or_default().trim() {
+            "text/csv" => Ok(ImportFormat::Csv),
+            "application/x-ndjson" | "application/ndjson" => Ok(ImportFormat::Ndjson),
+            other => Err(AppError::UnsupportedMediaType(format!(
+                "Imports must be text/csv or application/x-ndjson, not '{}'",
+                other
+            ))),
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ImportFormat::Csv => "text/csv; charset=utf-8",
+            ImportFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImportFormat::Csv => "csv",
+            ImportFormat::Ndjson => "ndjson",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "import_job_status", rename_all = "snake_case")]
+pub enum ImportJobStatus {
+    Queued,
+    Running,
+    /// Every row was processed; some may have failed.
+    Completed,
+    /// The file could not be processed at all, see `failure`.
+    Failed,
+}
+
+/// Whether an imported row created a product or updated an existing one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportOutcome {
+    Created,
+    Updated,
+}
+
+/// A row of an import file that was not applied.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ImportRowError {
+    /// Line of the row in the file, starting at 1.
+    pub row: i64,
+    pub external_id: Option<String>,
+    pub sku: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct ImportJob {
+    pub id: Uuid,
+    pub format: ImportFormat,
+    pub status: ImportJobStatus,
+    pub total_rows: i32,
+    pub processed_rows: i32,
+    pub created_rows: i32,
+    pub updated_rows: i32,
+    pub failed_rows: i32,
+    pub errors: Json<Vec<ImportRowError>>,
+    pub failure: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl ToDto<ImportRowErrorDto> for ImportRowError {
+    fn to_dto(&self) -> ImportRowErrorDto {
+        ImportRowErrorDto {
+            row: self.row,
+            external_id: self.external_id.clone(),
+            sku: self.sku.clone(),
+            message: self.message.clone(),
+        }
+    }
+}
+
+impl ToDto<ImportJobDto> for ImportJob {
+    fn to_dto(&self) -> ImportJobDto {
+        ImportJobDto {
+            id: self.id,
+            format: self.format,
+            status: self.status,
+            total_rows: self.total_rows,
+            processed_rows: self.processed_rows,
+            created_rows: self.created_rows,
+            updated_rows: self.updated_rows,
+            failed_rows: self.failed_rows,
+            errors: self.errors.iter().map(|error| error.to_dto()).collect(),
+            failure: self.failure.clone(),
+            created_at: self.created_at,
+            started_at: self.started_at,
+            finished_at: self.finished_at,
+        }
+    }
+}
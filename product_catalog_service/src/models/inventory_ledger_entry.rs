@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::dtos::inventory_ledger_entry_dto::InventoryLedgerEntryDto;
+use crate::traits::to_dto::ToDto;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "inventory_reason", rename_all = "snake_case")]
+pub enum InventoryReason {
+    Receipt,
+    Sale,
+    Return,
+    Damage,
+    Correction,
+    Transfer,
+}
+
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct InventoryLedgerEntry {
+    pub id: i64,
+    pub variant_id: Uuid,
+    pub warehouse_id: Uuid,
+    pub warehouse_code: String,
+    pub quantity_delta: i32,
+    pub reason: InventoryReason,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ToDto<InventoryLedgerEntryDto> for InventoryLedgerEntry {
+    fn to_dto(&self) -> InventoryLedgerEntryDto {
+        InventoryLedgerEntryDto {
+            id: self.id,
+            warehouse_code: self.warehouse_code.clone(),
+            quantity_delta: self.quantity_delta,
+            reason: self.reason,
+            note: self.note.clone(),
+            created_at: self.created_at,
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::dtos::inventory_level_dto::InventoryLevelDto;
+use crate::traits::to_dto::ToDto;
+
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct InventoryLevel {
+    pub variant_id: Uuid,
+    pub sku: String,
+    pub warehouse_id: Uuid,
+    pub warehouse_code: String,
+    pub on_hand: i32,
+    pub reserved: i32,
+    pub low_stock_threshold: i32,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl InventoryLevel {
+    pub fn available(&self) -> i32 {
+        self.on_hand - self.reserved
+    }
+
+    pub fn is_low_stock(&self) -> bool {
+        self.available() <= self.low_stock_threshold
+    }
+}
+
+impl ToDto<InventoryLevelDto> for InventoryLevel {
+    fn to_dto(&self) -> InventoryLevelDto {
+        InventoryLevelDto {
+            sku: self.sku.clone(),
+            warehouse_code: self.warehouse_code.clone(),
+            on_hand: self.on_hand,
+            reserved: self.reserved,
+            available: self.available(),
+            low_stock_threshold: self.low_stock_threshold,
+            low_stock: self.is_low_stock(),
+            updated_at: self.updated_at,
+        }
+    }
+}
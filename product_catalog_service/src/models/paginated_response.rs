@@ -1,10 +1,16 @@
 use serde::Serialize;
 
+use crate::dtos::product_facets_dto::ProductFacetsDto;
+
 #[derive(Debug, Serialize)]
 pub struct PaginatedResponse<T> {
     pub page: i64,
     pub per_page: i64,
     pub total: i64,
     pub total_pages: i64,
+    pub next_cursor: Option<String>,
+    pub prev_cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facets: Option<ProductFacetsDto>,
     pub data: Vec<T>,
 }
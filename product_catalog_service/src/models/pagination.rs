@@ -1,7 +1,251 @@
+use std::collections::BTreeMap;
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::models::{
+    app_error::AppError,
+    cursor::{Cursor, PageCursor},
+    price_query::PriceQuery,
+    product::ProductStatus,
+    product_sort::{ProductSort, SortDirection},
+    product_visibility::ProductVisibility,
+};
+
+/// Inclusive lower and upper bound; `None` leaves that side open.
+pub type AttributeRange = (Option<BigDecimal>, Option<BigDecimal>);
 
 #[derive(Debug, Deserialize)]
 pub struct Pagination {
     pub page: Option<i64>,
     pub per_page: Option<i64>,
+    pub after: Option<String>,
+    pub before: Option<String>,
+    pub category: Option<Uuid>,
+    /// Full-text search query.
+    pub q: Option<String>,
+    pub min_price: Option<BigDecimal>,
+    pub max_price: Option<BigDecimal>,
+    /// Comma separated `name:value` pairs matched against variant options,
+    /// e.g. `color:red,color:blue,size:m`. Values of one name are alternatives.
+    pub attr: Option<String>,
+    /// Comma separated `name:min..max` ranges over numeric product attributes,
+    /// e.g. `ram_gb:8..32,weight_kg:..2`. Either bound may be left out.
+    pub attr_range: Option<String>,
+    pub in_stock: Option<bool>,
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+    pub sort: Option<ProductSort>,
+    pub order: Option<SortDirection>,
+    /// Prices the products in this currency, see [`PriceQuery`].
+    pub currency: Option<String>,
+    pub customer_group: Option<String>,
+    /// Public products unless the admin product list asks for another
+    /// visibility. Not read from the query string.
+    #[serde(skip)]
+    pub visibility: Option<ProductVisibility>,
+    pub status: Option<ProductStatus>,
+}
+
+impl Pagination {
+    /// Decodes the `after`/`before` query parameters, if any, for keyset pagination.
+    pub fn page_cursor(&self, order_by: &str) -> Result<Option<PageCursor>, AppError> {
+        let decode = |token: &str| match Cursor::decode(token) {
+            Some(cursor) if cursor.order_by == order_by => Ok(cursor),
+            _ => Err(AppError::Invalid(
+                "Pagination cursor is not valid for this query".to_string(),
+            )),
+        };
+
+        match (&self.after, &self.before) {
+            (Some(_), Some(_)) => Err(AppError::Invalid(
+                "Only one of `after` or `before` can be given".to_string(),
+            )),
+            (Some(after), None) => Ok(Some(PageCursor::After(decode(after)?))),
+            (None, Some(before)) => Ok(Some(PageCursor::Before(decode(before)?))),
+            (None, None) => Ok(None),
+        }
+    }
+
+    pub fn price_query(&self) -> PriceQuery {
+        PriceQuery {
+            currency: self.currency.clone(),
+            customer_group: self.customer_group.clone(),
+        }
+    }
+
+    /// Identifies the ordering inside pagination cursors. Prices differ per
+    /// currency and customer group, so those are part of it, e.g. `price:asc:EUR:vip`.
+    pub fn cursor_key(
+        &self,
+        sort: ProductSort,
+        direction: SortDirection,
+    ) -> Result<String, AppError> {
+        let mut key = sort.cursor_key(direction);
+
+        if let Some(currency) = self.price_query().currency()? {
+            key = format!("{}:{}", key, currency);
+            if let Some(customer_group) = &self.customer_group {
+                key = format!("{}:{}", key, customer_group);
+            }
+        }
+
+        Ok(key)
+    }
+
+    /// Resolves the requested ordering, defaulting to relevance for searches
+    /// and to newest first otherwise.
+    pub fn ordering(&self) -> Result<(ProductSort, SortDirection), AppError> {
+        let is_search = self.tsquery().is_some();
+        let sort = self.sort.unwrap_or(if is_search {
+            ProductSort::Relevance
+        } else {
+            ProductSort::Newest
+        });
+
+        if sort == ProductSort::Relevance && !is_search {
+            return Err(AppError::Invalid(
+                "Sorting by relevance requires a search query `q`".to_string(),
+            ));
+        }
+
+        Ok((sort, self.order.unwrap_or(sort.default_direction())))
+    }
+
+    /// Groups the `attr` filter by option name.
+    pub fn attribute_filters(&self) -> Result<BTreeMap<String, Vec<String>>, AppError> {
+        let mut filters: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        for pair in self.attr.as_deref().unwrap_or_default().split(',') {
+            if pair.trim().is_empty() {
+                continue;
+            }
+
+            match pair.split_once(':') {
+                Some((name, value)) if !name.trim().is_empty() && !value.trim().is_empty() => {
+                    filters
+                        .entry(name.trim().to_string())
+                        .or_default()
+                        .push(value.trim().to_string());
+                }
+                _ => {
+                    return Err(AppError::Invalid(format!(
+                        "Attribute filter `{}` must look like `name:value`",
+                        pair
+                    )));
+                }
+            }
+        }
+
+        Ok(filters)
+    }
+
+    /// Parses the `attr_range` filter into inclusive bounds per attribute.
+    pub fn attribute_ranges(&self) -> Result<BTreeMap<String, AttributeRange>, AppError> {
+        let mut ranges = BTreeMap::new();
+
+        for range in self.attr_range.as_deref().unwrap_or_default().split(',') {
+            if range.trim().is_empty() {
+                continue;
+            }
+
+            let invalid = || {
+                AppError::Invalid(format!(
+                    "Attribute range `{}` must look like `name:min..max`",
+                    range
+                ))
+            };
+            let bound = |value: &str| match value.trim() {
+                "" => Ok(None),
+                value => value.parse::<BigDecimal>().map(Some).map_err(|_| invalid()),
+            };
+
+            let (name, bounds) = range.split_once(':').ok_or_else(invalid)?;
+            let (min, max) = bounds.split_once("..").ok_or_else(invalid)?;
+            if name.trim().is_empty() {
+                return Err(invalid());
+            }
+
+            ranges.insert(name.trim().to_string(), (bound(min)?, bound(max)?));
+        }
+
+        Ok(ranges)
+    }
+
+    /// Turns `q` into a tsquery matching every word as a prefix, e.g.
+    /// `red:* & shoe:*`. Returns `None` when `q` has no words.
+    pub fn tsquery(&self) -> Option<String> {
+        let terms: Vec<String> = self
+            .q
+            .as_deref()
+            .unwrap_or_default()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|term| !term.is_empty())
+            .map(|term| format!("{}:*", term.to_lowercase()))
+            .collect();
+
+        if terms.is_empty() {
+            None
+        } else {
+            Some(terms.join(" & "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn pagination(query: serde_json::Value) -> Pagination {
+        serde_json::from_value(query).unwrap()
+    }
+
+    #[test]
+    fn tsquery_prefix_matches_every_word() {
+        let query = pagination(json!({ "q": "Red  Running-Shoe" }));
+
+        assert_eq!(
+            query.tsquery().as_deref(),
+            Some("red:* & running:* & shoe:*")
+        );
+    }
+
+    #[test]
+    fn tsquery_drops_tsquery_operators() {
+        let query = pagination(json!({ "q": "shoe & !(boot | sandal):*" }));
+
+        assert_eq!(
+            query.tsquery().as_deref(),
+            Some("shoe:* & boot:* & sandal:*")
+        );
+    }
+
+    #[test]
+    fn tsquery_is_none_without_words() {
+        assert_eq!(pagination(json!({})).tsquery(), None);
+        assert_eq!(pagination(json!({ "q": " &|! " })).tsquery(), None);
+    }
+
+    #[test]
+    fn page_cursor_rejects_cursors_of_another_ordering() {
+        let cursor = Cursor {
+            order_by: "name:asc".to_string(),
+            value: "shoe".to_string(),
+            id: Uuid::new_v4(),
+        };
+        let query = pagination(json!({ "after": cursor.encode() }));
+
+        assert!(matches!(
+            query.page_cursor("name:asc"),
+            Ok(Some(PageCursor::After(_)))
+        ));
+        assert!(matches!(
+            query.page_cursor("price:asc"),
+            Err(AppError::Invalid(_))
+        ));
+    }
 }
@@ -0,0 +1,48 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::dtos::price_history_entry_dto::PriceHistoryEntryDto;
+use crate::traits::to_dto::ToDto;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "price_change_source", rename_all = "snake_case")]
+pub enum PriceChangeSource {
+    Created,
+    Manual,
+    Scheduled,
+    ScheduleEnded,
+    Import,
+    /// Recomputed from the components of a discounted bundle.
+    Bundle,
+}
+
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct PriceHistoryEntry {
+    pub id: i64,
+    pub product_id: Uuid,
+    pub price: BigDecimal,
+    pub currency: String,
+    pub previous_price: Option<BigDecimal>,
+    pub previous_currency: Option<String>,
+    pub source: PriceChangeSource,
+    pub scheduled_price_change_id: Option<Uuid>,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl ToDto<PriceHistoryEntryDto> for PriceHistoryEntry {
+    fn to_dto(&self) -> PriceHistoryEntryDto {
+        PriceHistoryEntryDto {
+            price: self.price.clone(),
+            currency: self.currency.clone(),
+            previous_price: self.previous_price.clone(),
+            previous_currency: self.previous_currency.clone(),
+            source: self.source,
+            scheduled_price_change_id: self.scheduled_price_change_id,
+            changed_at: self.changed_at,
+        }
+    }
+}
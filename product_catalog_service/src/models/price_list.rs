@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::dtos::price_list_dto::PriceListDto;
+use crate::models::app_error::AppError;
+use crate::traits::to_dto::ToDto;
+
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct PriceList {
+    pub id: Uuid,
+    pub name: String,
+    pub currency: String,
+    pub customer_group: Option<String>,
+    pub priority: i32,
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_to: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Normalizes an ISO 4217 currency code, e.g. `eur` to `EUR`.
+pub fn parse_currency(code: &str) -> Result<String, AppError> {
+    let code = code.trim().to_ascii_uppercase();
+
+    if code.len() == 3 && code.chars().all(|c| c.is_ascii_uppercase()) {
+        Ok(code)
+    } else {
+        Err(AppError::Invalid(format!(
+            "Currency '{}' must be a three letter ISO 4217 code",
+            code
+        )))
+    }
+}
+
+impl ToDto<PriceListDto> for PriceList {
+    fn to_dto(&self) -> PriceListDto {
+        PriceListDto {
+            id: self.id,
+            name: self.name.clone(),
+            currency: self.currency.clone(),
+            customer_group: self.customer_group.clone(),
+            priority: self.priority,
+            valid_from: self.valid_from,
+            valid_to: self.valid_to,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::dtos::price_list_entry_dto::PriceListEntryDto;
+use crate::traits::to_dto::ToDto;
+
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct PriceListEntry {
+    pub price_list_id: Uuid,
+    pub product_id: Uuid,
+    pub price: BigDecimal,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ToDto<PriceListEntryDto> for PriceListEntry {
+    fn to_dto(&self) -> PriceListEntryDto {
+        PriceListEntryDto {
+            product_id: self.product_id,
+            price: self.price.clone(),
+            updated_at: self.updated_at,
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use serde::Deserialize;
+
+use crate::models::{app_error::AppError, price_list::parse_currency};
+
+/// Currency and customer group a product read is priced for.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PriceQuery {
+    pub currency: Option<String>,
+    pub customer_group: Option<String>,
+}
+
+impl PriceQuery {
+    /// The validated currency to price in; `None` keeps every product's base
+    /// price in its own currency.
+    pub fn currency(&self) -> Result<Option<String>, AppError> {
+        match (&self.currency, &self.customer_group) {
+            (Some(currency), _) => parse_currency(currency).map(Some),
+            (None, Some(_)) => Err(AppError::Invalid(
+                "`customer_group` prices need a `currency`".to_string(),
+            )),
+            (None, None) => Ok(None),
+        }
+    }
+}
@@ -1,33 +1,102 @@
+use std::collections::BTreeMap;
+
 use bigdecimal::BigDecimal;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use serde_json::Value;
+use sqlx::{FromRow, types::Json};
 use uuid::Uuid;
 
 use crate::dtos::product_dto::ProductDto;
-use crate::traits::to_dto::ToDto;
+use crate::models::cursor::Cursor;
+use crate::traits::{to_cursor::ToCursor, to_dto::ToDto};
+
+/// Where a product is in its lifecycle. Only active products are listed
+/// publicly, and only once their `published_at` has passed.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "product_status", rename_all = "snake_case")]
+pub enum ProductStatus {
+    Draft,
+    Active,
+    /// No longer sold; kept for order history and old links.
+    Archived,
+}
 
 #[derive(Debug, FromRow, Deserialize, Serialize)]
 pub struct Product {
     pub id: Uuid,
+    /// Identifier in the merchandisers' own systems, matched by imports.
+    pub external_id: Option<String>,
     pub name: String,
+    /// Path segment of the product's URL, generated from its name.
+    pub slug: String,
     pub description: Option<String>,
     pub price: BigDecimal,
+    pub currency: String,
+    /// Price list the price comes from; `None` for the base price.
+    pub price_list_id: Option<Uuid>,
     pub image_url: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub version: i64,
+    pub attributes: Json<BTreeMap<String, Value>>,
+    /// Average of the approved review ratings, 0 while there are none.
+    pub rating: BigDecimal,
+    pub rating_count: i32,
+    pub status: ProductStatus,
+    /// When the product goes or went public; set on activation if not given.
+    pub published_at: Option<DateTime<Utc>>,
+    /// Set while the product is deleted; restoring it clears the stamp.
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl ToDto<ProductDto> for Product {
     fn to_dto(&self) -> ProductDto {
         ProductDto {
             id: self.id,
+            external_id: self.external_id.clone(),
             name: self.name.clone(),
+            slug: self.slug.clone(),
             description: self.description.clone(),
             price: self.price.clone(),
+            currency: self.currency.clone(),
+            price_list_id: self.price_list_id,
             image_url: self.image_url.clone(),
             created_at: self.created_at,
             updated_at: self.updated_at,
+            version: self.version,
+            attributes: self.attributes.0.clone(),
+            rating: self.rating.clone(),
+            rating_count: self.rating_count,
+            status: self.status,
+            published_at: self.published_at,
+            deleted_at: self.deleted_at,
+            options: Vec::new(),
+            variants: Vec::new(),
+            images: Vec::new(),
+            bundle: None,
+            locale: None,
+            search: None,
+        }
+    }
+}
+
+impl ToCursor for Product {
+    fn to_cursor(&self, order_by: &str) -> Cursor {
+        // Cursor keys look like `price:asc`; only the sort matters here.
+        let value = match order_by.split(':').next().unwrap_or_default() {
+            "newest" => self.created_at.to_rfc3339_opts(SecondsFormat::Micros, true),
+            "price" => self.price.to_string(),
+            "name" => self.name.clone(),
+            "rating" => self.rating.to_string(),
+            _ => self.id.to_string(),
+        };
+
+        Cursor {
+            order_by: order_by.to_string(),
+            value,
+            id: self.id,
         }
     }
 }
@@ -0,0 +1,63 @@
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::dtos::{bundle_component_dto::BundleComponentDto, product_bundle_dto::ProductBundleDto};
+
+/// A product sold as a set of other products.
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct ProductBundle {
+    pub product_id: Uuid,
+    /// When set, the bundle costs its components' prices less this percentage;
+    /// otherwise it has a price of its own.
+    pub discount_percent: Option<BigDecimal>,
+}
+
+/// A product contained in a bundle, with its current stock.
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct BundleComponent {
+    pub bundle_id: Uuid,
+    pub component_id: Uuid,
+    pub name: String,
+    pub slug: String,
+    pub price: BigDecimal,
+    pub currency: String,
+    pub quantity: i32,
+    /// Unreserved stock over all variants and warehouses.
+    pub available: i64,
+}
+
+impl ProductBundle {
+    /// Like `ToDto`, with the bundle's components out of `components`.
+    pub fn to_dto(&self, components: &[BundleComponent]) -> ProductBundleDto {
+        let components: Vec<&BundleComponent> = components
+            .iter()
+            .filter(|component| component.bundle_id == self.product_id)
+            .collect();
+
+        // As many bundles as the scarcest component can be put together for.
+        let available_quantity = components
+            .iter()
+            .map(|component| component.available / component.quantity as i64)
+            .min()
+            .unwrap_or(0);
+
+        ProductBundleDto {
+            discount_percent: self.discount_percent.clone(),
+            available_quantity,
+            components: components
+                .iter()
+                .map(|component| BundleComponentDto {
+                    product_id: component.component_id,
+                    name: component.name.clone(),
+                    slug: component.slug.clone(),
+                    price: component.price.clone(),
+                    currency: component.currency.clone(),
+                    quantity: component.quantity,
+                    available: component.available,
+                })
+                .collect(),
+        }
+    }
+}
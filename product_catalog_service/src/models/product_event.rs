@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "product_event_type", rename_all = "snake_case")]
+pub enum ProductEventType {
+    Created,
+    Updated,
+    /// Soft deleted; the product can still be restored.
+    Deleted,
+    Restored,
+    /// The base price or currency changed, by hand or otherwise, or the
+    /// product's price in a price list, identified by `price_list_id`.
+    PriceChanged,
+}
+
+/// A change of a product as published to other services.
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct ProductEvent {
+    pub id: i64,
+    pub product_id: Uuid,
+    /// Position among the events of the product, starting at 1.
+    pub sequence: i64,
+    pub event_type: ProductEventType,
+    /// The product after the change; price changes carry the previous price.
+    pub payload: Value,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::{FromRow, types::Json};
+use uuid::Uuid;
+
+use crate::models::{
+    app_error::AppError,
+    import_job::ImportFormat,
+    product::ProductStatus,
+    product_import_row::{CATEGORY_ID_SEPARATOR, CSV_COLUMNS, ProductCsvRow},
+};
+
+/// A product as written to export files, in the shape imports read back.
+#[derive(Debug, FromRow, Serialize)]
+pub struct ProductExportRow {
+    pub id: Uuid,
+    pub external_id: Option<String>,
+    /// SKU of the product's first variant.
+    pub sku: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub price: BigDecimal,
+    pub currency: String,
+    pub image_url: Option<String>,
+    pub category_ids: Vec<Uuid>,
+    pub attributes: Json<BTreeMap<String, Value>>,
+    pub status: ProductStatus,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+impl ProductExportRow {
+    fn to_csv_row(&self) -> ProductCsvRow {
+        ProductCsvRow {
+            id: Some(self.id),
+            external_id: self.external_id.clone(),
+            sku: self.sku.clone(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            price: self.price.to_string(),
+            currency: Some(self.currency.clone()),
+            image_url: self.image_url.clone(),
+            category_ids: Some(
+                self.category_ids
+                    .iter()
+                    .map(Uuid::to_string)
+                    .collect::<Vec<_>>()
+                    .join(&CATEGORY_ID_SEPARATOR.to_string()),
+            ),
+            attributes: Some(Value::from_iter(self.attributes.0.clone()).to_string()),
+            status: Some(self.status),
+            published_at: self.published_at,
+        }
+    }
+
+    /// Encodes a batch of rows; the CSV header goes before the first batch.
+    pub fn encode(
+        rows: &[ProductExportRow],
+        format: ImportFormat,
+        first_batch: bool,
+    ) -> Result<Vec<u8>, AppError> {
+        let encoding_failed = |err: &dyn std::fmt::Display| {
+            tracing::error!(error = %err, "failed to encode exported products");
+            AppError::InternalServerError
+        };
+
+        match format {
+            ImportFormat::Csv => {
+                let mut writer = csv::WriterBuilder::new()
+                    .has_headers(false)
+                    .from_writer(Vec::new());
+                if first_batch {
+                    writer
+                        .write_record(CSV_COLUMNS)
+                        .map_err(|err| encoding_failed(&err))?;
+                }
+                for row in rows {
+                    writer
+                        .serialize(row.to_csv_row())
+                        .map_err(|err| encoding_failed(&err))?;
+                }
+                writer.into_inner().map_err(|err| encoding_failed(&err))
+            }
+            ImportFormat::Ndjson => {
+                let mut bytes = Vec::new();
+                for row in rows {
+                    serde_json::to_writer(&mut bytes, row).map_err(|err| encoding_failed(&err))?;
+                    bytes.push(b'\n');
+                }
+                Ok(bytes)
+            }
+        }
+    }
+}
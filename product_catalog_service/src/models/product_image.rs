@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, types::Json};
+use uuid::Uuid;
+
+use crate::dtos::{product_image_dto::ProductImageDto, thumbnail_dto::ThumbnailDto};
+use crate::traits::object_storage::ObjectStorage;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ImageThumbnail {
+    pub size: i32,
+    pub width: i32,
+    pub height: i32,
+    pub content_type: String,
+    pub storage_key: String,
+}
+
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct ProductImage {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub position: i32,
+    pub storage_key: String,
+    pub content_type: String,
+    pub byte_size: i64,
+    pub width: i32,
+    pub height: i32,
+    pub alt_text: Option<String>,
+    pub thumbnails: Json<Vec<ImageThumbnail>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An uploaded image whose files are already in storage.
+#[derive(Debug)]
+pub struct NewProductImage {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub storage_key: String,
+    pub content_type: String,
+    pub byte_size: i64,
+    pub width: i32,
+    pub height: i32,
+    pub alt_text: Option<String>,
+    pub thumbnails: Vec<ImageThumbnail>,
+}
+
+impl ProductImage {
+    /// Storage keys of the original and of every thumbnail.
+    pub fn storage_keys(&self) -> Vec<String> {
+        std::iter::once(self.storage_key.clone())
+            .chain(self.thumbnails.iter().map(|t| t.storage_key.clone()))
+            .collect()
+    }
+
+    /// Like `ToDto`, but URLs depend on where the files are stored.
+    pub fn to_dto(&self, storage: &dyn ObjectStorage) -> ProductImageDto {
+        ProductImageDto {
+            id: self.id,
+            position: self.position,
+            url: storage.public_url(&self.storage_key),
+            content_type: self.content_type.clone(),
+            byte_size: self.byte_size,
+            width: self.width,
+            height: self.height,
+            alt_text: self.alt_text.clone(),
+            thumbnails: self
+                .thumbnails
+                .iter()
+                .map(|thumbnail| ThumbnailDto {
+                    size: thumbnail.size,
+                    width: thumbnail.width,
+                    height: thumbnail.height,
+                    url: storage.public_url(&thumbnail.storage_key),
+                })
+                .collect(),
+            created_at: self.created_at,
+        }
+    }
+}
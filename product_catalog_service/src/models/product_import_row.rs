@@ -0,0 +1,372 @@
+use std::collections::BTreeMap;
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dtos::create_product_dto::CreateProductDto;
+use crate::models::{app_error::AppError, import_job::ImportFormat, product::ProductStatus};
+
+/// Separates the category ids of a CSV cell.
+pub const CATEGORY_ID_SEPARATOR: char = '|';
+
+/// Column order of exported CSV files.
+pub const CSV_COLUMNS: [&str; 12] = [
+    "id",
+    "external_id",
+    "sku",
+    "name",
+    "description",
+    "price",
+    "currency",
+    "image_url",
+    "category_ids",
+    "attributes",
+    "status",
+    "published_at",
+];
+
+/// A row of a product CSV file. Cells hold the fields of
+/// [`CreateProductDto`], with `category_ids` separated by `|` and
+/// `attributes` as a JSON object.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ProductCsvRow {
+    /// Written by exports; imports ignore it and match on `external_id` or `sku`.
+    pub id: Option<Uuid>,
+    pub external_id: Option<String>,
+    pub sku: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub price: String,
+    pub currency: Option<String>,
+    pub image_url: Option<String>,
+    pub category_ids: Option<String>,
+    pub attributes: Option<String>,
+    pub status: Option<ProductStatus>,
+    /// RFC 3339 timestamp.
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// A product to create or update, read from an import file.
+#[derive(Debug)]
+pub struct ProductImportRow {
+    /// Line of the row in the file, starting at 1.
+    pub line: i64,
+    /// SKU of a variant of the product; a new product gets a variant with it.
+    pub sku: Option<String>,
+    pub product: CreateProductDto,
+}
+
+/// A row that could not be read, with the identifiers that could be.
+#[derive(Debug)]
+pub struct InvalidImportRow {
+    pub line: i64,
+    pub external_id: Option<String>,
+    pub sku: Option<String>,
+    pub message: String,
+}
+
+fn non_blank(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+impl ProductImportRow {
+    /// Reads every row of an import file. Rows that cannot be read are
+    /// returned as errors so the rest of the file can still be imported; only
+    /// a file that cannot be read at all fails.
+    pub fn parse(
+        format: ImportFormat,
+        bytes: &[u8],
+    ) -> Result<Vec<Result<ProductImportRow, InvalidImportRow>>, AppError> {
+        match format {
+            ImportFormat::Csv => Self::parse_csv(bytes),
+            ImportFormat::Ndjson => Self::parse_ndjson(bytes),
+        }
+    }
+
+    fn parse_csv(
+        bytes: &[u8],
+    ) -> Result<Vec<Result<ProductImportRow, InvalidImportRow>>, AppError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(bytes);
+
+        let headers = reader
+            .headers()
+            .map_err(|err| AppError::Invalid(format!("CSV header is not readable: {}", err)))?
+            .clone();
+        for required in ["name", "price"] {
+            if !headers.iter().any(|header| header == required) {
+                return Err(AppError::Invalid(format!(
+                    "CSV header has no '{}' column",
+                    required
+                )));
+            }
+        }
+
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            let record = match record {
+                Ok(record) => record,
+                Err(err) => {
+                    rows.push(Err(InvalidImportRow {
+                        line: err.position().map_or(0, |position| position.line() as i64),
+                        external_id: None,
+                        sku: None,
+                        message: err.to_string(),
+                    }));
+                    continue;
+                }
+            };
+            let line = record
+                .position()
+                .map_or(0, |position| position.line() as i64);
+
+            let row = match record.deserialize::<ProductCsvRow>(Some(&headers)) {
+                Ok(row) => row,
+                Err(err) => {
+                    rows.push(Err(InvalidImportRow {
+                        line,
+                        external_id: None,
+                        sku: None,
+                        message: err.to_string(),
+                    }));
+                    continue;
+                }
+            };
+
+            rows.push(Self::from_csv_row(line, row));
+        }
+
+        Ok(rows)
+    }
+
+    fn from_csv_row(line: i64, row: ProductCsvRow) -> Result<ProductImportRow, InvalidImportRow> {
+        let external_id = non_blank(row.external_id);
+        let sku = non_blank(row.sku);
+        let invalid = |message: String| InvalidImportRow {
+            line,
+            external_id: external_id.clone(),
+            sku: sku.clone(),
+            message,
+        };
+
+        let price = row
+            .price
+            .parse::<BigDecimal>()
+            .map_err(|_| invalid(format!("Price '{}' is not a number", row.price)))?;
+
+        let category_ids = non_blank(row.category_ids)
+            .map(|cell| {
+                cell.split(CATEGORY_ID_SEPARATOR)
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .map(|id| {
+                        id.parse::<Uuid>()
+                            .map_err(|_| invalid(format!("Category id '{}' is not a UUID", id)))
+                    })
+                    .collect::<Result<Vec<Uuid>, _>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        let attributes = non_blank(row.attributes)
+            .map(|cell| {
+                serde_json::from_str::<BTreeMap<String, Value>>(&cell)
+                    .map_err(|_| invalid("Attributes must be a JSON object".to_string()))
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        Self::identified(ProductImportRow {
+            line,
+            sku: sku.clone(),
+            product: CreateProductDto {
+                external_id: external_id.clone(),
+                name: row.name,
+                description: non_blank(row.description),
+                price,
+                currency: non_blank(row.currency),
+                image_url: non_blank(row.image_url),
+                attributes,
+                category_ids,
+                status: row.status,
+                published_at: row.published_at,
+            },
+        })
+    }
+
+    fn parse_ndjson(
+        bytes: &[u8],
+    ) -> Result<Vec<Result<ProductImportRow, InvalidImportRow>>, AppError> {
+        let text = std::str::from_utf8(bytes)
+            .map_err(|_| AppError::Invalid("NDJSON files must be UTF-8".to_string()))?;
+
+        let mut rows = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            rows.push(Self::from_json_line(index as i64 + 1, line));
+        }
+
+        Ok(rows)
+    }
+
+    /// Lines are objects of [`CreateProductDto`] with `external_id` and `sku`.
+    fn from_json_line(line: i64, json: &str) -> Result<ProductImportRow, InvalidImportRow> {
+        let invalid =
+            |external_id: Option<String>, sku: Option<String>, message: String| InvalidImportRow {
+                line,
+                external_id,
+                sku,
+                message,
+            };
+
+        let object = match serde_json::from_str::<Value>(json) {
+            Ok(Value::Object(object)) => object,
+            Ok(_) => return Err(invalid(None, None, "Line is not a JSON object".to_string())),
+            Err(err) => return Err(invalid(None, None, err.to_string())),
+        };
+
+        let sku = non_blank(
+            object
+                .get("sku")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        );
+        let external_id = non_blank(
+            object
+                .get("external_id")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        );
+
+        // Read from the text rather than the object so prices keep their exact
+        // decimals; `id` and `sku` are ignored as unknown fields.
+        let mut product = serde_json::from_str::<CreateProductDto>(json)
+            .map_err(|err| invalid(external_id.clone(), sku.clone(), err.to_string()))?;
+        product.external_id = external_id;
+
+        Self::identified(ProductImportRow { line, sku, product })
+    }
+
+    /// Rows have to say which product they are about and pass the same
+    /// validation as [`CreateProductDto`] request bodies.
+    fn identified(row: ProductImportRow) -> Result<ProductImportRow, InvalidImportRow> {
+        if row.product.external_id.is_none() && row.sku.is_none() {
+            return Err(InvalidImportRow {
+                line: row.line,
+                external_id: None,
+                sku: None,
+                message: "Row needs an `external_id` or a `sku`".to_string(),
+            });
+        }
+
+        if let Err(err) = row.product.validate() {
+            return Err(InvalidImportRow {
+                line: row.line,
+                external_id: row.product.external_id,
+                sku: row.sku,
+                message: err.to_string(),
+            });
+        }
+
+        Ok(row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn parse(format: ImportFormat, file: &str) -> Vec<Result<ProductImportRow, InvalidImportRow>> {
+        ProductImportRow::parse(format, file.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn csv_rows_become_products() {
+        let category_id = Uuid::new_v4();
+        let file = format!(
+            "external_id,sku,name,price,category_ids,attributes,status\n\
+             ext-1, SKU-1 ,Red Shoe,19.990,{}|,\"{{\"\"color\"\":\"\"red\"\"}}\",active\n",
+            category_id
+        );
+
+        let rows = parse(ImportFormat::Csv, &file);
+        let row = rows[0].as_ref().unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(row.line, 2);
+        assert_eq!(row.sku.as_deref(), Some("SKU-1"));
+        assert_eq!(row.product.external_id.as_deref(), Some("ext-1"));
+        assert_eq!(row.product.name, "Red Shoe");
+        assert_eq!(row.product.price, BigDecimal::from_str("19.990").unwrap());
+        assert_eq!(row.product.category_ids, [category_id]);
+        assert_eq!(row.product.attributes["color"], "red");
+        assert_eq!(row.product.status, Some(ProductStatus::Active));
+    }
+
+    #[test]
+    fn csv_reports_bad_rows_and_keeps_the_rest() {
+        let file = "external_id,sku,name,price,category_ids\n\
+                    ext-1,,Shoe,cheap,\n\
+                    ext-2,,Boot,10,not-a-uuid\n\
+                    ,,Sock,2,\n\
+                    ext-4,,Hat,5,\n";
+
+        let rows = parse(ImportFormat::Csv, file);
+        let errors: Vec<(i64, &str)> = rows
+            .iter()
+            .filter_map(|row| row.as_ref().err())
+            .map(|row| (row.line, row.message.as_str()))
+            .collect();
+
+        assert_eq!(
+            errors,
+            [
+                (2, "Price 'cheap' is not a number"),
+                (3, "Category id 'not-a-uuid' is not a UUID"),
+                (4, "Row needs an `external_id` or a `sku`"),
+            ]
+        );
+        assert_eq!(rows[3].as_ref().unwrap().product.name, "Hat");
+    }
+
+    #[test]
+    fn csv_without_required_columns_is_rejected() {
+        let result = ProductImportRow::parse(ImportFormat::Csv, b"sku,name\nSKU-1,Shoe\n");
+
+        assert!(matches!(result, Err(AppError::Invalid(_))));
+    }
+
+    #[test]
+    fn ndjson_lines_become_products() {
+        let file = "{\"sku\":\"SKU-1\",\"name\":\"Shoe\",\"price\":19.990}\n\
+                    \n\
+                    [1, 2]\n\
+                    {\"external_id\":\"ext-3\",\"name\":\"\",\"price\":1}\n";
+
+        let rows = parse(ImportFormat::Ndjson, file);
+
+        assert_eq!(rows.len(), 3);
+        let row = rows[0].as_ref().unwrap();
+        assert_eq!(row.sku.as_deref(), Some("SKU-1"));
+        assert_eq!(row.product.price, BigDecimal::from_str("19.990").unwrap());
+
+        let not_an_object = rows[1].as_ref().unwrap_err();
+        assert_eq!(not_an_object.line, 3);
+        assert_eq!(not_an_object.message, "Line is not a JSON object");
+
+        let blank_name = rows[2].as_ref().unwrap_err();
+        assert_eq!(blank_name.line, 4);
+        assert_eq!(blank_name.external_id.as_deref(), Some("ext-3"));
+    }
+}
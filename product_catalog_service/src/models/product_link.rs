@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::dtos::product_link_dto::ProductLinkDto;
+use crate::traits::to_dto::ToDto;
+
+/// How a linked product relates to the product it is shown with.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "product_link_type", rename_all = "snake_case")]
+pub enum ProductLinkType {
+    Related,
+    /// Goes with the product, e.g. a case for a phone.
+    Accessory,
+    /// A more expensive alternative.
+    UpSell,
+    /// Takes over from a product that is no longer sold.
+    Replacement,
+}
+
+/// A link to another product, with the linked product's name and slug.
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct ProductLink {
+    pub product_id: Uuid,
+    pub linked_product_id: Uuid,
+    pub link_type: ProductLinkType,
+    pub position: i32,
+    pub name: String,
+    pub slug: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ToDto<ProductLinkDto> for ProductLink {
+    fn to_dto(&self) -> ProductLinkDto {
+        ProductLinkDto {
+            product_id: self.linked_product_id,
+            link_type: self.link_type,
+            position: self.position,
+            name: self.name.clone(),
+            slug: self.slug.clone(),
+            created_at: self.created_at,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::dtos::product_option_dto::ProductOptionDto;
+use crate::models::app_error::AppError;
+use crate::traits::to_dto::ToDto;
+
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct ProductOption {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub name: String,
+    pub position: i32,
+    pub values: Vec<String>,
+}
+
+/// Checks that a variant picks exactly one allowed value for every option axis.
+pub fn validate_option_values(
+    options: &[ProductOption],
+    values: &BTreeMap<String, String>,
+) -> Result<(), AppError> {
+    if let Some(unknown) = values
+        .keys()
+        .find(|name| !options.iter().any(|option| &option.name == *name))
+    {
+        return Err(AppError::Invalid(format!(
+            "Product has no option named '{}'",
+            unknown
+        )));
+    }
+
+    for option in options {
+        match values.get(&option.name) {
+            Some(value) if option.values.contains(value) => {}
+            Some(value) => {
+                return Err(AppError::Invalid(format!(
+                    "'{}' is not a value of option '{}'",
+                    value, option.name
+                )));
+            }
+            None => {
+                return Err(AppError::Invalid(format!(
+                    "Variant is missing a value for option '{}'",
+                    option.name
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl ToDto<ProductOptionDto> for ProductOption {
+    fn to_dto(&self) -> ProductOptionDto {
+        ProductOptionDto {
+            name: self.name.clone(),
+            values: self.values.clone(),
+        }
+    }
+}
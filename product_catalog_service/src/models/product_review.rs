@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::dtos::product_review_dto::ProductReviewDto;
+use crate::traits::to_dto::ToDto;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "review_status", rename_all = "snake_case")]
+pub enum ReviewStatus {
+    /// Waiting for a moderator; not shown and not counted in the rating.
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct ProductReview {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub author_id: Uuid,
+    pub rating: i16,
+    pub title: String,
+    pub body: Option<String>,
+    pub verified_purchase: bool,
+    pub status: ReviewStatus,
+    pub moderation_note: Option<String>,
+    pub moderated_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ToDto<ProductReviewDto> for ProductReview {
+    fn to_dto(&self) -> ProductReviewDto {
+        ProductReviewDto {
+            id: self.id,
+            product_id: self.product_id,
+            author_id: self.author_id,
+            rating: self.rating,
+            title: self.title.clone(),
+            body: self.body.clone(),
+            verified_purchase: self.verified_purchase,
+            status: self.status,
+            moderation_note: self.moderation_note.clone(),
+            moderated_at: self.moderated_at,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use sqlx::FromRow;
+
+use crate::dtos::product_search_match_dto::ProductSearchMatchDto;
+use crate::models::{cursor::Cursor, product::Product};
+use crate::traits::{to_cursor::ToCursor, to_dto::ToDto};
+
+/// A product matched by a full-text search, with its relevance and snippets.
+#[derive(Debug, FromRow)]
+pub struct ProductSearchHit {
+    #[sqlx(flatten)]
+    pub product: Product,
+    pub rank: f32,
+    pub name_highlight: String,
+    pub description_highlight: Option<String>,
+}
+
+impl ToDto<ProductSearchMatchDto> for ProductSearchHit {
+    fn to_dto(&self) -> ProductSearchMatchDto {
+        ProductSearchMatchDto {
+            rank: self.rank,
+            name_highlight: self.name_highlight.clone(),
+            description_highlight: self.description_highlight.clone(),
+        }
+    }
+}
+
+impl ToCursor for ProductSearchHit {
+    fn to_cursor(&self, order_by: &str) -> Cursor {
+        match order_by.split(':').next().unwrap_or_default() {
+            "relevance" => Cursor {
+                order_by: order_by.to_string(),
+                value: self.rank.to_string(),
+                id: self.product.id,
+            },
+            _ => self.product.to_cursor(order_by),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use serde::Deserialize;
+
+/// Orderings offered on the product list.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProductSort {
+    Newest,
+    Price,
+    Name,
+    /// Average review rating.
+    Rating,
+    /// Full-text search rank; only available together with `q`.
+    Relevance,
+}
+
+impl ProductSort {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProductSort::Newest => "newest",
+            ProductSort::Price => "price",
+            ProductSort::Name => "name",
+            ProductSort::Rating => "rating",
+            ProductSort::Relevance => "relevance",
+        }
+    }
+
+    /// Identifies the ordering inside pagination cursors, e.g. `price:asc`.
+    pub fn cursor_key(&self, direction: SortDirection) -> String {
+        format!("{}:{}", self.as_str(), direction.as_str())
+    }
+
+    /// Direction used when the caller does not ask for one.
+    pub fn default_direction(&self) -> SortDirection {
+        match self {
+            ProductSort::Price | ProductSort::Name => SortDirection::Asc,
+            ProductSort::Newest | ProductSort::Rating | ProductSort::Relevance => {
+                SortDirection::Desc
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    pub fn reverse(&self) -> SortDirection {
+        match self {
+            SortDirection::Asc => SortDirection::Desc,
+            SortDirection::Desc => SortDirection::Asc,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "asc",
+            SortDirection::Desc => "desc",
+        }
+    }
+
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        }
+    }
+
+    /// Comparison that selects the rows after a keyset cursor.
+    pub fn after_operator(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => ">",
+            SortDirection::Desc => "<",
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::dtos::product_translation_dto::ProductTranslationDto;
+use crate::traits::to_dto::ToDto;
+
+/// A product's name, description and slug in a locale other than the default.
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct ProductTranslation {
+    pub product_id: Uuid,
+    pub locale: String,
+    pub name: String,
+    /// `None` shows the product's own description.
+    pub description: Option<String>,
+    pub slug: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ToDto<ProductTranslationDto> for ProductTranslation {
+    fn to_dto(&self) -> ProductTranslationDto {
+        ProductTranslationDto {
+            locale: self.locale.clone(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            slug: self.slug.clone(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
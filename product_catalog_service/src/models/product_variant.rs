@@ -0,0 +1,40 @@
+use std::collections::BTreeMap;
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, types::Json};
+use uuid::Uuid;
+
+use crate::dtos::product_variant_dto::ProductVariantDto;
+use crate::traits::to_dto::ToDto;
+
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct ProductVariant {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub sku: String,
+    pub price: Option<BigDecimal>,
+    pub barcode: Option<String>,
+    pub weight_grams: Option<i32>,
+    pub image_url: Option<String>,
+    pub options: Json<BTreeMap<String, String>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ToDto<ProductVariantDto> for ProductVariant {
+    fn to_dto(&self) -> ProductVariantDto {
+        ProductVariantDto {
+            id: self.id,
+            sku: self.sku.clone(),
+            price: self.price.clone(),
+            barcode: self.barcode.clone(),
+            weight_grams: self.weight_grams,
+            image_url: self.image_url.clone(),
+            options: self.options.0.clone(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use serde::Deserialize;
+
+/// Which products are shown. Shoppers get the public catalog; `all` and
+/// `deleted` are for the admin routes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProductVisibility {
+    /// Active products whose `published_at` has passed.
+    #[default]
+    Public,
+    /// Every product that is not deleted, whatever its status.
+    All,
+    /// Deleted products, which can still be restored.
+    Deleted,
+}
@@ -0,0 +1,12 @@
+use sqlx::FromRow;
+
+use crate::models::{product::Product, product_link::ProductLinkType};
+
+/// A product shown with another one.
+#[derive(Debug, FromRow)]
+pub struct RelatedProduct {
+    #[sqlx(flatten)]
+    pub product: Product,
+    /// `None` for products picked from the same categories.
+    pub link_type: Option<ProductLinkType>,
+}
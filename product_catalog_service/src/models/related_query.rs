@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+use crate::models::product_link::ProductLinkType;
+
+#[derive(Debug, Deserialize)]
+pub struct RelatedQuery {
+    /// Only links of this type; all types otherwise.
+    #[serde(rename = "type")]
+    pub link_type: Option<ProductLinkType>,
+    pub limit: Option<i64>,
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::dtos::reservation_item_dto::ReservationItemDto;
+use crate::traits::to_dto::ToDto;
+
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct ReservationItem {
+    pub reservation_id: Uuid,
+    pub variant_id: Uuid,
+    pub sku: String,
+    pub warehouse_id: Uuid,
+    pub warehouse_code: String,
+    pub quantity: i32,
+}
+
+impl ToDto<ReservationItemDto> for ReservationItem {
+    fn to_dto(&self) -> ReservationItemDto {
+        ReservationItemDto {
+            sku: self.sku.clone(),
+            warehouse_code: self.warehouse_code.clone(),
+            quantity: self.quantity,
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+use crate::models::product_review::ReviewStatus;
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewQuery {
+    pub status: Option<ReviewStatus>,
+}
@@ -0,0 +1,53 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::dtos::scheduled_price_change_dto::ScheduledPriceChangeDto;
+use crate::traits::to_dto::ToDto;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "scheduled_price_status", rename_all = "snake_case")]
+pub enum ScheduledPriceStatus {
+    Scheduled,
+    /// Started and waiting for its end to restore the previous price.
+    Active,
+    Completed,
+    Cancelled,
+    /// Could not be applied; `failure` says why.
+    Failed,
+}
+
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct ScheduledPriceChange {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub price: BigDecimal,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+    pub status: ScheduledPriceStatus,
+    pub previous_price: Option<BigDecimal>,
+    pub failure: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ToDto<ScheduledPriceChangeDto> for ScheduledPriceChange {
+    fn to_dto(&self) -> ScheduledPriceChangeDto {
+        ScheduledPriceChangeDto {
+            id: self.id,
+            price: self.price.clone(),
+            starts_at: self.starts_at,
+            ends_at: self.ends_at,
+            note: self.note.clone(),
+            status: self.status,
+            previous_price: self.previous_price.clone(),
+            failure: self.failure.clone(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
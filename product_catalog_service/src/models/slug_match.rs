@@ -0,0 +1,11 @@
+use uuid::Uuid;
+
+/// The product a slug leads to.
+#[derive(Debug)]
+pub struct SlugMatch {
+    pub product_id: Uuid,
+    /// Locale of the translation the slug belongs to.
+    pub locale: Option<String>,
+    /// The product's current slug when the requested one is an old slug.
+    pub redirect_to: Option<String>,
+}
@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct SuggestQuery {
+    /// What the user has typed so far.
+    pub q: String,
+    pub limit: Option<i64>,
+}
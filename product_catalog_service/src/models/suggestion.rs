@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::dtos::suggestion_dto::SuggestionDto;
+use crate::traits::to_dto::ToDto;
+
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct Suggestion {
+    /// Either `product` or `category`.
+    pub kind: String,
+    pub id: Uuid,
+    pub name: String,
+    pub score: f64,
+}
+
+impl ToDto<SuggestionDto> for Suggestion {
+    fn to_dto(&self) -> SuggestionDto {
+        SuggestionDto {
+            id: self.id,
+            name: self.name.clone(),
+            score: self.score,
+        }
+    }
+}
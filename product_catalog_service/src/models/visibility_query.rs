@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+use crate::models::product_visibility::ProductVisibility;
+
+#[derive(Debug, Deserialize)]
+pub struct VisibilityQuery {
+    /// Which products the admin product list shows.
+    pub visibility: Option<ProductVisibility>,
+}
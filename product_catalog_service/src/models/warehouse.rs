@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::dtos::warehouse_dto::WarehouseDto;
+use crate::traits::to_dto::ToDto;
+
+#[derive(Debug, FromRow, Deserialize, Serialize)]
+pub struct Warehouse {
+    pub id: Uuid,
+    pub code: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ToDto<WarehouseDto> for Warehouse {
+    fn to_dto(&self) -> WarehouseDto {
+        WarehouseDto {
+            id: self.id,
+            code: self.code.clone(),
+            name: self.name.clone(),
+            created_at: self.created_at,
+        }
+    }
+}
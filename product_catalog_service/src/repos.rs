@@ -1,2 +1,19 @@
+pub mod attribute_repo;
+pub mod category_repo;
+pub mod import_job_repo;
+pub mod inventory_repo;
+pub mod outbox_repo;
+pub mod price_history_repo;
+pub mod price_list_repo;
+pub mod product_bundle_repo;
+pub mod product_image_repo;
+pub mod product_link_repo;
 pub mod product_repo;
+pub mod product_review_repo;
+pub mod product_translation_repo;
+pub mod product_variant_repo;
 pub mod repository_traits;
+pub mod reservation_repo;
+pub mod scheduled_price_repo;
+pub mod suggestion_repo;
+pub mod warehouse_repo;
@@ -0,0 +1,212 @@
+use sqlx::{PgConnection, PgPool};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::{
+        create_attribute_definition_dto::CreateAttributeDefinitionDto,
+        update_attribute_definition_dto::UpdateAttributeDefinitionDto,
+    },
+    models::{
+        app_error::AppError,
+        attribute_definition::{AttributeDefinition, AttributeType},
+    },
+};
+
+pub struct AttributeRepo {
+    pub pool: PgPool,
+}
+
+impl AttributeRepo {
+    /// The attribute schema a product has to satisfy: the definitions of its
+    /// categories and their ancestors. A definition on a deeper category wins
+    /// over one of the same name further up.
+    pub async fn schema_for_product(
+        conn: &mut PgConnection,
+        product_id: Uuid,
+    ) -> Result<Vec<AttributeDefinition>, AppError> {
+        let definitions = sqlx::query_as!(
+            AttributeDefinition,
+            r#"
+            SELECT DISTINCT ON (d.name)
+                d.id, d.category_id, d.name, d.attribute_type AS "attribute_type: AttributeType",
+                d.unit, d.required, d.allowed_values, d.created_at, d.updated_at
+            FROM attribute_definitions d
+            JOIN categories c ON c.id = d.category_id
+            WHERE EXISTS (
+                SELECT 1 FROM product_categories pc
+                JOIN categories pcat ON pcat.id = pc.category_id
+                WHERE pc.product_id = $1 AND pcat.path LIKE c.path || '%'
+            )
+            ORDER BY d.name, length(c.path) DESC
+            "#,
+            product_id
+        )
+        .fetch_all(conn)
+        .await?;
+
+        Ok(definitions)
+    }
+
+    fn validate_allowed_values(
+        attribute_type: AttributeType,
+        allowed_values: &[String],
+    ) -> Result<(), AppError> {
+        match (attribute_type, allowed_values.is_empty()) {
+            (AttributeType::Enum, true) => Err(AppError::Invalid(
+                "Enum attributes need at least one allowed value".to_string(),
+            )),
+            (AttributeType::Enum, false) => Ok(()),
+            (_, false) => Err(AppError::Invalid(
+                "Only enum attributes take allowed values".to_string(),
+            )),
+            (_, true) => Ok(()),
+        }
+    }
+
+    /// Definitions that apply to products of the category, including those
+    /// inherited from its ancestors.
+    #[instrument(skip(self))]
+    pub async fn get_for_category(
+        &self,
+        category_id: Uuid,
+    ) -> Result<Vec<AttributeDefinition>, AppError> {
+        let definitions = sqlx::query_as!(
+            AttributeDefinition,
+            r#"
+            SELECT DISTINCT ON (d.name)
+                d.id, d.category_id, d.name, d.attribute_type AS "attribute_type: AttributeType",
+                d.unit, d.required, d.allowed_values, d.created_at, d.updated_at
+            FROM attribute_definitions d
+            JOIN categories c ON c.id = d.category_id
+            WHERE (SELECT path FROM categories WHERE id = $1) LIKE c.path || '%'
+            ORDER BY d.name, length(c.path) DESC
+            "#,
+            category_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(definitions)
+    }
+
+    #[instrument(skip(self, data))]
+    pub async fn create(
+        &self,
+        category_id: Uuid,
+        data: &CreateAttributeDefinitionDto,
+    ) -> Result<AttributeDefinition, AppError> {
+        if data.name.trim().is_empty() {
+            return Err(AppError::Invalid(
+                "Attribute name must not be empty".to_string(),
+            ));
+        }
+        Self::validate_allowed_values(data.attribute_type, &data.allowed_values)?;
+
+        let definition = sqlx::query_as!(
+            AttributeDefinition,
+            r#"
+            INSERT INTO attribute_definitions
+                (category_id, name, attribute_type, unit, required, allowed_values)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING
+                id, category_id, name, attribute_type AS "attribute_type: AttributeType",
+                unit, required, allowed_values, created_at, updated_at
+            "#,
+            category_id,
+            data.name.trim(),
+            data.attribute_type as AttributeType,
+            data.unit,
+            data.required,
+            &data.allowed_values
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(definition)
+    }
+
+    #[instrument(skip(self))]
+    async fn get(&self, category_id: Uuid, id: Uuid) -> Result<AttributeDefinition, AppError> {
+        let definition = sqlx::query_as!(
+            AttributeDefinition,
+            r#"
+            SELECT
+                id, category_id, name, attribute_type AS "attribute_type: AttributeType",
+                unit, required, allowed_values, created_at, updated_at
+            FROM attribute_definitions
+            WHERE id = $1 AND category_id = $2
+            "#,
+            id,
+            category_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Attribute definition not found".to_string()))?;
+
+        Ok(definition)
+    }
+
+    /// Existing product values are not re-checked; the new rules apply the
+    /// next time a product is written.
+    #[instrument(skip(self, data))]
+    pub async fn update(
+        &self,
+        category_id: Uuid,
+        id: Uuid,
+        data: &UpdateAttributeDefinitionDto,
+    ) -> Result<AttributeDefinition, AppError> {
+        let existing = self.get(category_id, id).await?;
+        if let Some(allowed_values) = &data.allowed_values {
+            Self::validate_allowed_values(existing.attribute_type, allowed_values)?;
+        }
+
+        let definition = sqlx::query_as!(
+            AttributeDefinition,
+            r#"
+            UPDATE attribute_definitions
+            SET
+                unit = COALESCE($1, unit),
+                required = COALESCE($2, required),
+                allowed_values = COALESCE($3, allowed_values),
+                updated_at = NOW()
+            WHERE id = $4 AND category_id = $5
+            RETURNING
+                id, category_id, name, attribute_type AS "attribute_type: AttributeType",
+                unit, required, allowed_values, created_at, updated_at
+            "#,
+            data.unit,
+            data.required,
+            data.allowed_values.as_deref(),
+            id,
+            category_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Attribute definition not found".to_string()))?;
+
+        Ok(definition)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn delete(&self, category_id: Uuid, id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM attribute_definitions
+            WHERE id = $1 AND category_id = $2
+            "#,
+            id,
+            category_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(
+                "Attribute definition not found".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
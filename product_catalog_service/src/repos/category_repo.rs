@@ -0,0 +1,305 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+use sqlx::{PgPool, types::Json};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::{create_category_dto::CreateCategoryDto, update_category_dto::UpdateCategoryDto},
+    models::{
+        app_error::AppError, attribute_definition::validate_attribute_values, category::Category,
+    },
+    repos::attribute_repo::AttributeRepo,
+};
+
+pub struct CategoryRepo {
+    pub pool: PgPool,
+}
+
+impl CategoryRepo {
+    #[instrument(skip(self, data))]
+    pub async fn create(&self, data: &CreateCategoryDto) -> Result<Category, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // The share lock makes a concurrent move of the parent's subtree
+        // either wait for the new child or finish before the path is read.
+        let parent_path = match data.parent_id {
+            Some(parent_id) => sqlx::query_scalar!(
+                r#"
+                SELECT path FROM categories
+                WHERE id = $1
+                FOR SHARE
+                "#,
+                parent_id
+            )
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Category not found".to_string()))?,
+            None => String::from("/"),
+        };
+
+        let id = Uuid::new_v4();
+        let category = sqlx::query_as!(
+            Category,
+            r#"
+            INSERT INTO categories (id, parent_id, name, path)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+            id,
+            data.parent_id,
+            data.name,
+            format!("{}{}/", parent_path, id)
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(category)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_all(&self) -> Result<Vec<Category>, AppError> {
+        let categories = sqlx::query_as!(
+            Category,
+            r#"
+            SELECT * FROM categories
+            ORDER BY name
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(categories)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_by_id(&self, id: Uuid) -> Result<Category, AppError> {
+        let category = sqlx::query_as!(
+            Category,
+            r#"
+            SELECT * FROM categories
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Category not found".to_string()))?;
+
+        Ok(category)
+    }
+
+    #[instrument(skip(self, data))]
+    pub async fn update(&self, id: Uuid, data: &UpdateCategoryDto) -> Result<Category, AppError> {
+        let category = sqlx::query_as!(
+            Category,
+            r#"
+            UPDATE categories
+            SET name = $1, updated_at = NOW()
+            WHERE id = $2
+            RETURNING *
+            "#,
+            data.name,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Category not found".to_string()))?;
+
+        Ok(category)
+    }
+
+    /// Re-parents a category, rewriting the path of its whole subtree.
+    #[instrument(skip(self))]
+    pub async fn move_subtree(
+        &self,
+        id: Uuid,
+        parent_id: Option<Uuid>,
+    ) -> Result<Category, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let old_path = sqlx::query_scalar!(
+            r#"
+            SELECT path FROM categories
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Category not found".to_string()))?;
+
+        let parent_path = match parent_id {
+            Some(parent_id) => sqlx::query_scalar!(
+                r#"
+                SELECT path FROM categories
+                WHERE id = $1
+                FOR UPDATE
+                "#,
+                parent_id
+            )
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Parent category not found".to_string()))?,
+            None => String::from("/"),
+        };
+
+        if parent_path.starts_with(&old_path) {
+            return Err(AppError::Invalid(
+                "Category cannot be moved under itself or one of its descendants".to_string(),
+            ));
+        }
+
+        let new_path = format!("{}{}/", parent_path, id);
+
+        // Waits for children being added under the subtree, so the rewrite
+        // below, which reads a fresh snapshot, includes them.
+        sqlx::query!(
+            r#"
+            SELECT id FROM categories
+            WHERE path LIKE $1 || '%'
+            FOR UPDATE
+            "#,
+            old_path
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE categories
+            SET path = $2 || SUBSTRING(path FROM LENGTH($1) + 1), updated_at = NOW()
+            WHERE path LIKE $1 || '%'
+            "#,
+            old_path,
+            new_path
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let category = sqlx::query_as!(
+            Category,
+            r#"
+            UPDATE categories
+            SET parent_id = $2
+            WHERE id = $1
+            RETURNING *
+            "#,
+            id,
+            parent_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(category)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn delete(&self, id: Uuid) -> Result<(), AppError> {
+        let has_children = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (SELECT 1 FROM categories WHERE parent_id = $1) AS "exists!"
+            "#,
+            id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        if has_children {
+            return Err(AppError::Conflict(
+                "Category still has subcategories".to_string(),
+            ));
+        }
+
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM categories
+            WHERE id = $1
+            "#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Category not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_for_product(&self, product_id: Uuid) -> Result<Vec<Category>, AppError> {
+        let categories = sqlx::query_as!(
+            Category,
+            r#"
+            SELECT c.* FROM categories c
+            JOIN product_categories pc ON pc.category_id = c.id
+            WHERE pc.product_id = $1
+            ORDER BY c.path
+            "#,
+            product_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(categories)
+    }
+
+    /// Replaces the set of categories a product is assigned to.
+    #[instrument(skip(self))]
+    pub async fn set_for_product(
+        &self,
+        product_id: Uuid,
+        category_ids: &[Uuid],
+    ) -> Result<Vec<Category>, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM product_categories
+            WHERE product_id = $1
+            "#,
+            product_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO product_categories (product_id, category_id)
+            SELECT $1, category_id FROM UNNEST($2::UUID[]) AS category_id
+            ON CONFLICT DO NOTHING
+            "#,
+            product_id,
+            category_ids
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // The product's attribute values have to fit the new categories' schema.
+        let Json(attributes) = sqlx::query_scalar!(
+            r#"
+            SELECT attributes AS "attributes: Json<BTreeMap<String, Value>>"
+            FROM products
+            WHERE id = $1
+            "#,
+            product_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let schema = AttributeRepo::schema_for_product(&mut tx, product_id).await?;
+        validate_attribute_values(&schema, &attributes)?;
+
+        tx.commit().await?;
+
+        self.get_for_product(product_id).await
+    }
+}
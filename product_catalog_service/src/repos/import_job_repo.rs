@@ -0,0 +1,150 @@
+use sqlx::{PgPool, types::Json};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::models::{
+    app_error::AppError,
+    import_job::{ImportFormat, ImportJob, ImportJobStatus, ImportRowError},
+};
+
+pub struct ImportJobRepo {
+    pub pool: PgPool,
+}
+
+impl ImportJobRepo {
+    #[instrument(skip(self))]
+    pub async fn create(&self, format: ImportFormat) -> Result<ImportJob, AppError> {
+        let job = sqlx::query_as!(
+            ImportJob,
+            r#"
+            INSERT INTO product_import_jobs (format)
+            VALUES ($1)
+            RETURNING
+                id, format AS "format: ImportFormat", status AS "status: ImportJobStatus",
+                total_rows, processed_rows, created_rows, updated_rows, failed_rows,
+                errors AS "errors: Json<Vec<ImportRowError>>", failure,
+                created_at, started_at, finished_at
+            "#,
+            format as ImportFormat
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_by_id(&self, id: Uuid) -> Result<ImportJob, AppError> {
+        let job = sqlx::query_as!(
+            ImportJob,
+            r#"
+            SELECT
+                id, format AS "format: ImportFormat", status AS "status: ImportJobStatus",
+                total_rows, processed_rows, created_rows, updated_rows, failed_rows,
+                errors AS "errors: Json<Vec<ImportRowError>>", failure,
+                created_at, started_at, finished_at
+            FROM product_import_jobs
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Import not found".to_string()))?;
+
+        Ok(job)
+    }
+
+    /// Marks the job as running once the file has been read.
+    #[instrument(skip(self))]
+    pub async fn start(&self, id: Uuid, total_rows: i32) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE product_import_jobs
+            SET status = 'running', total_rows = $2, started_at = NOW()
+            WHERE id = $1
+            "#,
+            id,
+            total_rows
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Adds the rows processed since the last call to the job's counters and
+    /// appends their errors to the report.
+    #[instrument(skip(self, errors))]
+    pub async fn record_progress(
+        &self,
+        id: Uuid,
+        created_rows: i32,
+        updated_rows: i32,
+        failed_rows: i32,
+        errors: &[ImportRowError],
+    ) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE product_import_jobs
+            SET
+                processed_rows = processed_rows + $2 + $3 + $4,
+                created_rows = created_rows + $2,
+                updated_rows = updated_rows + $3,
+                failed_rows = failed_rows + $4,
+                errors = errors || $5
+            WHERE id = $1
+            "#,
+            id,
+            created_rows,
+            updated_rows,
+            failed_rows,
+            Json(errors) as _
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Completes the job, or fails it with the reason when `failure` is set.
+    #[instrument(skip(self))]
+    pub async fn finish(&self, id: Uuid, failure: Option<&str>) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE product_import_jobs
+            SET
+                status = CASE WHEN $2::TEXT IS NULL THEN 'completed' ELSE 'failed' END::import_job_status,
+                failure = $2,
+                finished_at = NOW()
+            WHERE id = $1
+            "#,
+            id,
+            failure
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Fails imports left unfinished by a previous run of the service; their
+    /// files were only kept in memory.
+    #[instrument(skip(self))]
+    pub async fn fail_interrupted(&self) -> Result<u64, AppError> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE product_import_jobs
+            SET
+                status = 'failed',
+                failure = 'Interrupted by a restart of the service; upload the file again',
+                finished_at = NOW()
+            WHERE status IN ('queued', 'running')
+            "#
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
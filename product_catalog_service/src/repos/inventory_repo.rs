@@ -0,0 +1,300 @@
+use sqlx::PgPool;
+use tracing::instrument;
+
+use crate::{
+    dtos::{
+        inventory_adjustment_dto::InventoryAdjustmentDto, inventory_audit_dto::InventoryAuditDto,
+    },
+    models::{
+        app_error::AppError,
+        inventory_ledger_entry::{InventoryLedgerEntry, InventoryReason},
+        inventory_level::InventoryLevel,
+    },
+};
+
+pub struct InventoryRepo {
+    pub pool: PgPool,
+}
+
+impl InventoryRepo {
+    /// Records a ledger entry and applies it to the cached stock level in the
+    /// same transaction. Adjustments that would take stock below what is
+    /// currently reserved fail.
+    #[instrument(skip(self))]
+    pub async fn adjust(&self, data: &InventoryAdjustmentDto) -> Result<InventoryLevel, AppError> {
+        if data.quantity_delta == 0 {
+            return Err(AppError::Invalid(
+                "quantity_delta must not be zero".to_string(),
+            ));
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let variant_id = sqlx::query_scalar!(
+            r#"
+            SELECT id FROM product_variants
+            WHERE sku = $1
+            "#,
+            data.sku
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("SKU not found".to_string()))?;
+
+        let warehouse_id = sqlx::query_scalar!(
+            r#"
+            SELECT id FROM warehouses
+            WHERE code = $1
+            "#,
+            data.warehouse_code
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Warehouse not found".to_string()))?;
+
+        // Make sure a level row exists so concurrent adjustments serialise on it.
+        sqlx::query!(
+            r#"
+            INSERT INTO inventory_levels (variant_id, warehouse_id)
+            VALUES ($1, $2)
+            ON CONFLICT (variant_id, warehouse_id) DO NOTHING
+            "#,
+            variant_id,
+            warehouse_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let level = sqlx::query!(
+            r#"
+            SELECT on_hand, reserved FROM inventory_levels
+            WHERE variant_id = $1 AND warehouse_id = $2
+            FOR UPDATE
+            "#,
+            variant_id,
+            warehouse_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if (level.on_hand as i64) + (data.quantity_delta as i64) < level.reserved as i64 {
+            return Err(AppError::Conflict(format!(
+                "Insufficient stock: {} on hand, {} reserved",
+                level.on_hand, level.reserved
+            )));
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO inventory_ledger (variant_id, warehouse_id, quantity_delta, reason, note)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            variant_id,
+            warehouse_id,
+            data.quantity_delta,
+            data.reason as InventoryReason,
+            data.note
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let level = sqlx::query_as!(
+            InventoryLevel,
+            r#"
+            WITH updated AS (
+                UPDATE inventory_levels
+                SET on_hand = on_hand + $3, updated_at = NOW()
+                WHERE variant_id = $1 AND warehouse_id = $2
+                RETURNING *
+            )
+            SELECT u.variant_id, v.sku, u.warehouse_id, w.code AS warehouse_code,
+                   u.on_hand, u.reserved, u.low_stock_threshold, u.updated_at
+            FROM updated u
+            JOIN product_variants v ON v.id = u.variant_id
+            JOIN warehouses w ON w.id = u.warehouse_id
+            "#,
+            variant_id,
+            warehouse_id,
+            data.quantity_delta
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(level)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn set_low_stock_threshold(
+        &self,
+        sku: &str,
+        warehouse_code: &str,
+        threshold: i32,
+    ) -> Result<InventoryLevel, AppError> {
+        let level = sqlx::query_as!(
+            InventoryLevel,
+            r#"
+            WITH upserted AS (
+                INSERT INTO inventory_levels (variant_id, warehouse_id, low_stock_threshold)
+                SELECT v.id, w.id, $3
+                FROM product_variants v, warehouses w
+                WHERE v.sku = $1 AND w.code = $2
+                ON CONFLICT (variant_id, warehouse_id)
+                DO UPDATE SET low_stock_threshold = EXCLUDED.low_stock_threshold, updated_at = NOW()
+                RETURNING *
+            )
+            SELECT u.variant_id, v.sku, u.warehouse_id, w.code AS warehouse_code,
+                   u.on_hand, u.reserved, u.low_stock_threshold, u.updated_at
+            FROM upserted u
+            JOIN product_variants v ON v.id = u.variant_id
+            JOIN warehouses w ON w.id = u.warehouse_id
+            "#,
+            sku,
+            warehouse_code,
+            threshold
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("SKU or warehouse not found".to_string()))?;
+
+        Ok(level)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_levels(&self, skus: &[String]) -> Result<Vec<InventoryLevel>, AppError> {
+        let levels = sqlx::query_as!(
+            InventoryLevel,
+            r#"
+            SELECT l.variant_id, v.sku, l.warehouse_id, w.code AS warehouse_code,
+                   l.on_hand, l.reserved, l.low_stock_threshold, l.updated_at
+            FROM inventory_levels l
+            JOIN product_variants v ON v.id = l.variant_id
+            JOIN warehouses w ON w.id = l.warehouse_id
+            WHERE v.sku = ANY($1)
+            ORDER BY v.sku, w.code
+            "#,
+            skus
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(levels)
+    }
+
+    /// Returns the SKUs among `skus` that exist, so unknown ones can be reported.
+    #[instrument(skip(self))]
+    pub async fn existing_skus(&self, skus: &[String]) -> Result<Vec<String>, AppError> {
+        let existing = sqlx::query_scalar!(
+            r#"
+            SELECT sku FROM product_variants
+            WHERE sku = ANY($1)
+            "#,
+            skus
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(existing)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_low_stock(&self) -> Result<Vec<InventoryLevel>, AppError> {
+        let levels = sqlx::query_as!(
+            InventoryLevel,
+            r#"
+            SELECT l.variant_id, v.sku, l.warehouse_id, w.code AS warehouse_code,
+                   l.on_hand, l.reserved, l.low_stock_threshold, l.updated_at
+            FROM inventory_levels l
+            JOIN product_variants v ON v.id = l.variant_id
+            JOIN warehouses w ON w.id = l.warehouse_id
+            WHERE l.on_hand - l.reserved <= l.low_stock_threshold
+            ORDER BY v.sku, w.code
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(levels)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_ledger(&self, sku: &str) -> Result<Vec<InventoryLedgerEntry>, AppError> {
+        let entries = sqlx::query_as!(
+            InventoryLedgerEntry,
+            r#"
+            SELECT e.id, e.variant_id, e.warehouse_id, w.code AS warehouse_code,
+                   e.quantity_delta, e.reason AS "reason: InventoryReason", e.note, e.created_at
+            FROM inventory_ledger e
+            JOIN product_variants v ON v.id = e.variant_id
+            JOIN warehouses w ON w.id = e.warehouse_id
+            WHERE v.sku = $1
+            ORDER BY e.id DESC
+            "#,
+            sku
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    /// Lists stock levels whose cached `on_hand` disagrees with their ledger.
+    #[instrument(skip(self))]
+    pub async fn audit(&self) -> Result<Vec<InventoryAuditDto>, AppError> {
+        let discrepancies = sqlx::query_as!(
+            InventoryAuditDto,
+            r#"
+            SELECT v.sku, w.code AS warehouse_code, l.on_hand,
+                   COALESCE(s.total, 0) AS "ledger_on_hand!"
+            FROM inventory_levels l
+            JOIN product_variants v ON v.id = l.variant_id
+            JOIN warehouses w ON w.id = l.warehouse_id
+            LEFT JOIN (
+                SELECT variant_id, warehouse_id, SUM(quantity_delta)::BIGINT AS total
+                FROM inventory_ledger
+                GROUP BY variant_id, warehouse_id
+            ) s ON s.variant_id = l.variant_id AND s.warehouse_id = l.warehouse_id
+            WHERE l.on_hand <> COALESCE(s.total, 0)
+            ORDER BY v.sku, w.code
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(discrepancies)
+    }
+
+    /// Recomputes every cached stock level from the ledger, keeping thresholds.
+    /// Returns the number of levels that changed.
+    #[instrument(skip(self))]
+    pub async fn rebuild(&self) -> Result<u64, AppError> {
+        let result = sqlx::query!(
+            r#"
+            WITH totals AS (
+                SELECT variant_id, warehouse_id, SUM(quantity_delta)::INTEGER AS total
+                FROM inventory_ledger
+                GROUP BY variant_id, warehouse_id
+            ),
+            targets AS (
+                SELECT l.variant_id, l.warehouse_id, COALESCE(t.total, 0) AS total
+                FROM inventory_levels l
+                LEFT JOIN totals t
+                    ON t.variant_id = l.variant_id AND t.warehouse_id = l.warehouse_id
+                UNION
+                SELECT t.variant_id, t.warehouse_id, t.total
+                FROM totals t
+            )
+            INSERT INTO inventory_levels (variant_id, warehouse_id, on_hand)
+            SELECT variant_id, warehouse_id, total FROM targets
+            ON CONFLICT (variant_id, warehouse_id)
+            DO UPDATE SET on_hand = EXCLUDED.on_hand, updated_at = NOW()
+            WHERE inventory_levels.on_hand <> EXCLUDED.on_hand
+            "#
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,264 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::{PgConnection, PgPool, types::Json};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    models::{
+        app_error::AppError,
+        product::{Product, ProductStatus},
+        product_event::{ProductEvent, ProductEventType},
+    },
+    traits::event_sink::EventSink,
+};
+
+/// Events the relay claims per run.
+const RELAY_BATCH_SIZE: i64 = 100;
+
+/// How long a claimed batch stays reserved for the instance that claimed it.
+/// The relay stops publishing halfway through, so the claim never lapses
+/// while its events are still being published.
+const RELAY_CLAIM_MINUTES: i32 = 5;
+
+/// Delay before the events of a product are offered again after a failed
+/// delivery, doubling with every attempt up to the maximum.
+const RELAY_RETRY_BASE_SECONDS: i32 = 30;
+const RELAY_RETRY_MAX_SECONDS: i32 = 3600;
+
+/// Advisory lock taken while claiming, so only one batch is in flight and
+/// events of one product are never published out of order by two instances.
+const RELAY_LOCK_KEY: i64 = 0x7072_6f64_6576_7473;
+
+pub struct OutboxRepo {
+    pub pool: PgPool,
+}
+
+impl OutboxRepo {
+    /// Adds an event of the product to the outbox. Runs in the transaction
+    /// that changed the product; the product's counter hands out the sequence
+    /// number under its row lock, which keeps the sequence free of gaps and
+    /// duplicates even after published events were purged.
+    pub async fn record(
+        conn: &mut PgConnection,
+        product_id: Uuid,
+        event_type: ProductEventType,
+        payload: &impl Serialize,
+    ) -> Result<(), AppError> {
+        let payload = serde_json::to_value(payload).map_err(|err| {
+            tracing::error!(error = %err, "failed to serialize product event");
+            AppError::InternalServerError
+        })?;
+
+        let result = sqlx::query!(
+            r#"
+            WITH product AS (
+                UPDATE products
+                SET event_sequence = event_sequence + 1
+                WHERE id = $1
+                RETURNING event_sequence
+            )
+            INSERT INTO product_events (product_id, sequence, event_type, payload)
+            SELECT $1, event_sequence, $2, $3
+            FROM product
+            "#,
+            product_id,
+            event_type as ProductEventType,
+            payload
+        )
+        .execute(conn)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Product not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Adds an `updated` event carrying the product as it is now, for changes
+    /// made through its variants, translations, bundle or images. Runs in the
+    /// transaction that made the change, after it.
+    pub async fn record_updated(conn: &mut PgConnection, product_id: Uuid) -> Result<(), AppError> {
+        let product = sqlx::query_as!(
+            Product,
+            r#"
+            SELECT
+                id, external_id, name, slug, description, price, currency,
+                NULL::UUID AS "price_list_id?",
+                image_url, created_at, updated_at, version,
+                attributes AS "attributes: Json<BTreeMap<String, Value>>", rating, rating_count,
+                status AS "status: ProductStatus", published_at, deleted_at
+            FROM products
+            WHERE id = $1
+            "#,
+            product_id
+        )
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Product not found".to_string()))?;
+
+        Self::record(conn, product_id, ProductEventType::Updated, &product).await
+    }
+
+    /// Claims the oldest unpublished events for `claim`, skipping products
+    /// whose delivery failed and is waiting to be retried. Returns none while
+    /// another instance is claiming or still has a batch in flight.
+    async fn claim(&self, claim: Uuid) -> Result<Vec<ProductEvent>, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let locked = sqlx::query_scalar!(
+            r#"SELECT pg_try_advisory_xact_lock($1) AS "locked!""#,
+            RELAY_LOCK_KEY
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if !locked {
+            return Ok(Vec::new());
+        }
+
+        let in_flight = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM product_events
+                WHERE published_at IS NULL AND claimed_until > NOW()
+            ) AS "exists!"
+            "#
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if in_flight {
+            return Ok(Vec::new());
+        }
+
+        let mut events = sqlx::query_as!(
+            ProductEvent,
+            r#"
+            UPDATE product_events
+            SET claimed_by = $1, claimed_until = NOW() + make_interval(mins => $2)
+            WHERE id IN (
+                SELECT e.id FROM product_events e
+                WHERE e.published_at IS NULL
+                    AND NOT EXISTS (
+                        SELECT 1 FROM product_events b
+                        WHERE b.product_id = e.product_id
+                            AND b.published_at IS NULL
+                            AND b.next_attempt_at > NOW()
+                    )
+                ORDER BY e.id
+                LIMIT $3
+            )
+            RETURNING
+                id, product_id, sequence, event_type AS "event_type: ProductEventType",
+                payload, created_at
+            "#,
+            claim,
+            RELAY_CLAIM_MINUTES,
+            RELAY_BATCH_SIZE
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        events.sort_by_key(|event| event.id);
+
+        Ok(events)
+    }
+
+    /// Publishes the oldest unpublished events in order and returns how many
+    /// were delivered. The batch is claimed first and published outside of
+    /// any transaction. An event is only marked published once the sink took
+    /// it; after a failure, the events of that product back off while the
+    /// other products' events keep flowing. Returns 0 while another instance
+    /// is relaying.
+    #[instrument(skip(self, sink))]
+    pub async fn relay(&self, sink: &dyn EventSink) -> Result<usize, AppError> {
+        let claim = Uuid::new_v4();
+        let events = self.claim(claim).await?;
+        if events.is_empty() {
+            return Ok(0);
+        }
+
+        let deadline = Instant::now() + Duration::from_secs(RELAY_CLAIM_MINUTES as u64 * 60 / 2);
+
+        let mut published = Vec::new();
+        let mut failed = Vec::new();
+        let mut blocked = HashSet::new();
+
+        for event in &events {
+            if Instant::now() >= deadline {
+                break;
+            }
+            if blocked.contains(&event.product_id) {
+                continue;
+            }
+
+            match sink.publish(event).await {
+                Ok(()) => published.push(event.id),
+                Err(_) => {
+                    failed.push(event.id);
+                    blocked.insert(event.product_id);
+                }
+            }
+        }
+
+        // Releases the whole claim; events not attempted go out next run.
+        sqlx::query!(
+            r#"
+            UPDATE product_events
+            SET
+                published_at = CASE WHEN id = ANY($2) THEN NOW() END,
+                attempts = attempts + CASE WHEN id = ANY($2) OR id = ANY($3) THEN 1 ELSE 0 END,
+                next_attempt_at = CASE WHEN id = ANY($3)
+                    THEN NOW() + make_interval(secs => LEAST(
+                        $4::INTEGER * POWER(2, LEAST(attempts, 20)),
+                        $5::INTEGER
+                    ))
+                    ELSE next_attempt_at END,
+                claimed_by = NULL,
+                claimed_until = NULL
+            WHERE claimed_by = $1
+            "#,
+            claim,
+            &published,
+            &failed,
+            RELAY_RETRY_BASE_SECONDS,
+            RELAY_RETRY_MAX_SECONDS
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if !failed.is_empty() {
+            tracing::warn!(
+                failed = failed.len(),
+                "product events failed to deliver and will be retried"
+            );
+        }
+
+        Ok(published.len())
+    }
+
+    /// Deletes events published more than `retention_days` ago.
+    #[instrument(skip(self))]
+    pub async fn purge_published(&self, retention_days: i32) -> Result<u64, AppError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM product_events
+            WHERE published_at < NOW() - make_interval(days => $1)
+            "#,
+            retention_days
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,88 @@
+use bigdecimal::BigDecimal;
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    models::{
+        app_error::AppError,
+        price_history_entry::{PriceChangeSource, PriceHistoryEntry},
+        product_event::ProductEventType,
+    },
+    repos::outbox_repo::OutboxRepo,
+};
+
+pub struct PriceHistoryRepo {
+    pub pool: PgPool,
+}
+
+impl PriceHistoryRepo {
+    /// Records the product's current base price as a history entry and, unless
+    /// the product was just created, as a price change event. Runs in the
+    /// transaction that changed the price, after the change.
+    pub async fn record(
+        conn: &mut PgConnection,
+        product_id: Uuid,
+        previous_price: Option<&BigDecimal>,
+        previous_currency: Option<&str>,
+        source: PriceChangeSource,
+        scheduled_price_change_id: Option<Uuid>,
+    ) -> Result<(), AppError> {
+        let entry = sqlx::query!(
+            r#"
+            INSERT INTO price_history
+                (product_id, price, currency, previous_price, previous_currency, source,
+                 scheduled_price_change_id)
+            SELECT id, price, currency, $2, $3, $4, $5
+            FROM products
+            WHERE id = $1
+            RETURNING price, currency
+            "#,
+            product_id,
+            previous_price,
+            previous_currency,
+            source as PriceChangeSource,
+            scheduled_price_change_id
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        if source != PriceChangeSource::Created {
+            let payload = json!({
+                "price": entry.price,
+                "currency": entry.currency,
+                "previous_price": previous_price,
+                "previous_currency": previous_currency,
+                "source": source,
+            });
+            OutboxRepo::record(conn, product_id, ProductEventType::PriceChanged, &payload).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Every base price the product has had, newest first.
+    #[instrument(skip(self))]
+    pub async fn get_for_product(
+        &self,
+        product_id: Uuid,
+    ) -> Result<Vec<PriceHistoryEntry>, AppError> {
+        let entries = sqlx::query_as!(
+            PriceHistoryEntry,
+            r#"
+            SELECT
+                id, product_id, price, currency, previous_price, previous_currency,
+                source AS "source: PriceChangeSource", scheduled_price_change_id, changed_at
+            FROM price_history
+            WHERE product_id = $1
+            ORDER BY changed_at DESC, id DESC
+            "#,
+            product_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+}
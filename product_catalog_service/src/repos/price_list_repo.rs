@@ -0,0 +1,313 @@
+use bigdecimal::BigDecimal;
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::{create_price_list_dto::CreatePriceListDto, update_price_list_dto::UpdatePriceListDto},
+    models::{
+        app_error::AppError,
+        price_list::{PriceList, parse_currency},
+        price_list_entry::PriceListEntry,
+        product_event::ProductEventType,
+    },
+    repos::outbox_repo::OutboxRepo,
+};
+
+pub struct PriceListRepo {
+    pub pool: PgPool,
+}
+
+impl PriceListRepo {
+    /// Locks the product, so its price list prices change one at a time and
+    /// the events carry the right previous price.
+    async fn lock_product(conn: &mut PgConnection, product_id: Uuid) -> Result<(), AppError> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT id FROM products
+            WHERE id = $1 AND deleted_at IS NULL
+            FOR UPDATE
+            "#,
+            product_id
+        )
+        .fetch_optional(conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Product not found".to_string()))?;
+
+        Ok(())
+    }
+
+    /// Adds a price change event for the product's price in the price list;
+    /// `price` is `None` once the product has none there. Runs in the
+    /// transaction that changed the price, after the change.
+    async fn record_price_change(
+        conn: &mut PgConnection,
+        price_list_id: Uuid,
+        product_id: Uuid,
+        price: Option<&BigDecimal>,
+        previous_price: Option<&BigDecimal>,
+    ) -> Result<(), AppError> {
+        let currency = sqlx::query_scalar!(
+            r#"
+            SELECT currency FROM price_lists
+            WHERE id = $1
+            "#,
+            price_list_id
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        let payload = json!({
+            "price_list_id": price_list_id,
+            "price": price,
+            "currency": currency,
+            "previous_price": previous_price,
+        });
+
+        OutboxRepo::record(conn, product_id, ProductEventType::PriceChanged, &payload).await
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_all(&self) -> Result<Vec<PriceList>, AppError> {
+        let price_lists = sqlx::query_as!(
+            PriceList,
+            r#"
+            SELECT * FROM price_lists
+            ORDER BY currency, customer_group NULLS FIRST, priority DESC, name
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(price_lists)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_by_id(&self, id: Uuid) -> Result<PriceList, AppError> {
+        let price_list = sqlx::query_as!(
+            PriceList,
+            r#"
+            SELECT * FROM price_lists
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Price list not found".to_string()))?;
+
+        Ok(price_list)
+    }
+
+    #[instrument(skip(self, data))]
+    pub async fn create(&self, data: &CreatePriceListDto) -> Result<PriceList, AppError> {
+        if data.name.trim().is_empty() {
+            return Err(AppError::Invalid(
+                "Price list name must not be empty".to_string(),
+            ));
+        }
+        if let (Some(valid_from), Some(valid_to)) = (data.valid_from, data.valid_to)
+            && valid_from >= valid_to
+        {
+            return Err(AppError::Invalid(
+                "`valid_from` must be before `valid_to`".to_string(),
+            ));
+        }
+
+        let customer_group = data
+            .customer_group
+            .as_deref()
+            .map(str::trim)
+            .filter(|group| !group.is_empty());
+
+        let price_list = sqlx::query_as!(
+            PriceList,
+            r#"
+            INSERT INTO price_lists (name, currency, customer_group, priority, valid_from, valid_to)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+            data.name.trim(),
+            parse_currency(&data.currency)?,
+            customer_group,
+            data.priority,
+            data.valid_from,
+            data.valid_to
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(price_list)
+    }
+
+    /// The currency and customer group are fixed; create another list instead.
+    #[instrument(skip(self, data))]
+    pub async fn update(&self, id: Uuid, data: &UpdatePriceListDto) -> Result<PriceList, AppError> {
+        if data
+            .name
+            .as_deref()
+            .is_some_and(|name| name.trim().is_empty())
+        {
+            return Err(AppError::Invalid(
+                "Price list name must not be empty".to_string(),
+            ));
+        }
+
+        let price_list = sqlx::query_as!(
+            PriceList,
+            r#"
+            UPDATE price_lists
+            SET
+                name = COALESCE($1, name),
+                priority = COALESCE($2, priority),
+                valid_from = COALESCE($3, valid_from),
+                valid_to = COALESCE($4, valid_to),
+                updated_at = NOW()
+            WHERE id = $5
+            RETURNING *
+            "#,
+            data.name.as_deref().map(str::trim),
+            data.priority,
+            data.valid_from,
+            data.valid_to,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Price list not found".to_string()))?;
+
+        Ok(price_list)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn delete(&self, id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM price_lists
+            WHERE id = $1
+            "#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Price list not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_entries(&self, price_list_id: Uuid) -> Result<Vec<PriceListEntry>, AppError> {
+        let entries = sqlx::query_as!(
+            PriceListEntry,
+            r#"
+            SELECT e.price_list_id, e.product_id, e.price, e.updated_at
+            FROM price_list_entries e
+            JOIN products p ON p.id = e.product_id
+            WHERE e.price_list_id = $1
+            ORDER BY p.name, e.product_id
+            "#,
+            price_list_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn set_price(
+        &self,
+        price_list_id: Uuid,
+        product_id: Uuid,
+        price: &BigDecimal,
+    ) -> Result<PriceListEntry, AppError> {
+        if price < &BigDecimal::from(0) {
+            return Err(AppError::Invalid("Price must not be negative".to_string()));
+        }
+
+        let mut tx = self.pool.begin().await?;
+        Self::lock_product(&mut tx, product_id).await?;
+
+        let previous_price = sqlx::query_scalar!(
+            r#"
+            SELECT price FROM price_list_entries
+            WHERE price_list_id = $1 AND product_id = $2
+            "#,
+            price_list_id,
+            product_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let entry = sqlx::query_as!(
+            PriceListEntry,
+            r#"
+            INSERT INTO price_list_entries (price_list_id, product_id, price)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (price_list_id, product_id)
+            DO UPDATE SET price = EXCLUDED.price, updated_at = NOW()
+            RETURNING price_list_id, product_id, price, updated_at
+            "#,
+            price_list_id,
+            product_id,
+            price
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if previous_price.as_ref() != Some(&entry.price) {
+            Self::record_price_change(
+                &mut tx,
+                price_list_id,
+                product_id,
+                Some(&entry.price),
+                previous_price.as_ref(),
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(entry)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn delete_price(
+        &self,
+        price_list_id: Uuid,
+        product_id: Uuid,
+    ) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+        Self::lock_product(&mut tx, product_id).await?;
+
+        let previous_price = sqlx::query_scalar!(
+            r#"
+            DELETE FROM price_list_entries
+            WHERE price_list_id = $1 AND product_id = $2
+            RETURNING price
+            "#,
+            price_list_id,
+            product_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Product has no price in this price list".to_string()))?;
+
+        Self::record_price_change(
+            &mut tx,
+            price_list_id,
+            product_id,
+            None,
+            Some(&previous_price),
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
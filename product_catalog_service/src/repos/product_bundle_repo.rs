@@ -0,0 +1,396 @@
+use std::collections::HashSet;
+
+use sqlx::{PgConnection, PgPool};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::set_product_bundle_dto::SetProductBundleDto,
+    models::{
+        app_error::AppError,
+        price_history_entry::PriceChangeSource,
+        product_bundle::{BundleComponent, ProductBundle},
+    },
+    repos::{outbox_repo::OutboxRepo, price_history_repo::PriceHistoryRepo},
+};
+
+pub struct ProductBundleRepo {
+    pub pool: PgPool,
+}
+
+impl ProductBundleRepo {
+    /// Rejects a base price change of a bundle priced from its components.
+    pub async fn ensure_own_price(
+        conn: &mut PgConnection,
+        product_id: Uuid,
+    ) -> Result<(), AppError> {
+        let is_discounted = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM product_bundles
+                WHERE product_id = $1 AND discount_percent IS NOT NULL
+            ) AS "is_discounted!"
+            "#,
+            product_id
+        )
+        .fetch_one(conn)
+        .await?;
+
+        if is_discounted {
+            return Err(AppError::Invalid(
+                "The price of a discounted bundle is derived from its components".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Sets the base price of a discounted bundle to its components' prices
+    /// less the discount, recording the change. Runs in the transaction that
+    /// changed the bundle or one of its components.
+    pub async fn reprice(conn: &mut PgConnection, bundle_id: Uuid) -> Result<(), AppError> {
+        let bundle = sqlx::query!(
+            r#"
+            SELECT p.price, p.currency, b.discount_percent
+            FROM products p
+            JOIN product_bundles b ON b.product_id = p.id
+            WHERE p.id = $1
+            FOR UPDATE OF p
+            "#,
+            bundle_id
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        let Some(bundle) = bundle else {
+            return Ok(());
+        };
+        let Some(discount_percent) = bundle.discount_percent else {
+            return Ok(());
+        };
+
+        let derived = sqlx::query!(
+            r#"
+            SELECT
+                ROUND(SUM(p.price * c.quantity) * (100 - $2::NUMERIC) / 100, 2) AS price,
+                COALESCE(BOOL_AND(p.currency = $3), TRUE) AS "same_currency!"
+            FROM product_bundle_components c
+            JOIN products p ON p.id = c.component_id
+            WHERE c.bundle_id = $1
+            "#,
+            bundle_id,
+            discount_percent,
+            bundle.currency
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        if !derived.same_currency {
+            tracing::warn!(
+                %bundle_id,
+                "bundle components are priced in another currency, keeping the current price"
+            );
+            return Ok(());
+        }
+
+        let Some(price) = derived.price else {
+            return Ok(());
+        };
+        if price == bundle.price {
+            return Ok(());
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE products
+            SET price = $1, updated_at = NOW(), version = version + 1
+            WHERE id = $2
+            "#,
+            price,
+            bundle_id
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        PriceHistoryRepo::record(
+            conn,
+            bundle_id,
+            Some(&bundle.price),
+            Some(&bundle.currency),
+            PriceChangeSource::Bundle,
+            None,
+        )
+        .await
+    }
+
+    /// Reprices the discounted bundles the product is a component of, after
+    /// its base price changed.
+    pub async fn reprice_containing(
+        conn: &mut PgConnection,
+        component_id: Uuid,
+    ) -> Result<(), AppError> {
+        let bundle_ids = sqlx::query_scalar!(
+            r#"
+            SELECT c.bundle_id
+            FROM product_bundle_components c
+            JOIN product_bundles b ON b.product_id = c.bundle_id
+            WHERE c.component_id = $1 AND b.discount_percent IS NOT NULL
+            ORDER BY c.bundle_id
+            "#,
+            component_id
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        for bundle_id in bundle_ids {
+            Self::reprice(conn, bundle_id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Those of the products that are bundles.
+    #[instrument(skip(self))]
+    pub async fn get_bundles(&self, product_ids: &[Uuid]) -> Result<Vec<ProductBundle>, AppError> {
+        let bundles = sqlx::query_as!(
+            ProductBundle,
+            r#"
+            SELECT product_id, discount_percent
+            FROM product_bundles
+            WHERE product_id = ANY($1)
+            "#,
+            product_ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(bundles)
+    }
+
+    /// Components of the bundles in their listed order. A deleted component
+    /// counts as out of stock.
+    #[instrument(skip(self))]
+    pub async fn get_components(
+        &self,
+        bundle_ids: &[Uuid],
+    ) -> Result<Vec<BundleComponent>, AppError> {
+        let components = sqlx::query_as!(
+            BundleComponent,
+            r#"
+            SELECT
+                c.bundle_id, c.component_id, p.name, p.slug, p.price, p.currency, c.quantity,
+                CASE WHEN p.deleted_at IS NULL THEN COALESCE((
+                    SELECT SUM(l.on_hand - l.reserved)
+                    FROM product_variants v
+                    JOIN inventory_levels l ON l.variant_id = v.id
+                    WHERE v.product_id = c.component_id
+                ), 0) ELSE 0 END::BIGINT AS "available!"
+            FROM product_bundle_components c
+            JOIN products p ON p.id = c.component_id
+            WHERE c.bundle_id = ANY($1)
+            ORDER BY c.bundle_id, c.position
+            "#,
+            bundle_ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(components)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_by_product(&self, product_id: Uuid) -> Result<ProductBundle, AppError> {
+        let bundle = sqlx::query_as!(
+            ProductBundle,
+            r#"
+            SELECT b.product_id, b.discount_percent
+            FROM product_bundles b
+            JOIN products p ON p.id = b.product_id
+            WHERE b.product_id = $1 AND p.deleted_at IS NULL
+            "#,
+            product_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Product is not a bundle".to_string()))?;
+
+        Ok(bundle)
+    }
+
+    /// Makes the product a bundle of the given components or replaces its
+    /// components. Bundles do not nest, and a discounted bundle must be
+    /// priced in its components' currency. Bumps the product's version.
+    #[instrument(skip(self, data))]
+    pub async fn set(
+        &self,
+        product_id: Uuid,
+        data: &SetProductBundleDto,
+    ) -> Result<ProductBundle, AppError> {
+        let component_ids: Vec<Uuid> = data.components.iter().map(|line| line.product_id).collect();
+        let quantities: Vec<i32> = data.components.iter().map(|line| line.quantity).collect();
+
+        if component_ids.contains(&product_id) {
+            return Err(AppError::Invalid(
+                "A bundle cannot contain itself".to_string(),
+            ));
+        }
+        if component_ids.iter().collect::<HashSet<_>>().len() != component_ids.len() {
+            return Err(AppError::Invalid(
+                "Each component may only be listed once".to_string(),
+            ));
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let currency = sqlx::query_scalar!(
+            r#"
+            UPDATE products
+            SET updated_at = NOW(), version = version + 1
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING currency
+            "#,
+            product_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Product not found".to_string()))?;
+
+        let is_component = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM product_bundle_components WHERE component_id = $1
+            ) AS "is_component!"
+            "#,
+            product_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if is_component {
+            return Err(AppError::Invalid(
+                "A product in a bundle cannot be a bundle itself".to_string(),
+            ));
+        }
+
+        let components = sqlx::query!(
+            r#"
+            SELECT
+                p.id, p.currency,
+                EXISTS (SELECT 1 FROM product_bundles b WHERE b.product_id = p.id)
+                    AS "is_bundle!"
+            FROM products p
+            WHERE p.id = ANY($1) AND p.deleted_at IS NULL
+            FOR SHARE OF p
+            "#,
+            &component_ids
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        if components.len() != component_ids.len() {
+            return Err(AppError::NotFound(
+                "Component product not found".to_string(),
+            ));
+        }
+        if components.iter().any(|component| component.is_bundle) {
+            return Err(AppError::Invalid(
+                "A bundle cannot contain another bundle".to_string(),
+            ));
+        }
+        if data.discount_percent.is_some()
+            && components
+                .iter()
+                .any(|component| component.currency != currency)
+        {
+            return Err(AppError::Invalid(
+                "Components of a discounted bundle must be priced in the bundle's currency"
+                    .to_string(),
+            ));
+        }
+
+        let bundle = sqlx::query_as!(
+            ProductBundle,
+            r#"
+            INSERT INTO product_bundles (product_id, discount_percent)
+            VALUES ($1, $2)
+            ON CONFLICT (product_id) DO UPDATE
+            SET discount_percent = EXCLUDED.discount_percent, updated_at = NOW()
+            RETURNING product_id, discount_percent
+            "#,
+            product_id,
+            data.discount_percent
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM product_bundle_components
+            WHERE bundle_id = $1
+            "#,
+            product_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO product_bundle_components (bundle_id, component_id, quantity, position)
+            SELECT $1, line.component_id, line.quantity, line.position
+            FROM UNNEST($2::UUID[], $3::INT[]) WITH ORDINALITY
+                AS line(component_id, quantity, position)
+            "#,
+            product_id,
+            &component_ids,
+            &quantities
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Self::reprice(&mut tx, product_id).await?;
+
+        OutboxRepo::record_updated(&mut tx, product_id).await?;
+
+        tx.commit().await?;
+
+        Ok(bundle)
+    }
+
+    /// Turns the bundle back into a plain product, keeping its current price.
+    #[instrument(skip(self))]
+    pub async fn delete(&self, product_id: Uuid) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM product_bundles b
+            USING products p
+            WHERE b.product_id = $1 AND p.id = b.product_id AND p.deleted_at IS NULL
+            "#,
+            product_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Product is not a bundle".to_string()));
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE products
+            SET updated_at = NOW(), version = version + 1
+            WHERE id = $1
+            "#,
+            product_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        OutboxRepo::record_updated(&mut tx, product_id).await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
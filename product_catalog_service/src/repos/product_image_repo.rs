@@ -0,0 +1,203 @@
+use std::collections::BTreeSet;
+
+use sqlx::{PgPool, Postgres, Transaction, types::Json};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    models::{
+        app_error::AppError,
+        product_image::{ImageThumbnail, NewProductImage, ProductImage},
+    },
+    repos::outbox_repo::OutboxRepo,
+};
+
+pub struct ProductImageRepo {
+    pub pool: PgPool,
+}
+
+impl ProductImageRepo {
+    /// Bumps the product version so its ETag changes along with the embedded
+    /// images. The row lock also makes concurrent uploads and reorders agree
+    /// on positions.
+    async fn touch_product(
+        tx: &mut Transaction<'_, Postgres>,
+        product_id: Uuid,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE products
+            SET version = version + 1, updated_at = NOW()
+            WHERE id = $1
+            "#,
+            product_id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Product not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Images of the given products, in order.
+    #[instrument(skip(self))]
+    pub async fn get_for_products(
+        &self,
+        product_ids: &[Uuid],
+    ) -> Result<Vec<ProductImage>, AppError> {
+        let images = sqlx::query_as!(
+            ProductImage,
+            r#"
+            SELECT
+                id, product_id, position, storage_key, content_type, byte_size, width, height,
+                alt_text, thumbnails AS "thumbnails: Json<Vec<ImageThumbnail>>", created_at
+            FROM product_images
+            WHERE product_id = ANY($1)
+            ORDER BY product_id, position
+            "#,
+            product_ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(images)
+    }
+
+    /// Adds the image after the product's existing images.
+    #[instrument(skip(self, image), fields(id = %image.id))]
+    pub async fn create(&self, image: &NewProductImage) -> Result<ProductImage, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        Self::touch_product(&mut tx, image.product_id).await?;
+
+        let image = sqlx::query_as!(
+            ProductImage,
+            r#"
+            INSERT INTO product_images
+                (id, product_id, position, storage_key, content_type, byte_size, width, height,
+                 alt_text, thumbnails)
+            SELECT $1, $2, COALESCE(MAX(position) + 1, 0), $3, $4, $5, $6, $7, $8, $9
+            FROM product_images
+            WHERE product_id = $2
+            RETURNING
+                id, product_id, position, storage_key, content_type, byte_size, width, height,
+                alt_text, thumbnails AS "thumbnails: Json<Vec<ImageThumbnail>>", created_at
+            "#,
+            image.id,
+            image.product_id,
+            image.storage_key,
+            image.content_type,
+            image.byte_size,
+            image.width,
+            image.height,
+            image.alt_text,
+            Json(&image.thumbnails) as _
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        OutboxRepo::record_updated(&mut tx, image.product_id).await?;
+
+        tx.commit().await?;
+
+        Ok(image)
+    }
+
+    /// Puts the product's images in the given order; every image has to be
+    /// listed exactly once.
+    #[instrument(skip(self))]
+    pub async fn reorder(
+        &self,
+        product_id: Uuid,
+        image_ids: &[Uuid],
+    ) -> Result<Vec<ProductImage>, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        Self::touch_product(&mut tx, product_id).await?;
+
+        let existing: BTreeSet<Uuid> = sqlx::query_scalar!(
+            r#"
+            SELECT id FROM product_images
+            WHERE product_id = $1
+            "#,
+            product_id
+        )
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .collect();
+
+        let requested: BTreeSet<Uuid> = image_ids.iter().copied().collect();
+        if requested.len() != image_ids.len() || requested != existing {
+            return Err(AppError::Invalid(
+                "`image_ids` must list every image of the product exactly once".to_string(),
+            ));
+        }
+
+        // Positions are only unique once the transaction commits.
+        sqlx::query!(
+            r#"
+            UPDATE product_images i
+            SET position = o.position - 1
+            FROM UNNEST($1::UUID[]) WITH ORDINALITY AS o(id, position)
+            WHERE i.id = o.id AND i.product_id = $2
+            "#,
+            image_ids,
+            product_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        OutboxRepo::record_updated(&mut tx, product_id).await?;
+
+        tx.commit().await?;
+
+        self.get_for_products(&[product_id]).await
+    }
+
+    /// Removes the image and closes the gap it leaves. Returns the removed
+    /// image so its files can be deleted.
+    #[instrument(skip(self))]
+    pub async fn delete(&self, product_id: Uuid, id: Uuid) -> Result<ProductImage, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        Self::touch_product(&mut tx, product_id).await?;
+
+        let image = sqlx::query_as!(
+            ProductImage,
+            r#"
+            DELETE FROM product_images
+            WHERE id = $1 AND product_id = $2
+            RETURNING
+                id, product_id, position, storage_key, content_type, byte_size, width, height,
+                alt_text, thumbnails AS "thumbnails: Json<Vec<ImageThumbnail>>", created_at
+            "#,
+            id,
+            product_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Product image not found".to_string()))?;
+
+        sqlx::query!(
+            r#"
+            UPDATE product_images
+            SET position = position - 1
+            WHERE product_id = $1 AND position > $2
+            "#,
+            product_id,
+            image.position
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        OutboxRepo::record_updated(&mut tx, product_id).await?;
+
+        tx.commit().await?;
+
+        Ok(image)
+    }
+}
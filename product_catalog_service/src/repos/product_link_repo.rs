@@ -0,0 +1,152 @@
+use std::collections::HashSet;
+
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::set_product_links_dto::SetProductLinksDto,
+    models::{
+        app_error::AppError,
+        product_link::{ProductLink, ProductLinkType},
+    },
+};
+
+pub struct ProductLinkRepo {
+    pub pool: PgPool,
+}
+
+impl ProductLinkRepo {
+    /// Links of the product by type and position, including those to products
+    /// that are not public.
+    #[instrument(skip(self))]
+    pub async fn get_for_product(
+        &self,
+        product_id: Uuid,
+        link_type: Option<ProductLinkType>,
+    ) -> Result<Vec<ProductLink>, AppError> {
+        let links = sqlx::query_as!(
+            ProductLink,
+            r#"
+            SELECT
+                l.product_id, l.linked_product_id, l.link_type AS "link_type: ProductLinkType",
+                l.position, p.name, p.slug, l.created_at
+            FROM product_links l
+            JOIN products p ON p.id = l.linked_product_id
+            WHERE l.product_id = $1
+                AND ($2::product_link_type IS NULL OR l.link_type = $2)
+                AND p.deleted_at IS NULL
+            ORDER BY l.link_type, l.position
+            "#,
+            product_id,
+            link_type as Option<ProductLinkType>
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(links)
+    }
+
+    /// Replaces the product's links of one type, in the given order.
+    #[instrument(skip(self, data))]
+    pub async fn set(
+        &self,
+        product_id: Uuid,
+        link_type: ProductLinkType,
+        data: &SetProductLinksDto,
+    ) -> Result<Vec<ProductLink>, AppError> {
+        if data.product_ids.contains(&product_id) {
+            return Err(AppError::Invalid(
+                "A product cannot be linked to itself".to_string(),
+            ));
+        }
+        if data.product_ids.iter().collect::<HashSet<_>>().len() != data.product_ids.len() {
+            return Err(AppError::Invalid(
+                "Each product may only be linked once".to_string(),
+            ));
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        // Serializes replacing the links of the product.
+        sqlx::query!(
+            r#"
+            SELECT id FROM products
+            WHERE id = $1 AND deleted_at IS NULL
+            FOR UPDATE
+            "#,
+            product_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Product not found".to_string()))?;
+
+        let found = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!" FROM products
+            WHERE id = ANY($1) AND deleted_at IS NULL
+            "#,
+            &data.product_ids
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if found != data.product_ids.len() as i64 {
+            return Err(AppError::NotFound("Linked product not found".to_string()));
+        }
+
+        sqlx::query!(
+            r#"
+            DELETE FROM product_links
+            WHERE product_id = $1 AND link_type = $2
+            "#,
+            product_id,
+            link_type as ProductLinkType
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO product_links (product_id, linked_product_id, link_type, position)
+            SELECT $1, linked.id, $2, linked.position
+            FROM UNNEST($3::UUID[]) WITH ORDINALITY AS linked(id, position)
+            "#,
+            product_id,
+            link_type as ProductLinkType,
+            &data.product_ids
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        self.get_for_product(product_id, Some(link_type)).await
+    }
+
+    #[instrument(skip(self))]
+    pub async fn delete(
+        &self,
+        product_id: Uuid,
+        link_type: ProductLinkType,
+        linked_product_id: Uuid,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM product_links
+            WHERE product_id = $1 AND link_type = $2 AND linked_product_id = $3
+            "#,
+            product_id,
+            link_type as ProductLinkType,
+            linked_product_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Link not found".to_string()));
+        }
+
+        Ok(())
+    }
+}
@@ -1,41 +1,1309 @@
+use std::collections::{BTreeMap, HashSet};
+
 use async_trait::async_trait;
-use sqlx::PgPool;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::{PgPool, Postgres, QueryBuilder, Transaction, types::Json};
+use tracing::instrument;
+use uuid::Uuid;
 
 use crate::{
-    dtos::{create_product_dto::CreateProductDto, update_product_dto::UpdateProductDto},
+    dtos::{
+        attribute_facet_dto::AttributeFacetDto, availability_facet_dto::AvailabilityFacetDto,
+        category_facet_dto::CategoryFacetDto, create_product_dto::CreateProductDto,
+        created_facet_dto::CreatedFacetDto, facet_value_dto::FacetValueDto,
+        price_bucket_dto::PriceBucketDto, price_facet_dto::PriceFacetDto,
+        product_facets_dto::ProductFacetsDto, update_product_dto::UpdateProductDto,
+    },
     models::{
-        app_error::AppError, paginated_response::PaginatedResponse, pagination::Pagination,
-        product::Product,
+        app_error::AppError,
+        attribute_definition::validate_attribute_values,
+        cursor::PageCursor,
+        facet_dimension::FacetDimension,
+        import_job::ImportOutcome,
+        paginated_response::PaginatedResponse,
+        pagination::Pagination,
+        price_history_entry::PriceChangeSource,
+        price_list::parse_currency,
+        price_query::PriceQuery,
+        product::{Product, ProductStatus},
+        product_export_row::ProductExportRow,
+        product_import_row::ProductImportRow,
+        product_event::ProductEventType,
+        product_link::ProductLinkType,
+        product_search_hit::ProductSearchHit,
+        product_sort::{ProductSort, SortDirection},
+        product_visibility::ProductVisibility,
+        related_product::RelatedProduct,
+        slug_match::SlugMatch,
+    },
+    repos::{
+        attribute_repo::AttributeRepo, outbox_repo::OutboxRepo,
+        price_history_repo::PriceHistoryRepo, product_bundle_repo::ProductBundleRepo,
+        repository_traits::Repository,
     },
-    repos::repository_traits::Repository,
+    traits::to_cursor::ToCursor,
+    utility::slug::{slug_stem, slugify},
 };
 
+/// Columns of the `products` table that make up a [`Product`].
+const PRODUCT_COLUMNS: &str = "products.id, products.external_id, products.name, products.slug, \
+     products.description, products.price, products.image_url, products.created_at, \
+     products.updated_at, products.version, products.attributes, products.currency, \
+     products.price_list_id, products.rating, products.rating_count, products.status, \
+     products.published_at, products.deleted_at";
+
+/// Whether the product is listed publicly.
+const PUBLIC_CONDITION: &str = "products.status = 'active' AND products.published_at <= NOW() \
+     AND products.deleted_at IS NULL";
+
+/// Whether any variant of the product has unreserved stock in some warehouse.
+/// Bundles are in stock while every live component has stock for one bundle.
+const IN_STOCK_CONDITION: &str = "CASE WHEN EXISTS (SELECT 1 FROM product_bundles b \
+     WHERE b.product_id = products.id) \
+     THEN NOT EXISTS (SELECT 1 FROM product_bundle_components c \
+     JOIN products cp ON cp.id = c.component_id \
+     WHERE c.bundle_id = products.id AND (cp.deleted_at IS NOT NULL OR c.quantity > \
+     COALESCE((SELECT SUM(l.on_hand - l.reserved) FROM product_variants v \
+     JOIN inventory_levels l ON l.variant_id = v.id \
+     WHERE v.product_id = c.component_id), 0))) \
+     ELSE EXISTS (SELECT 1 FROM product_variants v \
+     JOIN inventory_levels l ON l.variant_id = v.id \
+     WHERE v.product_id = products.id AND l.on_hand > l.reserved) END";
+
+/// Namespace of the advisory locks serializing slug allocation, keyed by the
+/// hash of the slug stem.
+const SLUG_LOCK_NAMESPACE: i32 = 0x736c_7567;
+
+/// Upper bounds of the price facet buckets; the last bucket is open ended.
+const PRICE_FACET_EDGES: [i64; 7] = [10, 25, 50, 100, 250, 500, 1000];
+
+/// Blank external ids are stored as `NULL`.
+fn external_id(external_id: &Option<String>) -> Option<&str> {
+    external_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|external_id| !external_id.is_empty())
+}
+
 pub struct ProductRepo {
     pub pool: PgPool,
+    /// Postgres text search configuration used to stem product text, e.g. `english`.
+    pub search_language: String,
+    /// Currency of products created without one.
+    pub default_currency: String,
 }
 
-#[async_trait]
-impl Repository<Product, CreateProductDto, UpdateProductDto> for ProductRepo {
-    async fn create(&self, data: &CreateProductDto) -> Result<Product, AppError> {
+impl ProductRepo {
+    /// Appends the product source of a list query, aliased `products`. When a
+    /// currency is requested, `price` and `currency` are the price in that
+    /// currency: from the best applicable price list, else the base price if
+    /// the product is sold in that currency, else `NULL`.
+    fn push_products(
+        query_builder: &mut QueryBuilder<'_, Postgres>,
+        price_query: &PriceQuery,
+    ) -> Result<(), AppError> {
+        let Some(currency) = price_query.currency()? else {
+            query_builder
+                .push("(SELECT products.*, NULL::UUID AS price_list_id FROM products) AS products");
+            return Ok(());
+        };
+
+        query_builder.push(
+            "(SELECT p.id, p.external_id, p.name, p.slug, p.description, p.image_url, \
+             p.created_at, p.updated_at, p.version, p.attributes, p.rating, p.rating_count, \
+             p.status, p.published_at, p.deleted_at, p.search_vector, ",
+        );
+        query_builder.push_bind(currency.clone());
+        query_builder
+            .push("::TEXT AS currency, COALESCE(list_price.price, CASE WHEN p.currency = ");
+        query_builder.push_bind(currency.clone());
+        query_builder.push(
+            " THEN p.price END) AS price, list_price.price_list_id \
+             FROM products p \
+             LEFT JOIN LATERAL (SELECT e.price, e.price_list_id FROM price_list_entries e \
+                 JOIN price_lists pl ON pl.id = e.price_list_id \
+                 WHERE e.product_id = p.id AND pl.currency = ",
+        );
+        query_builder.push_bind(currency);
+        query_builder.push(" AND (pl.customer_group IS NULL OR pl.customer_group = ");
+        query_builder.push_bind(price_query.customer_group.clone());
+        query_builder.push(
+            ") AND (pl.valid_from IS NULL OR pl.valid_from <= NOW()) \
+             AND (pl.valid_to IS NULL OR pl.valid_to > NOW()) \
+             ORDER BY pl.customer_group IS NULL, pl.priority DESC, e.price \
+             LIMIT 1) AS list_price ON TRUE) AS products",
+        );
+
+        Ok(())
+    }
+
+    fn push_tsquery(&self, query_builder: &mut QueryBuilder<'_, Postgres>, tsquery: &str) {
+        query_builder.push("to_tsquery(");
+        query_builder.push_bind(self.search_language.clone());
+        query_builder.push("::REGCONFIG, ");
+        query_builder.push_bind(tsquery.to_string());
+        query_builder.push(")");
+    }
+
+    /// Appends the condition for products with the given visibility.
+    fn push_visibility(
+        query_builder: &mut QueryBuilder<'_, Postgres>,
+        visibility: ProductVisibility,
+    ) {
+        match visibility {
+            ProductVisibility::Public => query_builder.push(" AND ").push(PUBLIC_CONDITION),
+            ProductVisibility::All => query_builder.push(" AND products.deleted_at IS NULL"),
+            ProductVisibility::Deleted => {
+                query_builder.push(" AND products.deleted_at IS NOT NULL")
+            }
+        };
+    }
+
+    /// Appends the `WHERE` clause shared by the product list, its count and its
+    /// facets. The filter of the `except` dimension is left out.
+    fn push_filters(
+        &self,
+        query_builder: &mut QueryBuilder<'_, Postgres>,
+        pagination: &Pagination,
+        except: Option<FacetDimension>,
+    ) -> Result<(), AppError> {
+        query_builder.push(" WHERE TRUE");
+
+        Self::push_visibility(query_builder, pagination.visibility.unwrap_or_default());
+
+        if let Some(status) = pagination.status {
+            query_builder.push(" AND products.status = ");
+            query_builder.push_bind(status);
+        }
+
+        // Products without a price in the requested currency are not for sale in it.
+        if pagination.price_query().currency()?.is_some() {
+            query_builder.push(" AND products.price IS NOT NULL");
+        }
+
+        if let Some(tsquery) = pagination.tsquery() {
+            query_builder.push(" AND products.search_vector @@ ");
+            self.push_tsquery(query_builder, &tsquery);
+        }
+
+        if except != Some(FacetDimension::Category)
+            && let Some(category_id) = pagination.category
+        {
+            // Products in the category or in any of its descendants.
+            query_builder.push(
+                " AND EXISTS (SELECT 1 FROM product_categories pc \
+                 JOIN categories c ON c.id = pc.category_id \
+                 WHERE pc.product_id = products.id \
+                 AND c.path LIKE (SELECT path FROM categories WHERE id = ",
+            );
+            query_builder.push_bind(category_id);
+            query_builder.push(") || '%')");
+        }
+
+        if except != Some(FacetDimension::Price) {
+            if let Some(min_price) = &pagination.min_price {
+                query_builder.push(" AND products.price >= ");
+                query_builder.push_bind(min_price.clone());
+            }
+            if let Some(max_price) = &pagination.max_price {
+                query_builder.push(" AND products.price <= ");
+                query_builder.push_bind(max_price.clone());
+            }
+        }
+
+        if except != Some(FacetDimension::Created) {
+            if let Some(created_from) = pagination.created_from {
+                query_builder.push(" AND products.created_at >= ");
+                query_builder.push_bind(created_from);
+            }
+            if let Some(created_to) = pagination.created_to {
+                query_builder.push(" AND products.created_at <= ");
+                query_builder.push_bind(created_to);
+            }
+        }
+
+        if except != Some(FacetDimension::InStock)
+            && let Some(in_stock) = pagination.in_stock
+        {
+            query_builder.push(if in_stock { " AND " } else { " AND NOT " });
+            query_builder.push(IN_STOCK_CONDITION);
+        }
+
+        if except != Some(FacetDimension::Attributes) {
+            // A name matches a product attribute or an option of any variant.
+            for (name, values) in pagination.attribute_filters()? {
+                query_builder.push(" AND (products.attributes ->> ");
+                query_builder.push_bind(name.clone());
+                query_builder.push(" = ANY(");
+                query_builder.push_bind(values.clone());
+                query_builder.push(
+                    ") OR EXISTS (SELECT 1 FROM product_variants v \
+                     WHERE v.product_id = products.id AND v.options ->> ",
+                );
+                query_builder.push_bind(name);
+                query_builder.push(" = ANY(");
+                query_builder.push_bind(values);
+                query_builder.push(")))");
+            }
+
+            // The CASE keeps the cast from failing on non-numeric values,
+            // which never match a range.
+            for (name, (min, max)) in pagination.attribute_ranges()? {
+                query_builder.push(" AND jsonb_typeof(products.attributes -> ");
+                query_builder.push_bind(name.clone());
+                query_builder.push(") = 'number'");
+
+                for (bound, operator) in [(min, ">="), (max, "<=")] {
+                    if let Some(bound) = bound {
+                        query_builder.push(" AND (CASE WHEN jsonb_typeof(products.attributes -> ");
+                        query_builder.push_bind(name.clone());
+                        query_builder.push(") = 'number' THEN (products.attributes ->> ");
+                        query_builder.push_bind(name.clone());
+                        query_builder.push(format!(")::NUMERIC END) {} ", operator));
+                        query_builder.push_bind(bound);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Column a page is ordered by, as selected in the list queries.
+    fn sort_column(sort: ProductSort) -> &'static str {
+        match sort {
+            ProductSort::Newest => "created_at",
+            ProductSort::Price => "price",
+            ProductSort::Name => "name",
+            ProductSort::Rating => "rating",
+            ProductSort::Relevance => "rank",
+        }
+    }
+
+    /// Appends the keyset condition, `ORDER BY` and `LIMIT`/`OFFSET` of a page.
+    /// `rank` is the SQL expression of the search relevance when searching.
+    fn push_page(
+        query_builder: &mut QueryBuilder<'_, Postgres>,
+        sort: ProductSort,
+        direction: SortDirection,
+        page_cursor: &Option<PageCursor>,
+        page: i64,
+        per_page: i64,
+        rank: &str,
+    ) -> Result<(), AppError> {
+        let sort_sql = match sort {
+            ProductSort::Relevance => rank.to_string(),
+            _ => format!("products.{}", Self::sort_column(sort)),
+        };
+
+        if let Some(PageCursor::After(cursor) | PageCursor::Before(cursor)) = page_cursor {
+            let invalid =
+                || AppError::Invalid("Pagination cursor is not valid for this query".to_string());
+
+            query_builder.push(format!(
+                " AND ({}, products.id) {} (",
+                sort_sql,
+                direction.after_operator()
+            ));
+            match sort {
+                ProductSort::Newest => {
+                    let created_at = DateTime::parse_from_rfc3339(&cursor.value)
+                        .map_err(|_| invalid())?
+                        .with_timezone(&Utc);
+                    query_builder.push_bind(created_at);
+                }
+                ProductSort::Price => {
+                    let price = cursor.value.parse::<BigDecimal>().map_err(|_| invalid())?;
+                    query_builder.push_bind(price);
+                }
+                ProductSort::Rating => {
+                    let rating = cursor.value.parse::<BigDecimal>().map_err(|_| invalid())?;
+                    query_builder.push_bind(rating);
+                }
+                ProductSort::Name => {
+                    query_builder.push_bind(cursor.value.clone());
+                }
+                ProductSort::Relevance => {
+                    let rank = cursor.value.parse::<f32>().map_err(|_| invalid())?;
+                    query_builder.push_bind(rank);
+                    query_builder.push("::REAL");
+                }
+            }
+            query_builder.push(", ");
+            query_builder.push_bind(cursor.id);
+            query_builder.push(")");
+        }
+
+        query_builder.push(format!(
+            " ORDER BY {} {}, products.id {}",
+            sort_sql,
+            direction.as_sql(),
+            direction.as_sql()
+        ));
+
+        // One extra row is fetched to find out whether another page exists.
+        query_builder.push(" LIMIT ");
+        query_builder.push_bind(per_page + 1);
+
+        if page_cursor.is_none() {
+            query_builder.push(" OFFSET ");
+            query_builder.push_bind((page - 1) * per_page);
+        }
+
+        Ok(())
+    }
+
+    /// Trims the extra row fetched past `per_page`, restores the requested
+    /// order for `before` cursors and works out the neighbouring page cursors.
+    fn paginate<T: ToCursor>(
+        rows: &mut Vec<T>,
+        page_cursor: &Option<PageCursor>,
+        page: i64,
+        per_page: i64,
+        order_by: &str,
+    ) -> (Option<String>, Option<String>) {
+        if matches!(page_cursor, Some(PageCursor::Before(_))) {
+            rows.reverse();
+        }
+
+        let has_more = rows.len() as i64 > per_page;
+        if has_more {
+            match page_cursor {
+                Some(PageCursor::Before(_)) => {
+                    rows.remove(0);
+                }
+                _ => rows.truncate(per_page.max(0) as usize),
+            }
+        }
+
+        let first_cursor = rows.first().map(|row| row.to_cursor(order_by).encode());
+        let last_cursor = rows.last().map(|row| row.to_cursor(order_by).encode());
+
+        match page_cursor {
+            Some(PageCursor::Before(_)) => {
+                (if has_more { first_cursor } else { None }, last_cursor)
+            }
+            Some(PageCursor::After(_)) => (first_cursor, if has_more { last_cursor } else { None }),
+            None => (
+                if page > 1 { first_cursor } else { None },
+                if has_more { last_cursor } else { None },
+            ),
+        }
+    }
+
+    async fn count(&self, pagination: &Pagination) -> Result<i64, AppError> {
+        let mut count_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT COUNT(*) FROM ");
+        Self::push_products(&mut count_builder, &pagination.price_query())?;
+        self.push_filters(&mut count_builder, pagination, None)?;
+
+        let total: i64 = count_builder
+            .build_query_scalar()
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(total)
+    }
+
+    /// A product with the given visibility. Unlike [`Repository::get_by_id`],
+    /// which finds any product that is not deleted, this is what shoppers may
+    /// look up.
+    #[instrument(skip(self))]
+    pub async fn get_visible(
+        &self,
+        id: Uuid,
+        visibility: ProductVisibility,
+    ) -> Result<Product, AppError> {
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT ");
+        query_builder.push(PRODUCT_COLUMNS);
+        query_builder.push(" FROM ");
+        Self::push_products(&mut query_builder, &PriceQuery::default())?;
+        query_builder.push(" WHERE products.id = ");
+        query_builder.push_bind(id);
+        Self::push_visibility(&mut query_builder, visibility);
+
+        query_builder
+            .build_query_as()
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Product not found".to_string()))
+    }
+
+    /// A product with the given visibility priced in the requested currency,
+    /// see [`Self::push_products`].
+    #[instrument(skip(self))]
+    pub async fn get_priced(
+        &self,
+        id: Uuid,
+        price_query: &PriceQuery,
+        visibility: ProductVisibility,
+    ) -> Result<Product, AppError> {
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT ");
+        query_builder.push(PRODUCT_COLUMNS);
+        query_builder.push(" FROM ");
+        Self::push_products(&mut query_builder, price_query)?;
+        query_builder.push(" WHERE products.id = ");
+        query_builder.push_bind(id);
+        query_builder.push(" AND products.price IS NOT NULL");
+        Self::push_visibility(&mut query_builder, visibility);
+
+        let product: Option<Product> = query_builder
+            .build_query_as()
+            .fetch_optional(&self.pool)
+            .await?;
+
+        match product {
+            Some(product) => Ok(product),
+            None => {
+                // Tell a missing product apart from one not sold in the currency.
+                self.get_visible(id, visibility).await?;
+                Err(AppError::NotFound(format!(
+                    "Product has no price in {}",
+                    price_query.currency()?.unwrap_or_default()
+                )))
+            }
+        }
+    }
+
+    /// Public products linked from the product, by link type and position.
+    /// Without links to show, public products sharing the most categories
+    /// with it are picked instead. Priced like [`Self::get_priced`].
+    #[instrument(skip(self))]
+    pub async fn get_related(
+        &self,
+        id: Uuid,
+        link_type: Option<ProductLinkType>,
+        price_query: &PriceQuery,
+        limit: i64,
+    ) -> Result<Vec<RelatedProduct>, AppError> {
+        let is_priced = price_query.currency()?.is_some();
+
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT ");
+        query_builder.push(PRODUCT_COLUMNS);
+        query_builder.push(", l.link_type FROM product_links l JOIN ");
+        Self::push_products(&mut query_builder, price_query)?;
+        query_builder.push(" ON products.id = l.linked_product_id WHERE l.product_id = ");
+        query_builder.push_bind(id);
+        if let Some(link_type) = link_type {
+            query_builder.push(" AND l.link_type = ");
+            query_builder.push_bind(link_type);
+        }
+        query_builder.push(" AND ").push(PUBLIC_CONDITION);
+        if is_priced {
+            query_builder.push(" AND products.price IS NOT NULL");
+        }
+        query_builder.push(" ORDER BY l.link_type, l.position LIMIT ");
+        query_builder.push_bind(limit);
+
+        let linked: Vec<RelatedProduct> =
+            query_builder.build_query_as().fetch_all(&self.pool).await?;
+
+        if !linked.is_empty() {
+            return Ok(linked);
+        }
+
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT ");
+        query_builder.push(PRODUCT_COLUMNS);
+        query_builder.push(", NULL::product_link_type AS link_type FROM ");
+        Self::push_products(&mut query_builder, price_query)?;
+        query_builder.push(
+            " JOIN LATERAL (SELECT COUNT(*) AS shared FROM product_categories pc \
+             JOIN product_categories own ON own.category_id = pc.category_id \
+             WHERE pc.product_id = products.id AND own.product_id = ",
+        );
+        query_builder.push_bind(id);
+        query_builder.push(") AS categories ON categories.shared > 0 WHERE products.id <> ");
+        query_builder.push_bind(id);
+        query_builder.push(" AND ").push(PUBLIC_CONDITION);
+        if is_priced {
+            query_builder.push(" AND products.price IS NOT NULL");
+        }
+        query_builder.push(
+            " ORDER BY categories.shared DESC, products.rating DESC, \
+             products.created_at DESC, products.id LIMIT ",
+        );
+        query_builder.push_bind(limit);
+
+        let same_category = query_builder.build_query_as().fetch_all(&self.pool).await?;
+
+        Ok(same_category)
+    }
+
+    /// Re-stems products indexed under another text search configuration,
+    /// e.g. after the deployment's search language changed.
+    #[instrument(skip(self))]
+    pub async fn sync_search_language(&self) -> Result<u64, AppError> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE products
+            SET search_language = $1::TEXT::REGCONFIG
+            WHERE search_language <> $1::TEXT::REGCONFIG
+            "#,
+            self.search_language
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Full-text search over product names and descriptions. Every word of `q`
+    /// is matched as a prefix after stemming; results are most relevant first
+    /// unless another ordering is requested.
+    #[instrument(skip(self))]
+    pub async fn search(
+        &self,
+        pagination: &Pagination,
+    ) -> Result<PaginatedResponse<ProductSearchHit>, AppError> {
+        let tsquery = pagination
+            .tsquery()
+            .ok_or_else(|| AppError::Invalid("Search query must contain a word".to_string()))?;
+
+        let page = pagination.page.unwrap_or(1);
+        let per_page = pagination.per_page.unwrap_or(10);
+
+        let (sort, direction) = pagination.ordering()?;
+        let order_by = pagination.cursor_key(sort, direction)?;
+        let page_cursor = pagination.page_cursor(&order_by)?;
+
+        // Keyset pagination walks backwards for `before` cursors and the rows
+        // are flipped into the requested order once fetched.
+        let fetch_direction = match page_cursor {
+            Some(PageCursor::Before(_)) => direction.reverse(),
+            _ => direction,
+        };
+
+        // The page is ranked and cut first so snippets are only built for the
+        // rows actually returned.
+        let mut query_builder: QueryBuilder<Postgres> =
+            QueryBuilder::new("SELECT hit.*, ts_headline(");
+        query_builder.push_bind(self.search_language.clone());
+        query_builder.push(
+            "::REGCONFIG, hit.name, query, 'HighlightAll=true, StartSel=<mark>, StopSel=</mark>') \
+             AS name_highlight, ts_headline(",
+        );
+        query_builder.push_bind(self.search_language.clone());
+        query_builder.push(
+            "::REGCONFIG, hit.description, query, \
+             'StartSel=<mark>, StopSel=</mark>, MaxFragments=2, MaxWords=20, MinWords=5') \
+             AS description_highlight FROM (SELECT ",
+        );
+        query_builder.push(PRODUCT_COLUMNS);
+        query_builder.push(", ts_rank_cd(products.search_vector, query) AS rank FROM ");
+        Self::push_products(&mut query_builder, &pagination.price_query())?;
+        query_builder.push(", ");
+        self.push_tsquery(&mut query_builder, &tsquery);
+        query_builder.push(" AS query");
+        self.push_filters(&mut query_builder, pagination, None)?;
+        Self::push_page(
+            &mut query_builder,
+            sort,
+            fetch_direction,
+            &page_cursor,
+            page,
+            per_page,
+            "ts_rank_cd(products.search_vector, query)",
+        )?;
+        query_builder.push(") AS hit, ");
+        self.push_tsquery(&mut query_builder, &tsquery);
+        query_builder.push(format!(
+            " AS query ORDER BY hit.{} {}, hit.id {}",
+            Self::sort_column(sort),
+            fetch_direction.as_sql(),
+            fetch_direction.as_sql()
+        ));
+
+        let mut hits: Vec<ProductSearchHit> =
+            query_builder.build_query_as().fetch_all(&self.pool).await?;
+
+        let (prev_cursor, next_cursor) =
+            Self::paginate(&mut hits, &page_cursor, page, per_page, &order_by);
+
+        let total = self.count(pagination).await?;
+        let total_pages = (total as f64 / per_page as f64).ceil() as i64;
+
+        Ok(PaginatedResponse {
+            page,
+            per_page,
+            total,
+            total_pages,
+            next_cursor,
+            prev_cursor,
+            facets: None,
+            data: hits,
+        })
+    }
+
+    /// Counts the products matching the list filters along every facet
+    /// dimension, each ignoring its own filter.
+    #[instrument(skip(self))]
+    pub async fn facets(&self, pagination: &Pagination) -> Result<ProductFacetsDto, AppError> {
+        let mut query_builder: QueryBuilder<Postgres> =
+            QueryBuilder::new("SELECT c.id, c.name, COUNT(DISTINCT products.id) FROM ");
+        Self::push_products(&mut query_builder, &pagination.price_query())?;
+        query_builder.push(
+            " JOIN product_categories pc ON pc.product_id = products.id \
+             JOIN categories c ON c.id = pc.category_id",
+        );
+        self.push_filters(
+            &mut query_builder,
+            pagination,
+            Some(FacetDimension::Category),
+        )?;
+        query_builder.push(" GROUP BY c.id, c.name ORDER BY 3 DESC, c.name");
+
+        let categories: Vec<(Uuid, String, i64)> =
+            query_builder.build_query_as().fetch_all(&self.pool).await?;
+
+        let mut query_builder: QueryBuilder<Postgres> =
+            QueryBuilder::new("SELECT o.key, o.value, COUNT(DISTINCT products.id) FROM ");
+        Self::push_products(&mut query_builder, &pagination.price_query())?;
+        query_builder.push(
+            " CROSS JOIN LATERAL ( \
+                 SELECT key, value FROM jsonb_each_text(products.attributes) \
+                 UNION \
+                 SELECT option.key, option.value FROM product_variants pv \
+                 CROSS JOIN LATERAL jsonb_each_text(pv.options) AS option \
+                 WHERE pv.product_id = products.id \
+             ) AS o",
+        );
+        self.push_filters(
+            &mut query_builder,
+            pagination,
+            Some(FacetDimension::Attributes),
+        )?;
+        query_builder.push(" GROUP BY o.key, o.value ORDER BY o.key, o.value");
+
+        let attribute_values: Vec<(String, String, i64)> =
+            query_builder.build_query_as().fetch_all(&self.pool).await?;
+
+        let mut attributes: Vec<AttributeFacetDto> = Vec::new();
+        for (name, value, count) in attribute_values {
+            let facet_value = FacetValueDto { value, count };
+            match attributes.last_mut() {
+                Some(attribute) if attribute.name == name => attribute.values.push(facet_value),
+                _ => attributes.push(AttributeFacetDto {
+                    name,
+                    values: vec![facet_value],
+                }),
+            }
+        }
+
+        let edges = PRICE_FACET_EDGES
+            .iter()
+            .map(|edge| edge.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(format!(
+            "SELECT width_bucket(products.price, ARRAY[{edges}]::NUMERIC[]) AS bucket, \
+             COUNT(*), MIN(products.price), MAX(products.price) FROM "
+        ));
+        Self::push_products(&mut query_builder, &pagination.price_query())?;
+        self.push_filters(&mut query_builder, pagination, Some(FacetDimension::Price))?;
+        query_builder.push(" GROUP BY bucket ORDER BY bucket");
+
+        let price_buckets: Vec<(i32, i64, BigDecimal, BigDecimal)> =
+            query_builder.build_query_as().fetch_all(&self.pool).await?;
+
+        let price = PriceFacetDto {
+            min: price_buckets.first().map(|bucket| bucket.2.clone()),
+            max: price_buckets.last().map(|bucket| bucket.3.clone()),
+            buckets: price_buckets
+                .iter()
+                .map(|(bucket, count, _, _)| {
+                    let bucket = *bucket as usize;
+                    PriceBucketDto {
+                        from: bucket
+                            .checked_sub(1)
+                            .map(|index| BigDecimal::from(PRICE_FACET_EDGES[index])),
+                        to: PRICE_FACET_EDGES
+                            .get(bucket)
+                            .map(|edge| BigDecimal::from(*edge)),
+                        count: *count,
+                    }
+                })
+                .collect(),
+        };
+
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(format!(
+            "SELECT COUNT(*) FILTER (WHERE {IN_STOCK_CONDITION}), \
+             COUNT(*) FILTER (WHERE NOT {IN_STOCK_CONDITION}) FROM "
+        ));
+        Self::push_products(&mut query_builder, &pagination.price_query())?;
+        self.push_filters(
+            &mut query_builder,
+            pagination,
+            Some(FacetDimension::InStock),
+        )?;
+
+        let (in_stock, out_of_stock): (i64, i64) =
+            query_builder.build_query_as().fetch_one(&self.pool).await?;
+
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT \
+             COUNT(*) FILTER (WHERE products.created_at >= NOW() - INTERVAL '1 day'), \
+             COUNT(*) FILTER (WHERE products.created_at >= NOW() - INTERVAL '7 days'), \
+             COUNT(*) FILTER (WHERE products.created_at >= NOW() - INTERVAL '30 days'), \
+             COUNT(*) FILTER (WHERE products.created_at >= NOW() - INTERVAL '365 days') \
+             FROM ",
+        );
+        Self::push_products(&mut query_builder, &pagination.price_query())?;
+        self.push_filters(
+            &mut query_builder,
+            pagination,
+            Some(FacetDimension::Created),
+        )?;
+
+        let (last_day, last_week, last_month, last_year): (i64, i64, i64, i64) =
+            query_builder.build_query_as().fetch_one(&self.pool).await?;
+
+        Ok(ProductFacetsDto {
+            categories: categories
+                .into_iter()
+                .map(|(id, name, count)| CategoryFacetDto { id, name, count })
+                .collect(),
+            attributes,
+            price,
+            availability: AvailabilityFacetDto {
+                in_stock,
+                out_of_stock,
+            },
+            created: CreatedFacetDto {
+                last_day,
+                last_week,
+                last_month,
+                last_year,
+            },
+        })
+    }
+
+    /// Picks the slug for a product named `name`: the name's slug, else that
+    /// slug numbered from 2 up, skipping slugs other products have or had in
+    /// any locale. Allocations that could pick the same slug are serialized
+    /// until the transaction ends.
+    pub async fn unique_slug(
+        tx: &mut Transaction<'_, Postgres>,
+        name: &str,
+        product_id: Option<Uuid>,
+    ) -> Result<String, AppError> {
+        let base = slugify(name);
+
+        // `shoe` may be numbered to `shoe-2`, the slug of a product named
+        // `Shoe 2`, so both lock the stem they share.
+        sqlx::query!(
+            "SELECT pg_advisory_xact_lock($1, hashtext($2))",
+            SLUG_LOCK_NAMESPACE,
+            slug_stem(&base)
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        // Slugs only hold letters, digits and hyphens, none of which LIKE
+        // treats specially.
+        let taken: HashSet<String> = sqlx::query_scalar!(
+            r#"
+            SELECT slug AS "slug!" FROM products
+            WHERE (slug = $1 OR slug LIKE $1 || '-%') AND id IS DISTINCT FROM $2
+            UNION
+            SELECT slug FROM product_translations
+            WHERE (slug = $1 OR slug LIKE $1 || '-%') AND product_id IS DISTINCT FROM $2
+            UNION
+            SELECT slug FROM product_slug_history
+            WHERE (slug = $1 OR slug LIKE $1 || '-%') AND product_id IS DISTINCT FROM $2
+            "#,
+            base,
+            product_id
+        )
+        .fetch_all(&mut **tx)
+        .await?
+        .into_iter()
+        .collect();
+
+        let mut slug = base.clone();
+        let mut n = 1;
+        while taken.contains(&slug) {
+            n += 1;
+            slug = format!("{}-{}", base, n);
+        }
+
+        Ok(slug)
+    }
+
+    /// Gives a renamed product, or its translation into `locale`, the slug of
+    /// its new name, keeping the current one in its history so links to it
+    /// still resolve. Returns `None` when the slug stays the same.
+    pub async fn rename_slug(
+        tx: &mut Transaction<'_, Postgres>,
+        product_id: Uuid,
+        locale: Option<&str>,
+        current_slug: &str,
+        name: &str,
+    ) -> Result<Option<String>, AppError> {
+        let slug = Self::unique_slug(tx, name, Some(product_id)).await?;
+        if slug == current_slug {
+            return Ok(None);
+        }
+
+        // A product renamed back takes its old slug out of the history.
+        sqlx::query!(
+            r#"
+            DELETE FROM product_slug_history
+            WHERE slug = $1 AND product_id = $2
+            "#,
+            slug,
+            product_id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO product_slug_history (slug, product_id, locale)
+            VALUES ($1, $2, $3)
+            "#,
+            current_slug,
+            product_id,
+            locale
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(Some(slug))
+    }
+
+    /// Inserts the product with its categories and first price history entry.
+    async fn insert(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        data: &CreateProductDto,
+    ) -> Result<Product, AppError> {
+        let attributes: BTreeMap<String, Value> = data
+            .attributes
+            .iter()
+            .filter(|(_, value)| !value.is_null())
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+
+        let currency = parse_currency(data.currency.as_deref().unwrap_or(&self.default_currency))?;
+        let slug = Self::unique_slug(tx, &data.name, None).await?;
+
         let product = sqlx::query_as!(
             Product,
             r#"
-            INSERT INTO products (name, description, price, image_url)
-            VALUES ($1, $2, $3, $4)
-            RETURNING *
+            INSERT INTO products
+                (name, description, price, currency, image_url, search_language, attributes,
+                 external_id, status, published_at, slug)
+            VALUES (
+                $1, $2, $3, $4, $5, $6::TEXT::REGCONFIG, $7, $8, $9,
+                COALESCE($10, CASE WHEN $9 = 'active'::product_status THEN NOW() END), $11
+            )
+            RETURNING
+                id, external_id, name, slug, description, price, currency,
+                NULL::UUID AS "price_list_id?",
+                image_url, created_at, updated_at, version,
+                attributes AS "attributes: Json<BTreeMap<String, Value>>", rating, rating_count,
+                status AS "status: ProductStatus", published_at, deleted_at
             "#,
             data.name,
             data.description,
             data.price,
+            currency,
             data.image_url,
+            self.search_language,
+            Json(&attributes) as _,
+            external_id(&data.external_id),
+            data.status.unwrap_or(ProductStatus::Draft) as ProductStatus,
+            data.published_at,
+            slug
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO product_categories (product_id, category_id)
+            SELECT $1, category_id FROM UNNEST($2::UUID[]) AS category_id
+            ON CONFLICT DO NOTHING
+            "#,
+            product.id,
+            &data.category_ids
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        let schema = AttributeRepo::schema_for_product(tx, product.id).await?;
+        validate_attribute_values(&schema, &attributes)?;
+
+        PriceHistoryRepo::record(
+            tx,
+            product.id,
+            None,
+            None,
+            PriceChangeSource::Created,
+            None,
+        )
+        .await?;
+
+        OutboxRepo::record(tx, product.id, ProductEventType::Created, &product).await?;
+
+        Ok(product)
+    }
+
+    /// Creates or updates the product an import row describes. Rows match the
+    /// product with their `external_id`, else the one owning the variant with
+    /// their `sku`. An update overwrites the product with the row, keeping
+    /// only the currency, categories, status and publication time when the
+    /// row has none. Deleted products have to be restored first.
+    #[instrument(skip(self, row), fields(line = row.line))]
+    pub async fn import(&self, row: &ProductImportRow) -> Result<ImportOutcome, AppError> {
+        let data = &row.product;
+        let mut tx = self.pool.begin().await?;
+
+        let by_external_id = match &data.external_id {
+            Some(external_id) => {
+                sqlx::query_scalar!(
+                    r#"
+                    SELECT id FROM products
+                    WHERE external_id = $1
+                    FOR UPDATE
+                    "#,
+                    external_id
+                )
+                .fetch_optional(&mut *tx)
+                .await?
+            }
+            None => None,
+        };
+        let by_sku = match &row.sku {
+            Some(sku) => {
+                sqlx::query_scalar!(
+                    r#"
+                    SELECT p.id FROM products p
+                    JOIN product_variants v ON v.product_id = p.id
+                    WHERE v.sku = $1
+                    FOR UPDATE OF p
+                    "#,
+                    sku
+                )
+                .fetch_optional(&mut *tx)
+                .await?
+            }
+            None => None,
+        };
+
+        let id = match (by_external_id, by_sku) {
+            (Some(by_external_id), Some(by_sku)) if by_external_id != by_sku => {
+                return Err(AppError::Invalid(
+                    "`external_id` and `sku` belong to different products".to_string(),
+                ));
+            }
+            (by_external_id, by_sku) => by_external_id.or(by_sku),
+        };
+
+        let Some(id) = id else {
+            let product = self.insert(&mut tx, data).await?;
+
+            if let Some(sku) = &row.sku {
+                sqlx::query!(
+                    r#"
+                    INSERT INTO product_variants (product_id, sku)
+                    VALUES ($1, $2)
+                    "#,
+                    product.id,
+                    sku
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            tx.commit().await?;
+
+            return Ok(ImportOutcome::Created);
+        };
+
+        let currency = data.currency.as_deref().map(parse_currency).transpose()?;
+        let attributes: BTreeMap<String, Value> = data
+            .attributes
+            .iter()
+            .filter(|(_, value)| !value.is_null())
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+
+        let previous = sqlx::query!(
+            r#"
+            SELECT name, slug, price, currency, deleted_at FROM products
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if previous.deleted_at.is_some() {
+            return Err(AppError::Invalid(
+                "Product is deleted; restore it before importing it again".to_string(),
+            ));
+        }
+
+        let slug = if data.name != previous.name {
+            Self::rename_slug(&mut tx, id, None, &previous.slug, &data.name).await?
+        } else {
+            None
+        };
+
+        let product = sqlx::query_as!(
+            Product,
+            r#"
+            UPDATE products
+            SET
+                external_id = COALESCE($1, external_id),
+                name = $2,
+                description = $3,
+                price = $4,
+                currency = COALESCE($5, currency),
+                image_url = $6,
+                attributes = $7,
+                status = COALESCE($9, status),
+                published_at = CASE
+                    WHEN $10::TIMESTAMPTZ IS NOT NULL THEN $10
+                    WHEN COALESCE($9, status) = 'active' THEN COALESCE(published_at, NOW())
+                    ELSE published_at
+                END,
+                slug = COALESCE($11, slug),
+                updated_at = NOW(),
+                version = version + 1
+            WHERE id = $8
+            RETURNING
+                id, external_id, name, slug, description, price, currency,
+                NULL::UUID AS "price_list_id?",
+                image_url, created_at, updated_at, version,
+                attributes AS "attributes: Json<BTreeMap<String, Value>>", rating, rating_count,
+                status AS "status: ProductStatus", published_at, deleted_at
+            "#,
+            external_id(&data.external_id),
+            data.name,
+            data.description,
+            data.price,
+            currency,
+            data.image_url,
+            Json(&attributes) as _,
+            id,
+            data.status as Option<ProductStatus>,
+            data.published_at,
+            slug
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if !data.category_ids.is_empty() {
+            sqlx::query!(
+                r#"
+                DELETE FROM product_categories
+                WHERE product_id = $1
+                "#,
+                id
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            sqlx::query!(
+                r#"
+                INSERT INTO product_categories (product_id, category_id)
+                SELECT $1, category_id FROM UNNEST($2::UUID[]) AS category_id
+                ON CONFLICT DO NOTHING
+                "#,
+                id,
+                &data.category_ids
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let schema = AttributeRepo::schema_for_product(&mut tx, id).await?;
+        validate_attribute_values(&schema, &attributes)?;
+
+        OutboxRepo::record(&mut tx, id, ProductEventType::Updated, &product).await?;
+
+        if product.price != previous.price || product.currency != previous.currency {
+            ProductBundleRepo::ensure_own_price(&mut tx, id).await?;
+
+            PriceHistoryRepo::record(
+                &mut tx,
+                id,
+                Some(&previous.price),
+                Some(&previous.currency),
+                PriceChangeSource::Import,
+                None,
+            )
+            .await?;
+
+            ProductBundleRepo::reprice_containing(&mut tx, id).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(ImportOutcome::Updated)
+    }
+
+    /// The next `limit` products after `after` in id order, for exports.
+    /// Deleted products are left out.
+    #[instrument(skip(self))]
+    pub async fn export_batch(
+        &self,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<ProductExportRow>, AppError> {
+        let rows = sqlx::query_as!(
+            ProductExportRow,
+            r#"
+            SELECT
+                p.id, p.external_id,
+                (
+                    SELECT v.sku FROM product_variants v
+                    WHERE v.product_id = p.id
+                    ORDER BY v.created_at, v.id
+                    LIMIT 1
+                ) AS sku,
+                p.name, p.description, p.price, p.currency, p.image_url,
+                ARRAY(
+                    SELECT pc.category_id FROM product_categories pc
+                    WHERE pc.product_id = p.id
+                    ORDER BY pc.category_id
+                ) AS "category_ids!",
+                p.attributes AS "attributes: Json<BTreeMap<String, Value>>",
+                p.status AS "status: ProductStatus", p.published_at
+            FROM products p
+            WHERE p.deleted_at IS NULL AND ($1::UUID IS NULL OR p.id > $1)
+            ORDER BY p.id
+            LIMIT $2
+            "#,
+            after,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Brings a deleted product back with the status it had.
+    #[instrument(skip(self))]
+    pub async fn restore(&self, id: Uuid, version: Option<i64>) -> Result<Product, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let product = sqlx::query_as!(
+            Product,
+            r#"
+            UPDATE products
+            SET deleted_at = NULL, updated_at = NOW(), version = version + 1
+            WHERE id = $1 AND deleted_at IS NOT NULL AND ($2::BIGINT IS NULL OR version = $2)
+            RETURNING
+                id, external_id, name, slug, description, price, currency,
+                NULL::UUID AS "price_list_id?",
+                image_url, created_at, updated_at, version,
+                attributes AS "attributes: Json<BTreeMap<String, Value>>", rating, rating_count,
+                status AS "status: ProductStatus", published_at, deleted_at
+            "#,
+            id,
+            version
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(product) = product {
+            OutboxRepo::record(&mut tx, id, ProductEventType::Restored, &product).await?;
+
+            tx.commit().await?;
+
+            return Ok(product);
+        }
+
+        // Tell a missing or live product apart from a stale version.
+        let deleted = sqlx::query_scalar!(
+            r#"
+            SELECT deleted_at IS NOT NULL AS "deleted!" FROM products
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Product not found".to_string()))?;
+
+        if !deleted {
+            return Err(AppError::Conflict("Product is not deleted".to_string()));
+        }
+
+        Err(AppError::PreconditionFailed)
+    }
+
+    /// Finds the public product a slug belongs to in any locale, now or before a
+    /// rename. Old slugs lead to the product's current slug in their locale,
+    /// or to its own slug once that translation is gone.
+    #[instrument(skip(self))]
+    pub async fn resolve_slug(&self, slug: &str) -> Result<SlugMatch, AppError> {
+        let slug_match = sqlx::query!(
+            r#"
+            WITH matches AS (
+                SELECT id AS product_id, NULL::TEXT AS locale, FALSE AS historic, 0 AS rank
+                FROM products
+                WHERE slug = $1
+                UNION ALL
+                SELECT product_id, locale, FALSE, 1
+                FROM product_translations
+                WHERE slug = $1
+                UNION ALL
+                SELECT product_id, locale, TRUE, 2
+                FROM product_slug_history
+                WHERE slug = $1
+            )
+            SELECT
+                p.id, m.locale, m.historic AS "historic!",
+                COALESCE(t.slug, p.slug) AS "current_slug!"
+            FROM matches m
+            JOIN products p ON p.id = m.product_id
+                AND p.status = 'active' AND p.published_at <= NOW() AND p.deleted_at IS NULL
+            LEFT JOIN product_translations t ON t.product_id = p.id AND t.locale = m.locale
+            ORDER BY m.rank
+            LIMIT 1
+            "#,
+            slug
         )
-        .fetch_one(&self.pool)
-        .await
-        .map_err(|_| AppError::InternalServerError)?;
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Product not found".to_string()))?;
+
+        if slug_match.historic {
+            return Ok(SlugMatch {
+                product_id: slug_match.id,
+                locale: None,
+                redirect_to: Some(slug_match.current_slug),
+            });
+        }
+
+        Ok(SlugMatch {
+            product_id: slug_match.id,
+            locale: slug_match.locale,
+            redirect_to: None,
+        })
+    }
+}
+
+#[async_trait]
+impl Repository<Product, CreateProductDto, UpdateProductDto> for ProductRepo {
+    #[instrument(skip(self, data))]
+    async fn create(&self, data: &CreateProductDto) -> Result<Product, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let product = self.insert(&mut tx, data).await?;
+
+        tx.commit().await?;
 
         Ok(product)
     }
 
+    #[instrument(skip(self))]
     async fn get_all(
         &self,
         pagination: &Pagination,
@@ -43,28 +1311,39 @@ impl Repository<Product, CreateProductDto, UpdateProductDto> for ProductRepo {
         let page = pagination.page.unwrap_or(1);
         let per_page = pagination.per_page.unwrap_or(10);
 
-        let offset = (page - 1) * per_page;
+        let (sort, direction) = pagination.ordering()?;
+        let order_by = pagination.cursor_key(sort, direction)?;
+        let page_cursor = pagination.page_cursor(&order_by)?;
 
-        let products = sqlx::query_as!(
-            Product,
-            r#"
-            SELECT * FROM products
-            ORDER BY created_at DESC
-            LIMIT $1
-            OFFSET $2
-            "#,
+        // Keyset pagination walks backwards for `before` cursors and the rows
+        // are flipped into the requested order once fetched.
+        let fetch_direction = match page_cursor {
+            Some(PageCursor::Before(_)) => direction.reverse(),
+            _ => direction,
+        };
+
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT ");
+        query_builder.push(PRODUCT_COLUMNS);
+        query_builder.push(" FROM ");
+        Self::push_products(&mut query_builder, &pagination.price_query())?;
+        self.push_filters(&mut query_builder, pagination, None)?;
+        Self::push_page(
+            &mut query_builder,
+            sort,
+            fetch_direction,
+            &page_cursor,
+            page,
             per_page,
-            offset
-        )
-        .fetch_all(&self.pool)
-        .await
-        .map_err(|_| AppError::InternalServerError)?;
+            "NULL",
+        )?;
 
-        let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM products")
-            .fetch_one(&self.pool)
-            .await
-            .map_err(|_| AppError::InternalServerError)?;
+        let mut products: Vec<Product> =
+            query_builder.build_query_as().fetch_all(&self.pool).await?;
 
+        let (prev_cursor, next_cursor) =
+            Self::paginate(&mut products, &page_cursor, page, per_page, &order_by);
+
+        let total = self.count(pagination).await?;
         let total_pages = (total as f64 / per_page as f64).ceil() as i64;
 
         Ok(PaginatedResponse {
@@ -72,27 +1351,91 @@ impl Repository<Product, CreateProductDto, UpdateProductDto> for ProductRepo {
             per_page,
             total,
             total_pages,
+            next_cursor,
+            prev_cursor,
+            facets: None,
             data: products,
         })
     }
 
+    #[instrument(skip(self))]
     async fn get_by_id(&self, id: uuid::Uuid) -> Result<Product, AppError> {
         let product = sqlx::query_as!(
             Product,
             r#"
-            SELECT * FROM products
-            WHERE id = $1
+            SELECT
+                id, external_id, name, slug, description, price, currency,
+                NULL::UUID AS "price_list_id?",
+                image_url, created_at, updated_at, version,
+                attributes AS "attributes: Json<BTreeMap<String, Value>>", rating, rating_count,
+                status AS "status: ProductStatus", published_at, deleted_at
+            FROM products
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
             id
         )
-        .fetch_one(&self.pool)
-        .await
-        .map_err(|_| AppError::NotFound("Product not found".to_string()))?;
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Product not found".to_string()))?;
 
         Ok(product)
     }
 
-    async fn update(&self, id: uuid::Uuid, data: &UpdateProductDto) -> Result<Product, AppError> {
+    #[instrument(skip(self, data))]
+    async fn update(
+        &self,
+        id: uuid::Uuid,
+        data: &UpdateProductDto,
+        version: Option<i64>,
+    ) -> Result<Product, AppError> {
+        let currency = data.currency.as_deref().map(parse_currency).transpose()?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let existing = sqlx::query!(
+            r#"
+            SELECT
+                name, slug, price, currency,
+                attributes AS "attributes: Json<BTreeMap<String, Value>>"
+            FROM products
+            WHERE id = $1 AND deleted_at IS NULL
+            FOR UPDATE
+            "#,
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Product not found".to_string()))?;
+
+        // Attribute changes are merged into the stored values, `null` removing
+        // one, and the result is checked against the category schema.
+        let attributes = match &data.attributes {
+            Some(changes) => {
+                let Json(mut attributes) = existing.attributes;
+
+                for (name, value) in changes {
+                    if value.is_null() {
+                        attributes.remove(name);
+                    } else {
+                        attributes.insert(name.clone(), value.clone());
+                    }
+                }
+
+                let schema = AttributeRepo::schema_for_product(&mut tx, id).await?;
+                validate_attribute_values(&schema, &attributes)?;
+
+                Some(Json(attributes))
+            }
+            None => None,
+        };
+
+        let slug = match &data.name {
+            Some(name) if *name != existing.name => {
+                Self::rename_slug(&mut tx, id, None, &existing.slug, name).await?
+            }
+            _ => None,
+        };
+
         let product = sqlx::query_as!(
             Product,
             r#"
@@ -101,35 +1444,101 @@ impl Repository<Product, CreateProductDto, UpdateProductDto> for ProductRepo {
                 name = COALESCE($1, name),
                 description = COALESCE($2, description),
                 price = COALESCE($3, price),
+                currency = COALESCE($8, currency),
                 image_url = COALESCE($4, image_url),
-                updated_at = NOW()
-            WHERE id = $5
-            RETURNING *
+                attributes = COALESCE($7, attributes),
+                external_id = COALESCE($9, external_id),
+                status = COALESCE($10, status),
+                published_at = CASE
+                    WHEN $11::TIMESTAMPTZ IS NOT NULL THEN $11
+                    WHEN COALESCE($10, status) = 'active' THEN COALESCE(published_at, NOW())
+                    ELSE published_at
+                END,
+                slug = COALESCE($12, slug),
+                updated_at = NOW(),
+                version = version + 1
+            WHERE id = $5 AND ($6::BIGINT IS NULL OR version = $6)
+            RETURNING
+                id, external_id, name, slug, description, price, currency,
+                NULL::UUID AS "price_list_id?",
+                image_url, created_at, updated_at, version,
+                attributes AS "attributes: Json<BTreeMap<String, Value>>", rating, rating_count,
+                status AS "status: ProductStatus", published_at, deleted_at
             "#,
             data.name,
             data.description,
             data.price,
             data.image_url,
-            id
+            id,
+            version,
+            attributes as _,
+            currency,
+            external_id(&data.external_id),
+            data.status as Option<ProductStatus>,
+            data.published_at,
+            slug
         )
-        .fetch_one(&self.pool)
-        .await
-        .map_err(|_| AppError::NotFound("Product not found".to_string()))?;
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AppError::PreconditionFailed)?;
+
+        OutboxRepo::record(&mut tx, id, ProductEventType::Updated, &product).await?;
+
+        if product.price != existing.price || product.currency != existing.currency {
+            ProductBundleRepo::ensure_own_price(&mut tx, id).await?;
+
+            PriceHistoryRepo::record(
+                &mut tx,
+                id,
+                Some(&existing.price),
+                Some(&existing.currency),
+                PriceChangeSource::Manual,
+                None,
+            )
+            .await?;
+
+            ProductBundleRepo::reprice_containing(&mut tx, id).await?;
+        }
+
+        tx.commit().await?;
 
         Ok(product)
     }
 
-    async fn delete(&self, id: uuid::Uuid) -> Result<(), AppError> {
-        sqlx::query!(
+    /// Soft deletes the product: it disappears from the catalog but keeps its
+    /// data and can be restored with [`ProductRepo::restore`].
+    #[instrument(skip(self))]
+    async fn delete(&self, id: uuid::Uuid, version: Option<i64>) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let product = sqlx::query_as!(
+            Product,
             r#"
-            DELETE FROM products
-            WHERE id = $1
+            UPDATE products
+            SET deleted_at = NOW(), updated_at = NOW(), version = version + 1
+            WHERE id = $1 AND deleted_at IS NULL AND ($2::BIGINT IS NULL OR version = $2)
+            RETURNING
+                id, external_id, name, slug, description, price, currency,
+                NULL::UUID AS "price_list_id?",
+                image_url, created_at, updated_at, version,
+                attributes AS "attributes: Json<BTreeMap<String, Value>>", rating, rating_count,
+                status AS "status: ProductStatus", published_at, deleted_at
             "#,
-            id
+            id,
+            version
         )
-        .execute(&self.pool)
-        .await
-        .map_err(|_| AppError::NotFound("Product not found".to_string()))?;
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(product) = product else {
+            // Tell a missing product apart from a stale version.
+            self.get_by_id(id).await?;
+            return Err(AppError::PreconditionFailed);
+        };
+
+        OutboxRepo::record(&mut tx, id, ProductEventType::Deleted, &product).await?;
+
+        tx.commit().await?;
 
         Ok(())
     }
@@ -0,0 +1,246 @@
+use sqlx::{PgConnection, PgPool};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::{
+        create_product_review_dto::CreateProductReviewDto, moderate_review_dto::ModerateReviewDto,
+    },
+    models::{
+        app_error::AppError,
+        product_review::{ProductReview, ReviewStatus},
+    },
+};
+
+pub struct ProductReviewRepo {
+    pub pool: PgPool,
+}
+
+impl ProductReviewRepo {
+    /// Recomputes the product's rating and review count from its approved
+    /// reviews, bumping the product version when they change so its ETag
+    /// does too. Called in the transaction that changed them.
+    async fn refresh_rating(conn: &mut PgConnection, product_id: Uuid) -> Result<(), AppError> {
+        // Lock first so the aggregate below sees reviews committed by
+        // concurrent moderators that held the lock before us.
+        sqlx::query!(
+            r#"
+            SELECT id FROM products
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+            product_id
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE products p
+            SET
+                rating = COALESCE(r.rating, 0),
+                rating_count = r.rating_count,
+                version = p.version + 1,
+                updated_at = NOW()
+            FROM (
+                SELECT ROUND(AVG(rating), 2) AS rating, COUNT(*)::INTEGER AS rating_count
+                FROM product_reviews
+                WHERE product_id = $1 AND status = 'approved'
+            ) r
+            WHERE p.id = $1
+                AND (p.rating, p.rating_count)
+                    IS DISTINCT FROM (COALESCE(r.rating, 0), r.rating_count)
+            "#,
+            product_id
+        )
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Reviews of a product, newest first.
+    #[instrument(skip(self))]
+    pub async fn get_for_product(
+        &self,
+        product_id: Uuid,
+        status: ReviewStatus,
+    ) -> Result<Vec<ProductReview>, AppError> {
+        let reviews = sqlx::query_as!(
+            ProductReview,
+            r#"
+            SELECT
+                id, product_id, author_id, rating, title, body, verified_purchase,
+                status AS "status: ReviewStatus", moderation_note, moderated_at,
+                created_at, updated_at
+            FROM product_reviews
+            WHERE product_id = $1 AND status = $2
+            ORDER BY created_at DESC, id
+            "#,
+            product_id,
+            status as ReviewStatus
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(reviews)
+    }
+
+    /// Reviews across all products, oldest first so moderators work through
+    /// the queue in order.
+    #[instrument(skip(self))]
+    pub async fn get_by_status(
+        &self,
+        status: ReviewStatus,
+    ) -> Result<Vec<ProductReview>, AppError> {
+        let reviews = sqlx::query_as!(
+            ProductReview,
+            r#"
+            SELECT
+                id, product_id, author_id, rating, title, body, verified_purchase,
+                status AS "status: ReviewStatus", moderation_note, moderated_at,
+                created_at, updated_at
+            FROM product_reviews
+            WHERE status = $1
+            ORDER BY created_at, id
+            "#,
+            status as ReviewStatus
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(reviews)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_by_id(&self, id: Uuid) -> Result<ProductReview, AppError> {
+        let review = sqlx::query_as!(
+            ProductReview,
+            r#"
+            SELECT
+                id, product_id, author_id, rating, title, body, verified_purchase,
+                status AS "status: ReviewStatus", moderation_note, moderated_at,
+                created_at, updated_at
+            FROM product_reviews
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Review not found".to_string()))?;
+
+        Ok(review)
+    }
+
+    /// New reviews wait for moderation, so the rating is left alone.
+    #[instrument(skip(self, data))]
+    pub async fn create(
+        &self,
+        product_id: Uuid,
+        data: &CreateProductReviewDto,
+    ) -> Result<ProductReview, AppError> {
+        if !(1..=5).contains(&data.rating) {
+            return Err(AppError::Invalid(
+                "Rating must be between 1 and 5".to_string(),
+            ));
+        }
+        if data.title.trim().is_empty() {
+            return Err(AppError::Invalid(
+                "Review title must not be empty".to_string(),
+            ));
+        }
+
+        let review = sqlx::query_as!(
+            ProductReview,
+            r#"
+            INSERT INTO product_reviews
+                (product_id, author_id, rating, title, body, verified_purchase)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING
+                id, product_id, author_id, rating, title, body, verified_purchase,
+                status AS "status: ReviewStatus", moderation_note, moderated_at,
+                created_at, updated_at
+            "#,
+            product_id,
+            data.author_id,
+            data.rating,
+            data.title.trim(),
+            data.body
+                .as_deref()
+                .map(str::trim)
+                .filter(|body| !body.is_empty()),
+            data.verified_purchase
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(review)
+    }
+
+    /// Moves the review to another moderation state and updates the product
+    /// rating accordingly.
+    #[instrument(skip(self, data))]
+    pub async fn moderate(
+        &self,
+        id: Uuid,
+        data: &ModerateReviewDto,
+    ) -> Result<ProductReview, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let review = sqlx::query_as!(
+            ProductReview,
+            r#"
+            UPDATE product_reviews
+            SET
+                status = $1::review_status,
+                moderation_note = $2,
+                moderated_at = CASE WHEN $1::review_status = 'pending' THEN NULL ELSE NOW() END,
+                updated_at = NOW()
+            WHERE id = $3
+            RETURNING
+                id, product_id, author_id, rating, title, body, verified_purchase,
+                status AS "status: ReviewStatus", moderation_note, moderated_at,
+                created_at, updated_at
+            "#,
+            data.status as ReviewStatus,
+            data.moderation_note
+                .as_deref()
+                .map(str::trim)
+                .filter(|note| !note.is_empty()),
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Review not found".to_string()))?;
+
+        Self::refresh_rating(&mut tx, review.product_id).await?;
+
+        tx.commit().await?;
+
+        Ok(review)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn delete(&self, id: Uuid) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let product_id = sqlx::query_scalar!(
+            r#"
+            DELETE FROM product_reviews
+            WHERE id = $1
+            RETURNING product_id
+            "#,
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Review not found".to_string()))?;
+
+        Self::refresh_rating(&mut tx, product_id).await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,209 @@
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::set_product_translation_dto::SetProductTranslationDto,
+    models::{app_error::AppError, product_translation::ProductTranslation},
+    repos::{outbox_repo::OutboxRepo, product_repo::ProductRepo},
+};
+
+pub struct ProductTranslationRepo {
+    pub pool: PgPool,
+}
+
+impl ProductTranslationRepo {
+    #[instrument(skip(self))]
+    pub async fn get_for_product(
+        &self,
+        product_id: Uuid,
+    ) -> Result<Vec<ProductTranslation>, AppError> {
+        let translations = sqlx::query_as!(
+            ProductTranslation,
+            r#"
+            SELECT product_id, locale, name, description, slug, created_at, updated_at
+            FROM product_translations
+            WHERE product_id = $1
+            ORDER BY locale
+            "#,
+            product_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(translations)
+    }
+
+    /// Translations of the products into any of `locales`.
+    #[instrument(skip(self))]
+    pub async fn get_for_products(
+        &self,
+        product_ids: &[Uuid],
+        locales: &[String],
+    ) -> Result<Vec<ProductTranslation>, AppError> {
+        let translations = sqlx::query_as!(
+            ProductTranslation,
+            r#"
+            SELECT product_id, locale, name, description, slug, created_at, updated_at
+            FROM product_translations
+            WHERE product_id = ANY($1) AND locale = ANY($2)
+            "#,
+            product_ids,
+            locales
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(translations)
+    }
+
+    /// Creates or replaces the product's translation into `locale`. A changed
+    /// name moves the translation to a new slug and keeps the old one
+    /// redirecting. Bumps the product's version like any other change to it.
+    #[instrument(skip(self, data))]
+    pub async fn set(
+        &self,
+        product_id: Uuid,
+        locale: &str,
+        data: &SetProductTranslationDto,
+    ) -> Result<ProductTranslation, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Also serializes slug changes of the product.
+        let result = sqlx::query!(
+            r#"
+            UPDATE products
+            SET updated_at = NOW(), version = version + 1
+            WHERE id = $1 AND deleted_at IS NULL
+            "#,
+            product_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Product not found".to_string()));
+        }
+
+        let existing = sqlx::query!(
+            r#"
+            SELECT name, slug FROM product_translations
+            WHERE product_id = $1 AND locale = $2
+            "#,
+            product_id,
+            locale
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let slug = match existing {
+            Some(existing) if existing.name == data.name => existing.slug,
+            Some(existing) => ProductRepo::rename_slug(
+                &mut tx,
+                product_id,
+                Some(locale),
+                &existing.slug,
+                &data.name,
+            )
+            .await?
+            .unwrap_or(existing.slug),
+            None => {
+                let slug = ProductRepo::unique_slug(&mut tx, &data.name, Some(product_id)).await?;
+
+                // The slug may be an old one of the product.
+                sqlx::query!(
+                    r#"
+                    DELETE FROM product_slug_history
+                    WHERE slug = $1 AND product_id = $2
+                    "#,
+                    slug,
+                    product_id
+                )
+                .execute(&mut *tx)
+                .await?;
+
+                slug
+            }
+        };
+
+        let translation = sqlx::query_as!(
+            ProductTranslation,
+            r#"
+            INSERT INTO product_translations (product_id, locale, name, description, slug)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (product_id, locale) DO UPDATE
+            SET
+                name = EXCLUDED.name,
+                description = EXCLUDED.description,
+                slug = EXCLUDED.slug,
+                updated_at = NOW()
+            RETURNING product_id, locale, name, description, slug, created_at, updated_at
+            "#,
+            product_id,
+            locale,
+            data.name,
+            data.description,
+            slug
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        OutboxRepo::record_updated(&mut tx, product_id).await?;
+
+        tx.commit().await?;
+
+        Ok(translation)
+    }
+
+    /// Removes the translation; its slug then redirects to the product's own.
+    #[instrument(skip(self))]
+    pub async fn delete(&self, product_id: Uuid, locale: &str) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE products
+            SET updated_at = NOW(), version = version + 1
+            WHERE id = $1 AND deleted_at IS NULL
+            "#,
+            product_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Product not found".to_string()));
+        }
+
+        let slug = sqlx::query_scalar!(
+            r#"
+            DELETE FROM product_translations
+            WHERE product_id = $1 AND locale = $2
+            RETURNING slug
+            "#,
+            product_id,
+            locale
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Translation not found".to_string()))?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO product_slug_history (slug, product_id, locale)
+            VALUES ($1, $2, $3)
+            "#,
+            slug,
+            product_id,
+            locale
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        OutboxRepo::record_updated(&mut tx, product_id).await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
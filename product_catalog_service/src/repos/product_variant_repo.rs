@@ -0,0 +1,336 @@
+use std::collections::BTreeMap;
+
+use sqlx::{PgPool, Postgres, Transaction, types::Json};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::{
+        create_product_variant_dto::CreateProductVariantDto, product_option_dto::ProductOptionDto,
+        update_product_variant_dto::UpdateProductVariantDto,
+    },
+    models::{
+        app_error::AppError,
+        product_option::{ProductOption, validate_option_values},
+        product_variant::ProductVariant,
+    },
+    repos::outbox_repo::OutboxRepo,
+};
+
+pub struct ProductVariantRepo {
+    pub pool: PgPool,
+}
+
+impl ProductVariantRepo {
+    /// Bumps the product version so its ETag changes along with the embedded
+    /// options and variants.
+    async fn touch_product(
+        tx: &mut Transaction<'_, Postgres>,
+        product_id: Uuid,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE products
+            SET version = version + 1, updated_at = NOW()
+            WHERE id = $1
+            "#,
+            product_id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Product not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn options_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        product_id: Uuid,
+    ) -> Result<Vec<ProductOption>, AppError> {
+        let options = sqlx::query_as!(
+            ProductOption,
+            r#"
+            SELECT * FROM product_options
+            WHERE product_id = $1
+            ORDER BY position
+            "#,
+            product_id
+        )
+        .fetch_all(&mut **tx)
+        .await?;
+
+        Ok(options)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_options(&self, product_ids: &[Uuid]) -> Result<Vec<ProductOption>, AppError> {
+        let options = sqlx::query_as!(
+            ProductOption,
+            r#"
+            SELECT * FROM product_options
+            WHERE product_id = ANY($1)
+            ORDER BY product_id, position
+            "#,
+            product_ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(options)
+    }
+
+    /// Replaces the option axes of a product. Fails if an existing variant
+    /// would no longer match the new axes.
+    #[instrument(skip(self, data))]
+    pub async fn set_options(
+        &self,
+        product_id: Uuid,
+        data: &[ProductOptionDto],
+    ) -> Result<Vec<ProductOption>, AppError> {
+        let mut tx = self.pool.begin().await?;
+        Self::touch_product(&mut tx, product_id).await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM product_options
+            WHERE product_id = $1
+            "#,
+            product_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        for (position, option) in data.iter().enumerate() {
+            sqlx::query!(
+                r#"
+                INSERT INTO product_options (product_id, name, position, values)
+                VALUES ($1, $2, $3, $4)
+                "#,
+                product_id,
+                option.name,
+                position as i32,
+                &option.values
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let options = Self::options_in_tx(&mut tx, product_id).await?;
+
+        let variants = sqlx::query_as!(
+            ProductVariant,
+            r#"
+            SELECT id, product_id, sku, price, barcode, weight_grams, image_url,
+                options AS "options: Json<BTreeMap<String, String>>", created_at, updated_at
+            FROM product_variants
+            WHERE product_id = $1
+            "#,
+            product_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for variant in &variants {
+            validate_option_values(&options, &variant.options).map_err(|_| {
+                AppError::Conflict(format!(
+                    "Variant '{}' does not match the new options",
+                    variant.sku
+                ))
+            })?;
+        }
+
+        OutboxRepo::record_updated(&mut tx, product_id).await?;
+
+        tx.commit().await?;
+
+        Ok(options)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_variants(
+        &self,
+        product_ids: &[Uuid],
+    ) -> Result<Vec<ProductVariant>, AppError> {
+        let variants = sqlx::query_as!(
+            ProductVariant,
+            r#"
+            SELECT id, product_id, sku, price, barcode, weight_grams, image_url,
+                options AS "options: Json<BTreeMap<String, String>>", created_at, updated_at
+            FROM product_variants
+            WHERE product_id = ANY($1)
+            ORDER BY product_id, sku
+            "#,
+            product_ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(variants)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_variant(
+        &self,
+        product_id: Uuid,
+        variant_id: Uuid,
+    ) -> Result<ProductVariant, AppError> {
+        let variant = sqlx::query_as!(
+            ProductVariant,
+            r#"
+            SELECT id, product_id, sku, price, barcode, weight_grams, image_url,
+                options AS "options: Json<BTreeMap<String, String>>", created_at, updated_at
+            FROM product_variants
+            WHERE product_id = $1 AND id = $2
+            "#,
+            product_id,
+            variant_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Variant not found".to_string()))?;
+
+        Ok(variant)
+    }
+
+    #[instrument(skip(self, data))]
+    pub async fn create_variant(
+        &self,
+        product_id: Uuid,
+        data: &CreateProductVariantDto,
+    ) -> Result<ProductVariant, AppError> {
+        let mut tx = self.pool.begin().await?;
+        Self::touch_product(&mut tx, product_id).await?;
+
+        let options = Self::options_in_tx(&mut tx, product_id).await?;
+        validate_option_values(&options, &data.options)?;
+
+        let variant = sqlx::query_as!(
+            ProductVariant,
+            r#"
+            INSERT INTO product_variants
+                (product_id, sku, price, barcode, weight_grams, image_url, options)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id, product_id, sku, price, barcode, weight_grams, image_url,
+                options AS "options: Json<BTreeMap<String, String>>", created_at, updated_at
+            "#,
+            product_id,
+            data.sku,
+            data.price,
+            data.barcode,
+            data.weight_grams,
+            data.image_url,
+            Json(&data.options) as _
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        OutboxRepo::record_updated(&mut tx, product_id).await?;
+
+        tx.commit().await?;
+
+        Ok(variant)
+    }
+
+    #[instrument(skip(self, data))]
+    pub async fn update_variant(
+        &self,
+        product_id: Uuid,
+        variant_id: Uuid,
+        data: &UpdateProductVariantDto,
+    ) -> Result<ProductVariant, AppError> {
+        let mut tx = self.pool.begin().await?;
+        Self::touch_product(&mut tx, product_id).await?;
+
+        if let Some(values) = &data.options {
+            let options = Self::options_in_tx(&mut tx, product_id).await?;
+            validate_option_values(&options, values)?;
+        }
+
+        let variant = sqlx::query_as!(
+            ProductVariant,
+            r#"
+            UPDATE product_variants
+            SET
+                sku = COALESCE($3, sku),
+                price = COALESCE($4, price),
+                barcode = COALESCE($5, barcode),
+                weight_grams = COALESCE($6, weight_grams),
+                image_url = COALESCE($7, image_url),
+                options = COALESCE($8, options),
+                updated_at = NOW()
+            WHERE product_id = $1 AND id = $2
+            RETURNING id, product_id, sku, price, barcode, weight_grams, image_url,
+                options AS "options: Json<BTreeMap<String, String>>", created_at, updated_at
+            "#,
+            product_id,
+            variant_id,
+            data.sku,
+            data.price,
+            data.barcode,
+            data.weight_grams,
+            data.image_url,
+            data.options.as_ref().map(Json) as _
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Variant not found".to_string()))?;
+
+        OutboxRepo::record_updated(&mut tx, product_id).await?;
+
+        tx.commit().await?;
+
+        Ok(variant)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn delete_variant(&self, product_id: Uuid, variant_id: Uuid) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+        Self::touch_product(&mut tx, product_id).await?;
+
+        // The inventory ledger is an audit trail and must outlive stock changes.
+        let has_ledger_entries = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM inventory_ledger l
+                JOIN product_variants v ON v.id = l.variant_id
+                WHERE v.product_id = $1 AND l.variant_id = $2
+            ) AS "exists!"
+            "#,
+            product_id,
+            variant_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if has_ledger_entries {
+            return Err(AppError::Conflict(
+                "Variant has inventory history and cannot be deleted".to_string(),
+            ));
+        }
+
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM product_variants
+            WHERE product_id = $1 AND id = $2
+            "#,
+            product_id,
+            variant_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Variant not found".to_string()));
+        }
+
+        OutboxRepo::record_updated(&mut tx, product_id).await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
@@ -8,6 +8,11 @@ pub trait Repository<T, C, U>: Send + Sync {
     async fn create(&self, data: &C) -> Result<T, AppError>;
     async fn get_all(&self, pagination: &Pagination) -> Result<PaginatedResponse<T>, AppError>;
     async fn get_by_id(&self, id: uuid::Uuid) -> Result<T, AppError>;
-    async fn update(&self, id: uuid::Uuid, data: &U) -> Result<T, AppError>;
-    async fn delete(&self, id: uuid::Uuid) -> Result<(), AppError>;
+    async fn update(
+        &self,
+        id: uuid::Uuid,
+        data: &U,
+        version: Option<i64>,
+    ) -> Result<T, AppError>;
+    async fn delete(&self, id: uuid::Uuid, version: Option<i64>) -> Result<(), AppError>;
 }
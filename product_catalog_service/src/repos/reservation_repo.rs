@@ -0,0 +1,396 @@
+use std::collections::BTreeMap;
+
+use sqlx::{PgPool, Postgres, Transaction};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::reservation_line_dto::ReservationLineDto,
+    models::{
+        app_error::AppError,
+        reservation::{Reservation, ReservationStatus},
+        reservation_item::ReservationItem,
+    },
+};
+
+/// How many expired reservations the reaper releases per transaction.
+const EXPIRE_BATCH_SIZE: i64 = 100;
+
+pub struct ReservationRepo {
+    pub pool: PgPool,
+}
+
+impl ReservationRepo {
+    async fn get_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+    ) -> Result<Reservation, AppError> {
+        let reservation = sqlx::query_as!(
+            Reservation,
+            r#"
+            SELECT id, status AS "status: ReservationStatus", expires_at, created_at, updated_at
+            FROM reservations
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+            id
+        )
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Reservation not found".to_string()))?;
+
+        Ok(reservation)
+    }
+
+    /// Locks the stock levels held by the given reservations in a fixed order
+    /// so that concurrent reservations, confirmations and releases cannot
+    /// deadlock each other.
+    async fn lock_levels(tx: &mut Transaction<'_, Postgres>, ids: &[Uuid]) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            SELECT l.variant_id
+            FROM inventory_levels l
+            JOIN reservation_items i
+                ON i.variant_id = l.variant_id AND i.warehouse_id = l.warehouse_id
+            WHERE i.reservation_id = ANY($1)
+            ORDER BY l.variant_id, l.warehouse_id
+            FOR UPDATE OF l
+            "#,
+            ids
+        )
+        .fetch_all(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Hands the held stock back and moves the reservation to `status`. The
+    /// caller must already hold the level locks taken by `lock_levels`.
+    async fn release_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+        status: ReservationStatus,
+    ) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE inventory_levels l
+            SET reserved = l.reserved - i.quantity, updated_at = NOW()
+            FROM reservation_items i
+            WHERE i.reservation_id = $1
+                AND i.variant_id = l.variant_id
+                AND i.warehouse_id = l.warehouse_id
+            "#,
+            id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE reservations
+            SET status = $2, updated_at = NOW()
+            WHERE id = $1
+            "#,
+            id,
+            status as ReservationStatus
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    fn ensure_pending(reservation: &Reservation) -> Result<(), AppError> {
+        if reservation.status != ReservationStatus::Pending {
+            return Err(AppError::Conflict(format!(
+                "Reservation is already {}",
+                reservation.status.as_str()
+            )));
+        }
+
+        if reservation.expires_at <= chrono::Utc::now() {
+            return Err(AppError::Conflict("Reservation has expired".to_string()));
+        }
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get(&self, id: Uuid) -> Result<Reservation, AppError> {
+        let reservation = sqlx::query_as!(
+            Reservation,
+            r#"
+            SELECT id, status AS "status: ReservationStatus", expires_at, created_at, updated_at
+            FROM reservations
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Reservation not found".to_string()))?;
+
+        Ok(reservation)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_items(&self, id: Uuid) -> Result<Vec<ReservationItem>, AppError> {
+        let items = sqlx::query_as!(
+            ReservationItem,
+            r#"
+            SELECT i.reservation_id, i.variant_id, v.sku, i.warehouse_id,
+                   w.code AS warehouse_code, i.quantity
+            FROM reservation_items i
+            JOIN product_variants v ON v.id = i.variant_id
+            JOIN warehouses w ON w.id = i.warehouse_id
+            WHERE i.reservation_id = $1
+            ORDER BY v.sku, w.code
+            "#,
+            id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(items)
+    }
+
+    /// Holds stock for every line or for none of them. The stock levels of the
+    /// requested SKUs are locked with `SELECT ... FOR UPDATE` while quantities
+    /// are allocated, so concurrent reservations cannot oversell. A line may be
+    /// split across warehouses, drawing from the fullest first.
+    #[instrument(skip(self))]
+    pub async fn reserve(
+        &self,
+        lines: &[ReservationLineDto],
+        ttl_seconds: i64,
+    ) -> Result<Reservation, AppError> {
+        if lines.is_empty() {
+            return Err(AppError::Invalid(
+                "A reservation needs at least one item".to_string(),
+            ));
+        }
+
+        let mut requested: BTreeMap<&str, i64> = BTreeMap::new();
+        for line in lines {
+            if line.quantity <= 0 {
+                return Err(AppError::Invalid(format!(
+                    "Quantity for SKU {} must be positive",
+                    line.sku
+                )));
+            }
+            *requested.entry(line.sku.as_str()).or_default() += line.quantity as i64;
+        }
+
+        let skus: Vec<String> = requested.keys().map(|sku| sku.to_string()).collect();
+
+        let mut tx = self.pool.begin().await?;
+
+        let existing = sqlx::query_scalar!(
+            r#"
+            SELECT sku FROM product_variants
+            WHERE sku = ANY($1)
+            "#,
+            &skus
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        if let Some(missing) = skus.iter().find(|sku| !existing.contains(sku)) {
+            return Err(AppError::NotFound(format!("SKU {} not found", missing)));
+        }
+
+        let levels = sqlx::query!(
+            r#"
+            SELECT l.variant_id, v.sku, l.warehouse_id, l.on_hand - l.reserved AS "available!"
+            FROM inventory_levels l
+            JOIN product_variants v ON v.id = l.variant_id
+            WHERE v.sku = ANY($1)
+            ORDER BY l.variant_id, l.warehouse_id
+            FOR UPDATE OF l
+            "#,
+            &skus
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut allocations = Vec::new();
+        for (sku, quantity) in &requested {
+            let mut candidates: Vec<_> = levels
+                .iter()
+                .filter(|level| level.sku == *sku && level.available > 0)
+                .collect();
+            candidates.sort_by_key(|level| std::cmp::Reverse(level.available));
+
+            let available: i64 = candidates.iter().map(|level| level.available as i64).sum();
+            if available < *quantity {
+                return Err(AppError::Conflict(format!(
+                    "Insufficient stock for SKU {}: {} available, {} requested",
+                    sku, available, quantity
+                )));
+            }
+
+            let mut remaining = *quantity;
+            for level in candidates {
+                if remaining == 0 {
+                    break;
+                }
+                let take = remaining.min(level.available as i64);
+                allocations.push((level.variant_id, level.warehouse_id, take as i32));
+                remaining -= take;
+            }
+        }
+
+        let reservation = sqlx::query_as!(
+            Reservation,
+            r#"
+            INSERT INTO reservations (expires_at)
+            VALUES (NOW() + $1::BIGINT * INTERVAL '1 second')
+            RETURNING id, status AS "status: ReservationStatus", expires_at, created_at, updated_at
+            "#,
+            ttl_seconds
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for (variant_id, warehouse_id, quantity) in allocations {
+            sqlx::query!(
+                r#"
+                UPDATE inventory_levels
+                SET reserved = reserved + $3, updated_at = NOW()
+                WHERE variant_id = $1 AND warehouse_id = $2
+                "#,
+                variant_id,
+                warehouse_id,
+                quantity
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            sqlx::query!(
+                r#"
+                INSERT INTO reservation_items (reservation_id, variant_id, warehouse_id, quantity)
+                VALUES ($1, $2, $3, $4)
+                "#,
+                reservation.id,
+                variant_id,
+                warehouse_id,
+                quantity
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(reservation)
+    }
+
+    /// Turns held stock into a sale: the reserved quantities leave on-hand
+    /// stock and are written to the ledger.
+    #[instrument(skip(self))]
+    pub async fn confirm(&self, id: Uuid) -> Result<Reservation, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let reservation = Self::get_in_tx(&mut tx, id).await?;
+        Self::ensure_pending(&reservation)?;
+        Self::lock_levels(&mut tx, &[id]).await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE inventory_levels l
+            SET on_hand = l.on_hand - i.quantity,
+                reserved = l.reserved - i.quantity,
+                updated_at = NOW()
+            FROM reservation_items i
+            WHERE i.reservation_id = $1
+                AND i.variant_id = l.variant_id
+                AND i.warehouse_id = l.warehouse_id
+            "#,
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO inventory_ledger (variant_id, warehouse_id, quantity_delta, reason, note)
+            SELECT variant_id, warehouse_id, -quantity, 'sale', 'Reservation ' || reservation_id
+            FROM reservation_items
+            WHERE reservation_id = $1
+            "#,
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE reservations
+            SET status = 'confirmed', updated_at = NOW()
+            WHERE id = $1
+            "#,
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let reservation = Self::get_in_tx(&mut tx, id).await?;
+        tx.commit().await?;
+
+        Ok(reservation)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn release(&self, id: Uuid) -> Result<Reservation, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let reservation = Self::get_in_tx(&mut tx, id).await?;
+        if reservation.status != ReservationStatus::Pending {
+            return Err(AppError::Conflict(format!(
+                "Reservation is already {}",
+                reservation.status.as_str()
+            )));
+        }
+
+        Self::lock_levels(&mut tx, &[id]).await?;
+        Self::release_in_tx(&mut tx, id, ReservationStatus::Released).await?;
+
+        let reservation = Self::get_in_tx(&mut tx, id).await?;
+        tx.commit().await?;
+
+        Ok(reservation)
+    }
+
+    /// Releases pending reservations whose lifetime has passed. Rows locked by
+    /// a concurrent confirm or release are skipped and picked up next time.
+    /// Returns the number of reservations expired.
+    #[instrument(skip(self))]
+    pub async fn expire_due(&self) -> Result<usize, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let ids = sqlx::query_scalar!(
+            r#"
+            SELECT id FROM reservations
+            WHERE status = 'pending' AND expires_at <= NOW()
+            ORDER BY expires_at
+            LIMIT $1
+            FOR UPDATE SKIP LOCKED
+            "#,
+            EXPIRE_BATCH_SIZE
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // Lock every level the batch touches in one ordered pass; locking them
+        // reservation by reservation could deadlock with `reserve`.
+        Self::lock_levels(&mut tx, &ids).await?;
+
+        for id in &ids {
+            Self::release_in_tx(&mut tx, *id, ReservationStatus::Expired).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(ids.len())
+    }
+}
@@ -0,0 +1,428 @@
+use bigdecimal::BigDecimal;
+use chrono::Utc;
+use sqlx::{PgPool, Postgres, Transaction};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::create_scheduled_price_change_dto::CreateScheduledPriceChangeDto,
+    models::{
+        app_error::AppError,
+        price_history_entry::PriceChangeSource,
+        scheduled_price_change::{ScheduledPriceChange, ScheduledPriceStatus},
+    },
+    repos::{price_history_repo::PriceHistoryRepo, product_bundle_repo::ProductBundleRepo},
+};
+
+/// How many due price changes the scheduler picks up per run.
+const APPLY_BATCH_SIZE: i64 = 100;
+
+pub struct ScheduledPriceRepo {
+    pub pool: PgPool,
+}
+
+impl ScheduledPriceRepo {
+    /// Sets the base price of a product and records the change, returning
+    /// the price it replaced.
+    async fn set_price(
+        tx: &mut Transaction<'_, Postgres>,
+        product_id: Uuid,
+        price: &BigDecimal,
+        source: PriceChangeSource,
+        change_id: Uuid,
+    ) -> Result<BigDecimal, AppError> {
+        let previous = sqlx::query!(
+            r#"
+            SELECT price, currency FROM products
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+            product_id
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE products
+            SET price = $1, updated_at = NOW(), version = version + 1
+            WHERE id = $2
+            "#,
+            price,
+            product_id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        PriceHistoryRepo::record(
+            tx,
+            product_id,
+            Some(&previous.price),
+            Some(&previous.currency),
+            source,
+            Some(change_id),
+        )
+        .await?;
+
+        ProductBundleRepo::reprice_containing(tx, product_id).await?;
+
+        Ok(previous.price)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_for_product(
+        &self,
+        product_id: Uuid,
+    ) -> Result<Vec<ScheduledPriceChange>, AppError> {
+        let changes = sqlx::query_as!(
+            ScheduledPriceChange,
+            r#"
+            SELECT
+                id, product_id, price, starts_at, ends_at, note,
+                status AS "status: ScheduledPriceStatus", previous_price, failure,
+                created_at, updated_at
+            FROM scheduled_price_changes
+            WHERE product_id = $1
+            ORDER BY starts_at, id
+            "#,
+            product_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(changes)
+    }
+
+    /// Schedules a base price change. Sales of one product must not overlap
+    /// each other or any other pending change.
+    #[instrument(skip(self, data))]
+    pub async fn create(
+        &self,
+        product_id: Uuid,
+        data: &CreateScheduledPriceChangeDto,
+    ) -> Result<ScheduledPriceChange, AppError> {
+        if let Some(ends_at) = data.ends_at {
+            if ends_at <= data.starts_at {
+                return Err(AppError::Invalid(
+                    "`ends_at` must be after `starts_at`".to_string(),
+                ));
+            }
+            if ends_at <= Utc::now() {
+                return Err(AppError::Invalid(
+                    "`ends_at` must be in the future".to_string(),
+                ));
+            }
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        // Serializes scheduling per product so the overlap check holds.
+        sqlx::query!(
+            r#"
+            SELECT id FROM products
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+            product_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Product not found".to_string()))?;
+
+        ProductBundleRepo::ensure_own_price(&mut tx, product_id).await?;
+
+        let overlaps = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM scheduled_price_changes
+                WHERE product_id = $1
+                    AND status IN ('scheduled', 'active')
+                    AND tstzrange(starts_at, COALESCE(ends_at, starts_at), '[]')
+                        && tstzrange($2::TIMESTAMPTZ, COALESCE($3::TIMESTAMPTZ, $2), '[]')
+            ) AS "overlaps!"
+            "#,
+            product_id,
+            data.starts_at,
+            data.ends_at
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if overlaps {
+            return Err(AppError::Conflict(
+                "Overlaps another scheduled price change of the product".to_string(),
+            ));
+        }
+
+        let change = sqlx::query_as!(
+            ScheduledPriceChange,
+            r#"
+            INSERT INTO scheduled_price_changes (product_id, price, starts_at, ends_at, note)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING
+                id, product_id, price, starts_at, ends_at, note,
+                status AS "status: ScheduledPriceStatus", previous_price, failure,
+                created_at, updated_at
+            "#,
+            product_id,
+            data.price,
+            data.starts_at,
+            data.ends_at,
+            data.note
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(change)
+    }
+
+    /// Cancels a pending change. A running sale is ended instead; the
+    /// scheduler restores the previous price on its next run.
+    #[instrument(skip(self))]
+    pub async fn cancel(
+        &self,
+        product_id: Uuid,
+        id: Uuid,
+    ) -> Result<ScheduledPriceChange, AppError> {
+        let change = sqlx::query_as!(
+            ScheduledPriceChange,
+            r#"
+            UPDATE scheduled_price_changes
+            SET
+                status = CASE WHEN status = 'scheduled'
+                    THEN 'cancelled'::scheduled_price_status ELSE status END,
+                ends_at = CASE WHEN status = 'active' THEN NOW() ELSE ends_at END,
+                updated_at = NOW()
+            WHERE id = $1 AND product_id = $2 AND status IN ('scheduled', 'active')
+            RETURNING
+                id, product_id, price, starts_at, ends_at, note,
+                status AS "status: ScheduledPriceStatus", previous_price, failure,
+                created_at, updated_at
+            "#,
+            id,
+            product_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match change {
+            Some(change) => Ok(change),
+            None => {
+                let exists = sqlx::query_scalar!(
+                    r#"
+                    SELECT EXISTS (
+                        SELECT 1 FROM scheduled_price_changes
+                        WHERE id = $1 AND product_id = $2
+                    ) AS "exists!"
+                    "#,
+                    id,
+                    product_id
+                )
+                .fetch_one(&self.pool)
+                .await?;
+
+                if exists {
+                    Err(AppError::Conflict(
+                        "Scheduled price change is already over".to_string(),
+                    ))
+                } else {
+                    Err(AppError::NotFound(
+                        "Scheduled price change not found".to_string(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Starts a due change, returning `false` when it was cancelled, applied
+    /// or taken by another scheduler since it was found.
+    async fn start_change(&self, id: Uuid) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let Some(change) = sqlx::query!(
+            r#"
+            SELECT id, product_id, price
+            FROM scheduled_price_changes
+            WHERE id = $1 AND status = 'scheduled' AND starts_at <= NOW()
+            FOR UPDATE SKIP LOCKED
+            "#,
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(false);
+        };
+
+        let previous_price = Self::set_price(
+            &mut tx,
+            change.product_id,
+            &change.price,
+            PriceChangeSource::Scheduled,
+            change.id,
+        )
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE scheduled_price_changes
+            SET
+                status = CASE WHEN ends_at IS NULL
+                    THEN 'completed'::scheduled_price_status
+                    ELSE 'active'::scheduled_price_status END,
+                previous_price = $1,
+                updated_at = NOW()
+            WHERE id = $2
+            "#,
+            previous_price,
+            change.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    /// Ends a sale that is over. The previous price is only put back if the
+    /// price was not changed again meanwhile.
+    async fn end_change(&self, id: Uuid) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let Some(change) = sqlx::query!(
+            r#"
+            SELECT id, product_id, price, previous_price AS "previous_price!"
+            FROM scheduled_price_changes
+            WHERE id = $1 AND status = 'active' AND ends_at <= NOW()
+            FOR UPDATE SKIP LOCKED
+            "#,
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(false);
+        };
+
+        let current_price = sqlx::query_scalar!(
+            r#"
+            SELECT price FROM products
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+            change.product_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if current_price == change.price {
+            Self::set_price(
+                &mut tx,
+                change.product_id,
+                &change.previous_price,
+                PriceChangeSource::ScheduleEnded,
+                change.id,
+            )
+            .await?;
+        } else {
+            tracing::info!(
+                change_id = %change.id,
+                "price changed during the sale, keeping the current price"
+            );
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE scheduled_price_changes
+            SET status = 'completed', updated_at = NOW()
+            WHERE id = $1
+            "#,
+            change.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    /// Counts a processed change, parking it as `failed` when it could not be
+    /// applied. An unreachable database is returned instead so the change is
+    /// retried on the next run.
+    async fn settle(&self, id: Uuid, result: Result<bool, AppError>) -> Result<usize, AppError> {
+        let failure = match result {
+            Ok(processed) => return Ok(processed as usize),
+            Err(AppError::ServiceUnavailable) => return Err(AppError::ServiceUnavailable),
+            Err(
+                AppError::NotFound(msg)
+                | AppError::Invalid(msg)
+                | AppError::Conflict(msg)
+                | AppError::ConstraintViolation(msg),
+            ) => msg,
+            Err(_) => "Internal Server Error".to_string(),
+        };
+
+        tracing::error!(change_id = %id, failure, "failed to apply scheduled price change");
+
+        sqlx::query!(
+            r#"
+            UPDATE scheduled_price_changes
+            SET status = 'failed', failure = $2, updated_at = NOW()
+            WHERE id = $1
+            "#,
+            id,
+            failure
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(1)
+    }
+
+    /// Starts the changes that are due and ends the sales that are over,
+    /// returning how many changes were processed. Every change is applied in
+    /// its own transaction so one that fails cannot hold up the others.
+    #[instrument(skip(self))]
+    pub async fn apply_due(&self) -> Result<usize, AppError> {
+        let starting = sqlx::query_scalar!(
+            r#"
+            SELECT id FROM scheduled_price_changes
+            WHERE status = 'scheduled' AND starts_at <= NOW()
+            ORDER BY starts_at
+            LIMIT $1
+            "#,
+            APPLY_BATCH_SIZE
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut processed = 0;
+        for id in starting {
+            let result = self.start_change(id).await;
+            processed += self.settle(id, result).await?;
+        }
+
+        let ending = sqlx::query_scalar!(
+            r#"
+            SELECT id FROM scheduled_price_changes
+            WHERE status = 'active' AND ends_at <= NOW()
+            ORDER BY ends_at
+            LIMIT $1
+            "#,
+            APPLY_BATCH_SIZE
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        for id in ending {
+            let result = self.end_change(id).await;
+            processed += self.settle(id, result).await?;
+        }
+
+        Ok(processed)
+    }
+}
@@ -0,0 +1,135 @@
+use sqlx::PgPool;
+use tracing::instrument;
+
+use crate::models::{app_error::AppError, suggestion::Suggestion};
+
+pub struct SuggestionRepo {
+    pub pool: PgPool,
+}
+
+impl SuggestionRepo {
+    /// Product names and categories starting with `term`, or close to it for
+    /// typos, best first. Exact prefixes outrank fuzzy matches; products are
+    /// boosted by recent sales and categories by their product count. Products
+    /// sharing a name are suggested once. Returns `None` when the lookup took
+    /// longer than `timeout_ms`; the database cancels it rather than letting it
+    /// run on and hold a connection after the caller gave up.
+    #[instrument(skip(self))]
+    pub async fn suggest(
+        &self,
+        term: &str,
+        limit: i64,
+        timeout_ms: u64,
+    ) -> Result<Option<Vec<Suggestion>>, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query_scalar!(
+            "SELECT set_config('statement_timeout', $1, true)",
+            format!("{}ms", timeout_ms)
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let suggestions = sqlx::query_as!(
+            Suggestion,
+            r#"
+            WITH input AS (
+                SELECT
+                    lower($1) AS term,
+                    replace(replace(replace(lower($1), '\', '\\'), '%', '\%'), '_', '\_') || '%'
+                        AS prefix
+            ),
+            product_matches AS (
+                SELECT DISTINCT ON (lower(p.name))
+                    p.id,
+                    p.name,
+                    CASE
+                        WHEN lower(p.name) LIKE i.prefix THEN 1
+                        ELSE GREATEST(
+                            similarity(i.term, lower(p.name)),
+                            word_similarity(i.term, lower(p.name))
+                        )
+                    END AS similarity,
+                    COALESCE(pp.score, 0) AS popularity
+                FROM products p
+                CROSS JOIN input i
+                LEFT JOIN product_popularity pp ON pp.product_id = p.id
+                WHERE p.status = 'active' AND p.published_at <= NOW() AND p.deleted_at IS NULL
+                    AND (
+                        lower(p.name) LIKE i.prefix
+                        OR i.term % lower(p.name)
+                        OR i.term <% lower(p.name)
+                    )
+                ORDER BY lower(p.name), COALESCE(pp.score, 0) DESC, p.id
+            ),
+            category_matches AS (
+                SELECT
+                    c.id,
+                    c.name,
+                    CASE
+                        WHEN lower(c.name) LIKE i.prefix THEN 1
+                        ELSE GREATEST(
+                            similarity(i.term, lower(c.name)),
+                            word_similarity(i.term, lower(c.name))
+                        )
+                    END AS similarity,
+                    (SELECT COUNT(*) FROM product_categories pc WHERE pc.category_id = c.id)
+                        AS popularity
+                FROM categories c
+                CROSS JOIN input i
+                WHERE lower(c.name) LIKE i.prefix
+                    OR i.term % lower(c.name)
+                    OR i.term <% lower(c.name)
+            )
+            (
+                SELECT
+                    'product' AS "kind!",
+                    id AS "id!",
+                    name AS "name!",
+                    (similarity * (1 + ln(1 + popularity::FLOAT8)))::FLOAT8 AS "score!"
+                FROM product_matches
+                ORDER BY 4 DESC, 3
+                LIMIT $2
+            )
+            UNION ALL
+            (
+                SELECT
+                    'category',
+                    id,
+                    name,
+                    (similarity * (1 + ln(1 + popularity::FLOAT8)))::FLOAT8
+                FROM category_matches
+                ORDER BY 4 DESC, 3
+                LIMIT $2
+            )
+            "#,
+            term,
+            limit
+        )
+        .fetch_all(&mut *tx)
+        .await;
+
+        let suggestions = match suggestions {
+            Ok(suggestions) => suggestions,
+            // query_canceled, raised when the statement timeout expires
+            Err(sqlx::Error::Database(err)) if err.code().as_deref() == Some("57014") => {
+                return Ok(None);
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        tx.commit().await?;
+
+        Ok(Some(suggestions))
+    }
+
+    /// Recomputes the sales figures suggestions are weighted by.
+    #[instrument(skip(self))]
+    pub async fn refresh_popularity(&self) -> Result<(), AppError> {
+        sqlx::query!("REFRESH MATERIALIZED VIEW CONCURRENTLY product_popularity")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
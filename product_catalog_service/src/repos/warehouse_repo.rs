@@ -0,0 +1,63 @@
+use sqlx::PgPool;
+use tracing::instrument;
+
+use crate::{
+    dtos::create_warehouse_dto::CreateWarehouseDto,
+    models::{app_error::AppError, warehouse::Warehouse},
+};
+
+pub struct WarehouseRepo {
+    pub pool: PgPool,
+}
+
+impl WarehouseRepo {
+    #[instrument(skip(self))]
+    pub async fn get_all(&self) -> Result<Vec<Warehouse>, AppError> {
+        let warehouses = sqlx::query_as!(
+            Warehouse,
+            r#"
+            SELECT * FROM warehouses
+            ORDER BY code
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(warehouses)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_by_code(&self, code: &str) -> Result<Warehouse, AppError> {
+        let warehouse = sqlx::query_as!(
+            Warehouse,
+            r#"
+            SELECT * FROM warehouses
+            WHERE code = $1
+            "#,
+            code
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Warehouse not found".to_string()))?;
+
+        Ok(warehouse)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn create(&self, data: &CreateWarehouseDto) -> Result<Warehouse, AppError> {
+        let warehouse = sqlx::query_as!(
+            Warehouse,
+            r#"
+            INSERT INTO warehouses (code, name)
+            VALUES ($1, $2)
+            RETURNING *
+            "#,
+            data.code,
+            data.name
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(warehouse)
+    }
+}
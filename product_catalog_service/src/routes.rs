@@ -1 +1,7 @@
+pub mod admin_routes;
+pub mod category_routes;
+pub mod inventory_routes;
+pub mod price_list_routes;
 pub mod product_routes;
+pub mod reservation_routes;
+pub mod review_routes;
@@ -0,0 +1,13 @@
+use axum::{Router, routing::get};
+use std::sync::Arc;
+
+use crate::{controllers::product_controller, models::app_state::AppState};
+
+/// Views of the catalog for the people maintaining it, showing drafts,
+/// archived and deleted products as well. Keep these behind the gateway's
+/// admin authentication.
+pub fn admin_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/products", get(product_controller::get_admin_products))
+        .route("/products/{id}", get(product_controller::get_admin_product))
+}
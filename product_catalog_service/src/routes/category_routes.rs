@@ -0,0 +1,35 @@
+use axum::{
+    Router,
+    routing::{get, post, put},
+};
+use std::sync::Arc;
+
+use crate::{
+    controllers::{attribute_controller, category_controller},
+    models::app_state::AppState,
+};
+
+pub fn category_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/",
+            post(category_controller::create_category).get(category_controller::get_category_tree),
+        )
+        .route(
+            "/{id}",
+            get(category_controller::get_category)
+                .put(category_controller::update_category)
+                .delete(category_controller::delete_category),
+        )
+        .route("/{id}/move", post(category_controller::move_category))
+        .route(
+            "/{id}/attributes",
+            get(attribute_controller::get_category_attributes)
+                .post(attribute_controller::create_category_attribute),
+        )
+        .route(
+            "/{id}/attributes/{attribute_id}",
+            put(attribute_controller::update_category_attribute)
+                .delete(attribute_controller::delete_category_attribute),
+        )
+}
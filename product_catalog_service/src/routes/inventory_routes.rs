@@ -0,0 +1,30 @@
+use axum::{
+    Router,
+    routing::{get, post, put},
+};
+use std::sync::Arc;
+
+use crate::{controllers::inventory_controller, models::app_state::AppState};
+
+pub fn inventory_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/adjustments", post(inventory_controller::adjust_inventory))
+        .route("/availability", get(inventory_controller::get_availability))
+        .route("/low-stock", get(inventory_controller::get_low_stock))
+        .route("/audit", get(inventory_controller::get_inventory_audit))
+        .route("/rebuild", post(inventory_controller::rebuild_inventory))
+        .route("/{sku}/ledger", get(inventory_controller::get_sku_ledger))
+        .route(
+            "/{sku}/warehouses/{code}/threshold",
+            put(inventory_controller::set_low_stock_threshold),
+        )
+}
+
+pub fn warehouse_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/",
+            get(inventory_controller::get_warehouses).post(inventory_controller::create_warehouse),
+        )
+        .route("/{code}", get(inventory_controller::get_warehouse))
+}
@@ -0,0 +1,31 @@
+use axum::{
+    Router,
+    routing::{get, put},
+};
+use std::sync::Arc;
+
+use crate::{controllers::price_list_controller, models::app_state::AppState};
+
+pub fn price_list_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/",
+            get(price_list_controller::get_price_lists)
+                .post(price_list_controller::create_price_list),
+        )
+        .route(
+            "/{id}",
+            get(price_list_controller::get_price_list)
+                .put(price_list_controller::update_price_list)
+                .delete(price_list_controller::delete_price_list),
+        )
+        .route(
+            "/{id}/prices",
+            get(price_list_controller::get_price_list_prices),
+        )
+        .route(
+            "/{id}/prices/{product_id}",
+            put(price_list_controller::set_price_list_price)
+                .delete(price_list_controller::delete_price_list_price),
+        )
+}
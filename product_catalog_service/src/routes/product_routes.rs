@@ -1,10 +1,19 @@
 use axum::{
     Router,
-    routing::{get, post},
+    extract::DefaultBodyLimit,
+    routing::{delete, get, post, put},
 };
 use std::sync::Arc;
 
-use crate::{controllers::product_controller, models::app_state::AppState};
+use crate::{
+    controllers::{
+        category_controller, price_history_controller, product_bundle_controller,
+        product_controller, product_image_controller, product_import_controller,
+        product_link_controller, product_review_controller, product_translation_controller,
+        product_variant_controller, suggestion_controller,
+    },
+    models::app_state::AppState,
+};
 
 pub fn product_routes() -> Router<Arc<AppState>> {
     Router::new()
@@ -12,10 +21,107 @@ pub fn product_routes() -> Router<Arc<AppState>> {
             "/",
             post(product_controller::create_product).get(product_controller::get_products),
         )
+        .route("/suggest", get(suggestion_controller::suggest_products))
+        .route(
+            "/by-slug/{slug}",
+            get(product_controller::get_product_by_slug),
+        )
+        .route("/export", get(product_import_controller::export_products))
+        .route("/imports", post(product_import_controller::import_products))
+        .route(
+            "/imports/{job_id}",
+            get(product_import_controller::get_product_import),
+        )
         .route(
             "/{id}",
             get(product_controller::get_product)
                 .put(product_controller::update_product)
                 .delete(product_controller::delete_product),
         )
+        .route("/{id}/restore", post(product_controller::restore_product))
+        .route(
+            "/{id}/categories",
+            get(category_controller::get_product_categories)
+                .put(category_controller::set_product_categories),
+        )
+        .route(
+            "/{id}/images",
+            get(product_image_controller::get_product_images)
+                .post(product_image_controller::upload_product_image)
+                // The upload caps every form field it reads itself.
+                .layer(DefaultBodyLimit::disable()),
+        )
+        .route(
+            "/{id}/images/order",
+            put(product_image_controller::reorder_product_images),
+        )
+        .route(
+            "/{id}/images/{image_id}",
+            delete(product_image_controller::delete_product_image),
+        )
+        .route(
+            "/{id}/price-history",
+            get(price_history_controller::get_price_history),
+        )
+        .route(
+            "/{id}/scheduled-prices",
+            get(price_history_controller::get_scheduled_prices)
+                .post(price_history_controller::create_scheduled_price),
+        )
+        .route(
+            "/{id}/scheduled-prices/{change_id}",
+            delete(price_history_controller::cancel_scheduled_price),
+        )
+        .route(
+            "/{id}/reviews",
+            get(product_review_controller::get_product_reviews)
+                .post(product_review_controller::create_product_review),
+        )
+        .route(
+            "/{id}/translations",
+            get(product_translation_controller::get_product_translations),
+        )
+        .route(
+            "/{id}/translations/{locale}",
+            put(product_translation_controller::set_product_translation)
+                .delete(product_translation_controller::delete_product_translation),
+        )
+        .route(
+            "/{id}/bundle",
+            get(product_bundle_controller::get_product_bundle)
+                .put(product_bundle_controller::set_product_bundle)
+                .delete(product_bundle_controller::delete_product_bundle),
+        )
+        .route(
+            "/{id}/links",
+            get(product_link_controller::get_product_links),
+        )
+        .route(
+            "/{id}/links/{link_type}",
+            put(product_link_controller::set_product_links),
+        )
+        .route(
+            "/{id}/links/{link_type}/{linked_product_id}",
+            delete(product_link_controller::delete_product_link),
+        )
+        .route(
+            "/{id}/related",
+            get(product_controller::get_related_products),
+        )
+        .route(
+            "/{id}/options",
+            get(product_variant_controller::get_product_options)
+                .put(product_variant_controller::set_product_options),
+        )
+        .route(
+            "/{id}/variants",
+            get(product_variant_controller::get_product_variants)
+                .post(product_variant_controller::create_product_variant),
+        )
+        .route(
+            "/{id}/variants/{variant_id}",
+            get(product_variant_controller::get_product_variant)
+                .put(product_variant_controller::update_product_variant)
+                .delete(product_variant_controller::delete_product_variant),
+        )
 }
@@ -0,0 +1,21 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+use std::sync::Arc;
+
+use crate::{controllers::reservation_controller, models::app_state::AppState};
+
+pub fn reservation_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", post(reservation_controller::create_reservation))
+        .route("/{id}", get(reservation_controller::get_reservation))
+        .route(
+            "/{id}/confirm",
+            post(reservation_controller::confirm_reservation),
+        )
+        .route(
+            "/{id}/release",
+            post(reservation_controller::release_reservation),
+        )
+}
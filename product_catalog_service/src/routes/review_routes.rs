@@ -0,0 +1,21 @@
+use axum::{
+    Router,
+    routing::{get, put},
+};
+use std::sync::Arc;
+
+use crate::{controllers::product_review_controller, models::app_state::AppState};
+
+pub fn review_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(product_review_controller::get_reviews))
+        .route(
+            "/{id}",
+            get(product_review_controller::get_review)
+                .delete(product_review_controller::delete_review),
+        )
+        .route(
+            "/{id}/moderation",
+            put(product_review_controller::moderate_review),
+        )
+}
@@ -1,31 +1,51 @@
+use std::collections::BTreeMap;
+
 use bigdecimal::{BigDecimal, FromPrimitive};
-use sqlx::PgPool;
 
 use crate::dtos::create_product_dto::CreateProductDto;
+use crate::models::product::ProductStatus;
 use crate::repos::product_repo::ProductRepo;
 use crate::repos::repository_traits::Repository;
 
-pub async fn seeding_products_data(pool: &PgPool) -> Result<(), Box<dyn std::error::Error>> {
-    let product_repo = ProductRepo { pool: pool.clone() };
-
+pub async fn seeding_products_data(
+    product_repo: &ProductRepo,
+) -> Result<(), Box<dyn std::error::Error>> {
     let products_to_seed = vec![
         CreateProductDto {
+            external_id: None,
             name: "Laptop".to_string(),
             description: Some("A powerful laptop".to_string()),
             price: BigDecimal::from_f64(1200.00).unwrap(),
+            currency: None,
             image_url: Some("https://example.com/laptop.jpg".to_string()),
+            attributes: BTreeMap::new(),
+            category_ids: Vec::new(),
+            status: Some(ProductStatus::Active),
+            published_at: None,
         },
         CreateProductDto {
+            external_id: None,
             name: "Mouse".to_string(),
             description: Some("A wireless mouse".to_string()),
             price: BigDecimal::from_f64(25.00).unwrap(),
+            currency: None,
             image_url: Some("https://example.com/mouse.jpg".to_string()),
+            attributes: BTreeMap::new(),
+            category_ids: Vec::new(),
+            status: Some(ProductStatus::Active),
+            published_at: None,
         },
         CreateProductDto {
+            external_id: None,
             name: "Keyboard".to_string(),
             description: Some("A mechanical keyboard".to_string()),
             price: BigDecimal::from_f64(75.00).unwrap(),
+            currency: None,
             image_url: Some("https://example.com/keyboard.jpg".to_string()),
+            attributes: BTreeMap::new(),
+            category_ids: Vec::new(),
+            status: Some(ProductStatus::Active),
+            published_at: None,
         },
     ];
 
@@ -0,0 +1,2 @@
+pub mod local_storage;
+pub mod s3_storage;
@@ -0,0 +1,45 @@
+use std::{io::ErrorKind, path::PathBuf};
+
+use async_trait::async_trait;
+
+use crate::{models::app_error::AppError, traits::object_storage::ObjectStorage};
+
+/// Keeps objects as files below `root`; the service serves them itself
+/// under `public_url`.
+pub struct LocalStorage {
+    pub root: PathBuf,
+    pub public_url: String,
+}
+
+#[async_trait]
+impl ObjectStorage for LocalStorage {
+    async fn put(&self, key: &str, _content_type: &str, bytes: Vec<u8>) -> Result<(), AppError> {
+        let path = self.root.join(key);
+
+        let written = async {
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::write(&path, bytes).await
+        };
+
+        written.await.map_err(|err| {
+            tracing::error!(error = %err, key, "failed to store object");
+            AppError::InternalServerError
+        })
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        match tokio::fs::remove_file(self.root.join(key)).await {
+            Err(err) if err.kind() != ErrorKind::NotFound => {
+                tracing::error!(error = %err, key, "failed to delete object");
+                Err(AppError::InternalServerError)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_url.trim_end_matches('/'), key)
+    }
+}
@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use aws_sdk_s3::{
+    Client,
+    config::{BehaviorVersion, Builder, Credentials, Region},
+    error::DisplayErrorContext,
+    primitives::ByteStream,
+};
+
+use crate::{models::app_error::AppError, traits::object_storage::ObjectStorage};
+
+/// Keeps objects in a bucket of an S3 compatible service such as AWS S3 or
+/// MinIO. Objects are expected to be readable under `public_url`, e.g. a
+/// public bucket or a CDN in front of it.
+pub struct S3Storage {
+    client: Client,
+    bucket: String,
+    public_url: String,
+}
+
+impl S3Storage {
+    pub fn new(
+        endpoint: &str,
+        region: &str,
+        bucket: &str,
+        access_key: &str,
+        secret_key: &str,
+        public_url: &str,
+    ) -> Self {
+        let config = Builder::new()
+            .behavior_version(BehaviorVersion::latest())
+            .endpoint_url(endpoint)
+            .region(Region::new(region.to_string()))
+            .credentials_provider(Credentials::new(
+                access_key, secret_key, None, None, "config",
+            ))
+            // Path style addressing works with every S3 compatible service.
+            .force_path_style(true)
+            .build();
+
+        S3Storage {
+            client: Client::from_conf(config),
+            bucket: bucket.to_string(),
+            public_url: public_url.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectStorage for S3Storage {
+    async fn put(&self, key: &str, content_type: &str, bytes: Vec<u8>) -> Result<(), AppError> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .body(ByteStream::from(bytes))
+            .send()
+            .await
+            .map_err(|err| {
+                tracing::error!(error = %DisplayErrorContext(&err), key, "failed to store object");
+                AppError::InternalServerError
+            })?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|err| {
+                tracing::error!(error = %DisplayErrorContext(&err), key, "failed to delete object");
+                AppError::InternalServerError
+            })?;
+
+        Ok(())
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_url.trim_end_matches('/'), key)
+    }
+}
@@ -1 +1,4 @@
+pub mod event_sink;
+pub mod object_storage;
+pub mod to_cursor;
 pub mod to_dto;
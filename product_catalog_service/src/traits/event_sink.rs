@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+
+use crate::models::{app_error::AppError, product_event::ProductEvent};
+
+/// Where the outbox relay publishes product change events. An event counts
+/// as delivered once `publish` succeeds; until then it is offered again, so
+/// sinks may see an event more than once.
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    async fn publish(&self, event: &ProductEvent) -> Result<(), AppError>;
+}
@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+
+use crate::models::app_error::AppError;
+
+/// Where uploaded files such as product images are kept. Keys are relative
+/// paths like `products/<id>/images/<id>/original.png`.
+#[async_trait]
+pub trait ObjectStorage: Send + Sync {
+    async fn put(&self, key: &str, content_type: &str, bytes: Vec<u8>) -> Result<(), AppError>;
+
+    /// Deleting a key that does not exist succeeds.
+    async fn delete(&self, key: &str) -> Result<(), AppError>;
+
+    /// URL clients download the object from.
+    fn public_url(&self, key: &str) -> String;
+}
@@ -0,0 +1,5 @@
+use crate::models::cursor::Cursor;
+
+pub trait ToCursor {
+    fn to_cursor(&self, order_by: &str) -> Cursor;
+}
@@ -1 +1,6 @@
-
+pub mod etag;
+pub mod image_processing;
+pub mod locale;
+pub mod request_id;
+pub mod slug;
+pub mod validation;
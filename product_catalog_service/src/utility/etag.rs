@@ -0,0 +1,120 @@
+use axum::http::{HeaderMap, header};
+
+use crate::models::app_error::AppError;
+
+/// Builds the strong entity tag for a row version.
+pub fn etag(version: i64) -> String {
+    format!("\"{}\"", version)
+}
+
+/// Reads the row version a write is conditioned on from the `If-Match` header.
+///
+/// Returns `Ok(None)` for `If-Match: *` and, unless `require` is set, when the
+/// header is missing. Only a single strong entity tag is accepted.
+pub fn if_match_version(headers: &HeaderMap, require: bool) -> Result<Option<i64>, AppError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return if require {
+            Err(AppError::PreconditionRequired)
+        } else {
+            Ok(None)
+        };
+    };
+
+    let value = value.to_str().map_err(|_| AppError::PreconditionFailed)?.trim();
+
+    if value == "*" {
+        return Ok(None);
+    }
+
+    value
+        .strip_prefix('"')
+        .and_then(|tag| tag.strip_suffix('"'))
+        .and_then(|tag| tag.parse::<i64>().ok())
+        .map(Some)
+        .ok_or(AppError::PreconditionFailed)
+}
+
+/// Whether the `If-None-Match` header matches the current row version, using
+/// weak comparison as required for conditional reads.
+pub fn if_none_match(headers: &HeaderMap, version: i64) -> bool {
+    let current = etag(version);
+
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == current)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    fn headers(name: header::HeaderName, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn etag_quotes_the_version() {
+        assert_eq!(etag(7), "\"7\"");
+    }
+
+    #[test]
+    fn if_match_reads_a_strong_tag() {
+        let headers = headers(header::IF_MATCH, " \"42\" ");
+
+        assert_eq!(if_match_version(&headers, true).unwrap(), Some(42));
+    }
+
+    #[test]
+    fn if_match_wildcard_matches_any_version() {
+        let headers = headers(header::IF_MATCH, "*");
+
+        assert_eq!(if_match_version(&headers, true).unwrap(), None);
+    }
+
+    #[test]
+    fn if_match_missing_is_only_an_error_when_required() {
+        let headers = HeaderMap::new();
+
+        assert_eq!(if_match_version(&headers, false).unwrap(), None);
+        assert!(matches!(
+            if_match_version(&headers, true),
+            Err(AppError::PreconditionRequired)
+        ));
+    }
+
+    #[test]
+    fn if_match_rejects_weak_lists_and_unquoted_tags() {
+        for value in ["W/\"3\"", "\"3\", \"4\"", "3", "\"three\""] {
+            let headers = headers(header::IF_MATCH, value);
+
+            assert!(
+                matches!(
+                    if_match_version(&headers, false),
+                    Err(AppError::PreconditionFailed)
+                ),
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn if_none_match_uses_weak_comparison() {
+        assert!(if_none_match(&headers(header::IF_NONE_MATCH, "\"5\""), 5));
+        assert!(if_none_match(&headers(header::IF_NONE_MATCH, "W/\"5\""), 5));
+        assert!(if_none_match(
+            &headers(header::IF_NONE_MATCH, "\"4\", \"5\""),
+            5
+        ));
+        assert!(if_none_match(&headers(header::IF_NONE_MATCH, "*"), 5));
+        assert!(!if_none_match(&headers(header::IF_NONE_MATCH, "\"4\""), 5));
+        assert!(!if_none_match(&HeaderMap::new(), 5));
+    }
+}
@@ -0,0 +1,224 @@
+use std::io::Cursor;
+
+use image::{DynamicImage, ImageFormat, ImageReader, Limits, codecs::jpeg::JpegEncoder};
+
+use crate::models::app_error::AppError;
+
+/// Formats accepted for upload, recognised by their content rather than by
+/// the declared content type.
+const ACCEPTED_FORMATS: [ImageFormat; 3] = [ImageFormat::Jpeg, ImageFormat::Png, ImageFormat::WebP];
+
+/// Largest width or height decoded, guarding against decompression bombs.
+const MAX_DIMENSION: u32 = 10_000;
+
+const THUMBNAIL_JPEG_QUALITY: u8 = 85;
+
+pub struct ProcessedImage {
+    pub content_type: &'static str,
+    pub extension: &'static str,
+    pub width: u32,
+    pub height: u32,
+    pub thumbnails: Vec<Thumbnail>,
+}
+
+pub struct Thumbnail {
+    /// Bounding box the thumbnail was fitted into.
+    pub size: u32,
+    pub width: u32,
+    pub height: u32,
+    pub content_type: &'static str,
+    pub extension: &'static str,
+    pub bytes: Vec<u8>,
+}
+
+/// Checks an uploaded image and renders a thumbnail fitting each of `sizes`.
+/// Images are never scaled up. Thumbnails are JPEG, or PNG when the image
+/// has transparency. Decoding is CPU bound; call from a blocking task.
+pub fn process_image(
+    bytes: &[u8],
+    declared_content_type: Option<&str>,
+    sizes: &[u32],
+) -> Result<ProcessedImage, AppError> {
+    let format = image::guess_format(bytes)
+        .ok()
+        .filter(|format| ACCEPTED_FORMATS.contains(format))
+        .ok_or_else(|| {
+            AppError::UnsupportedMediaType("Images must be JPEG, PNG or WebP".to_string())
+        })?;
+
+    let content_type = format.to_mime_type();
+    if let Some(declared) = declared_content_type
+        && declared != content_type
+        && declared != "application/octet-stream"
+    {
+        return Err(AppError::UnsupportedMediaType(format!(
+            "File is {} but was sent as {}",
+            content_type, declared
+        )));
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DIMENSION);
+    limits.max_image_height = Some(MAX_DIMENSION);
+
+    let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
+    reader.limits(limits);
+    let image = reader
+        .decode()
+        .map_err(|err| AppError::Invalid(format!("Image could not be decoded: {}", err)))?;
+
+    let thumbnails = sizes
+        .iter()
+        .map(|&size| render_thumbnail(&image, size))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ProcessedImage {
+        content_type,
+        extension: format.extensions_str()[0],
+        width: image.width(),
+        height: image.height(),
+        thumbnails,
+    })
+}
+
+fn render_thumbnail(image: &DynamicImage, size: u32) -> Result<Thumbnail, AppError> {
+    let resized = if image.width() <= size && image.height() <= size {
+        image.clone()
+    } else {
+        image.thumbnail(size, size)
+    };
+
+    let mut bytes = Vec::new();
+    let (content_type, extension) = if resized.color().has_alpha() {
+        resized
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .map_err(encode_error)?;
+        ("image/png", "png")
+    } else {
+        JpegEncoder::new_with_quality(&mut bytes, THUMBNAIL_JPEG_QUALITY)
+            .encode_image(&resized.to_rgb8())
+            .map_err(encode_error)?;
+        ("image/jpeg", "jpg")
+    };
+
+    Ok(Thumbnail {
+        size,
+        width: resized.width(),
+        height: resized.height(),
+        content_type,
+        extension,
+        bytes,
+    })
+}
+
+fn encode_error(err: image::ImageError) -> AppError {
+    tracing::error!(error = %err, "failed to encode thumbnail");
+    AppError::InternalServerError
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{RgbImage, RgbaImage};
+
+    use super::*;
+
+    fn encode(image: DynamicImage, format: ImageFormat) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut bytes), format)
+            .unwrap();
+        bytes
+    }
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        encode(
+            DynamicImage::ImageRgb8(RgbImage::new(width, height)),
+            ImageFormat::Png,
+        )
+    }
+
+    #[test]
+    fn thumbnails_fit_their_bounding_box_without_upscaling() {
+        let image = process_image(&png(400, 200), Some("image/png"), &[100, 800]).unwrap();
+
+        assert_eq!(image.content_type, "image/png");
+        assert_eq!(image.extension, "png");
+        assert_eq!((image.width, image.height), (400, 200));
+
+        let sizes: Vec<(u32, u32, u32)> = image
+            .thumbnails
+            .iter()
+            .map(|thumbnail| (thumbnail.size, thumbnail.width, thumbnail.height))
+            .collect();
+        assert_eq!(sizes, [(100, 100, 50), (800, 400, 200)]);
+    }
+
+    #[test]
+    fn opaque_thumbnails_are_jpeg() {
+        let image = process_image(&png(50, 50), None, &[10]).unwrap();
+        let thumbnail = &image.thumbnails[0];
+
+        assert_eq!(
+            (thumbnail.content_type, thumbnail.extension),
+            ("image/jpeg", "jpg")
+        );
+        assert_eq!(
+            image::guess_format(&thumbnail.bytes).unwrap(),
+            ImageFormat::Jpeg
+        );
+    }
+
+    #[test]
+    fn transparent_thumbnails_are_png() {
+        let bytes = encode(
+            DynamicImage::ImageRgba8(RgbaImage::new(50, 50)),
+            ImageFormat::Png,
+        );
+
+        let image = process_image(&bytes, None, &[10]).unwrap();
+        let thumbnail = &image.thumbnails[0];
+
+        assert_eq!(
+            (thumbnail.content_type, thumbnail.extension),
+            ("image/png", "png")
+        );
+        assert_eq!(
+            image::guess_format(&thumbnail.bytes).unwrap(),
+            ImageFormat::Png
+        );
+    }
+
+    #[test]
+    fn content_type_is_checked_against_the_file() {
+        assert!(process_image(&png(10, 10), Some("application/octet-stream"), &[]).is_ok());
+        assert!(matches!(
+            process_image(&png(10, 10), Some("image/jpeg"), &[]),
+            Err(AppError::UnsupportedMediaType(_))
+        ));
+    }
+
+    #[test]
+    fn unsupported_and_corrupt_files_are_rejected() {
+        let gif = b"GIF89a\x01\x00\x01\x00\x00\x00\x00;";
+        assert!(matches!(
+            process_image(gif, None, &[]),
+            Err(AppError::UnsupportedMediaType(_))
+        ));
+
+        let truncated = &png(10, 10)[..40];
+        assert!(matches!(
+            process_image(truncated, None, &[]),
+            Err(AppError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn oversized_images_are_not_decoded() {
+        let bytes = png(MAX_DIMENSION + 1, 1);
+
+        assert!(matches!(
+            process_image(&bytes, None, &[]),
+            Err(AppError::Invalid(_))
+        ));
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use axum::http::{HeaderMap, header};
+
+/// Normalizes a language tag to the lowercase form translations are stored
+/// under, e.g. `de-AT` to `de-at`. Returns `None` for anything else.
+pub fn normalize_locale(tag: &str) -> Option<String> {
+    let tag = tag.trim().to_ascii_lowercase();
+    let mut subtags = tag.split('-');
+
+    let language = subtags.next()?;
+    if !(2..=3).contains(&language.len()) || !language.bytes().all(|b| b.is_ascii_lowercase()) {
+        return None;
+    }
+    for subtag in subtags {
+        if !(1..=8).contains(&subtag.len()) || !subtag.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return None;
+        }
+    }
+
+    Some(tag)
+}
+
+/// Locale of the product columns and the fallbacks tried for other locales.
+#[derive(Debug, Clone)]
+pub struct LocaleSettings {
+    pub default_locale: String,
+    /// Locales tried in order when a product has no translation in the key's
+    /// locale, e.g. `fr-ch` to `["fr", "de"]`. Locales without an entry fall
+    /// back to their language, `de-at` to `de`.
+    pub fallbacks: HashMap<String, Vec<String>>,
+}
+
+impl LocaleSettings {
+    pub fn new(default_locale: &str, fallbacks: &HashMap<String, Vec<String>>) -> Self {
+        let normalize = |tag: &str| normalize_locale(tag).unwrap_or_else(|| tag.to_lowercase());
+
+        LocaleSettings {
+            default_locale: normalize(default_locale),
+            fallbacks: fallbacks
+                .iter()
+                .map(|(locale, chain)| {
+                    (
+                        normalize(locale),
+                        chain.iter().map(|locale| normalize(locale)).collect(),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Locales to look for translations in, most preferred first, from the
+    /// `Accept-Language` header and the fallback chains. The list ends before
+    /// the default locale since the product columns answer for it.
+    pub fn negotiate(&self, headers: &HeaderMap) -> Vec<String> {
+        let Some(accept_language) = headers
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+        else {
+            return Vec::new();
+        };
+
+        // `*` and malformed ranges are skipped, as are those with `q=0`.
+        let mut ranges: Vec<(String, f32)> = accept_language
+            .split(',')
+            .filter_map(|range| {
+                let mut parts = range.split(';');
+                let locale = normalize_locale(parts.next()?)?;
+                let quality = match parts.find_map(|part| part.trim().strip_prefix("q=")) {
+                    Some(quality) => quality.trim().parse::<f32>().ok()?,
+                    None => 1.0,
+                };
+                (quality > 0.0).then_some((locale, quality))
+            })
+            .collect();
+        ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let mut locales: Vec<String> = Vec::new();
+        for (locale, _) in ranges {
+            let fallbacks = match self.fallbacks.get(&locale) {
+                Some(chain) => chain.clone(),
+                None => locale
+                    .split_once('-')
+                    .map(|(language, _)| vec![language.to_string()])
+                    .unwrap_or_default(),
+            };
+
+            for locale in std::iter::once(locale).chain(fallbacks) {
+                if locale == self.default_locale {
+                    return locales;
+                }
+                if !locales.contains(&locale) {
+                    locales.push(locale);
+                }
+            }
+        }
+
+        locales
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    fn settings() -> LocaleSettings {
+        let fallbacks = HashMap::from([(
+            "fr-CH".to_string(),
+            vec!["fr".to_string(), "DE".to_string()],
+        )]);
+        LocaleSettings::new("EN", &fallbacks)
+    }
+
+    fn accept_language(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::ACCEPT_LANGUAGE,
+            HeaderValue::from_str(value).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn normalize_locale_lowercases_valid_tags() {
+        assert_eq!(normalize_locale(" de-AT ").as_deref(), Some("de-at"));
+        assert_eq!(
+            normalize_locale("zh-Hant-TW").as_deref(),
+            Some("zh-hant-tw")
+        );
+    }
+
+    #[test]
+    fn normalize_locale_rejects_malformed_tags() {
+        for tag in ["", "*", "d", "deutsch", "de-", "de_AT", "1a"] {
+            assert_eq!(normalize_locale(tag), None, "{tag}");
+        }
+    }
+
+    #[test]
+    fn negotiate_orders_by_quality_and_adds_language_fallbacks() {
+        let locales = settings().negotiate(&accept_language("it;q=0.5, de-AT, es;q=0.8"));
+
+        assert_eq!(locales, ["de-at", "de", "es", "it"]);
+    }
+
+    #[test]
+    fn negotiate_uses_configured_fallback_chains() {
+        let locales = settings().negotiate(&accept_language("fr-ch, de"));
+
+        assert_eq!(locales, ["fr-ch", "fr", "de"]);
+    }
+
+    #[test]
+    fn negotiate_stops_at_the_default_locale() {
+        let locales = settings().negotiate(&accept_language("nl, en-GB, fr"));
+
+        assert_eq!(locales, ["nl", "en-gb"]);
+    }
+
+    #[test]
+    fn negotiate_skips_wildcards_malformed_and_refused_ranges() {
+        let locales = settings().negotiate(&accept_language("*, x_y, fr;q=0, it;q=abc, pt"));
+
+        assert_eq!(locales, ["pt"]);
+        assert!(settings().negotiate(&HeaderMap::new()).is_empty());
+    }
+}
@@ -0,0 +1,108 @@
+/// Longest slug made from a name, before a collision number is appended.
+const MAX_SLUG_LENGTH: usize = 80;
+
+/// Slug of names without a single letter or digit.
+const FALLBACK_SLUG: &str = "product";
+
+/// Turns a product name into a URL path segment: lowercase letters and digits
+/// with single hyphens between words, e.g. `Men's T-Shirt (XL)` becomes
+/// `mens-t-shirt-xl`. Letters outside ASCII are kept.
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    let mut length = 0;
+
+    for c in name
+        .chars()
+        .filter(|c| !matches!(c, '\'' | '’'))
+        .flat_map(char::to_lowercase)
+    {
+        if length == MAX_SLUG_LENGTH {
+            break;
+        }
+        if c.is_alphanumeric() {
+            slug.push(c);
+            length += 1;
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+            length += 1;
+        }
+    }
+
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        FALLBACK_SLUG.to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// The slug without collision numbers, e.g. `shoe` for `shoe-2-3`. A slug
+/// numbered from a name has the stem of that name's slug.
+pub fn slug_stem(slug: &str) -> &str {
+    let mut stem = slug;
+    while let Some((rest, number)) = stem.rsplit_once('-')
+        && !number.is_empty()
+        && number.bytes().all(|b| b.is_ascii_digit())
+    {
+        stem = rest;
+    }
+    stem
+}
+
+/// Percent-encodes a slug for use in a URL; only letters outside ASCII need it.
+pub fn encode_slug(slug: &str) -> String {
+    let mut encoded = String::with_capacity(slug.len());
+    for byte in slug.bytes() {
+        if byte.is_ascii() {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_joins_words_with_single_hyphens() {
+        assert_eq!(slugify("Men's T-Shirt (XL)"), "mens-t-shirt-xl");
+        assert_eq!(slugify("  Red -- Running   Shoes!  "), "red-running-shoes");
+    }
+
+    #[test]
+    fn slugify_keeps_letters_outside_ascii() {
+        assert_eq!(slugify("Größe Über"), "größe-über");
+    }
+
+    #[test]
+    fn slugify_falls_back_without_letters_or_digits() {
+        assert_eq!(slugify("!!! ---"), FALLBACK_SLUG);
+        assert_eq!(slugify(""), FALLBACK_SLUG);
+    }
+
+    #[test]
+    fn slugify_caps_the_length() {
+        let slug = slugify(&"ab ".repeat(100));
+
+        assert!(slug.chars().count() <= MAX_SLUG_LENGTH);
+        assert!(!slug.ends_with('-'));
+    }
+
+    #[test]
+    fn slug_stem_strips_collision_numbers() {
+        assert_eq!(slug_stem("shoe"), "shoe");
+        assert_eq!(slug_stem("shoe-2"), "shoe");
+        assert_eq!(slug_stem("shoe-2-3"), "shoe");
+        assert_eq!(slug_stem("size-42-shoe"), "size-42-shoe");
+        assert_eq!(slug_stem("2024"), "2024");
+    }
+
+    #[test]
+    fn encode_slug_escapes_only_non_ascii() {
+        assert_eq!(encode_slug("red-shoe"), "red-shoe");
+        assert_eq!(encode_slug("größe"), "gr%C3%B6%C3%9Fe");
+    }
+}
@@ -0,0 +1,47 @@
+use bigdecimal::BigDecimal;
+use validator::ValidationError;
+
+/// Decimal places a price may have; no ISO 4217 currency uses more.
+pub const MAX_PRICE_SCALE: i64 = 4;
+
+/// Rejects strings that are empty or only whitespace.
+pub fn validate_not_blank(value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(ValidationError::new("blank").with_message("must not be blank".into()));
+    }
+
+    Ok(())
+}
+
+/// Prices have to be positive and use at most [`MAX_PRICE_SCALE`] decimal places.
+pub fn validate_price(price: &BigDecimal) -> Result<(), ValidationError> {
+    if price <= &BigDecimal::from(0) {
+        return Err(ValidationError::new("positive").with_message("must be positive".into()));
+    }
+
+    if price.normalized().fractional_digit_count() > MAX_PRICE_SCALE {
+        let mut error = ValidationError::new("scale")
+            .with_message(format!("must have at most {} decimal places", MAX_PRICE_SCALE).into());
+        error.add_param("max_scale".into(), &MAX_PRICE_SCALE);
+        return Err(error);
+    }
+
+    Ok(())
+}
+
+/// Discounts are a percentage from 0 up to, but not including, 100 with at
+/// most two decimal places.
+pub fn validate_discount_percent(discount_percent: &BigDecimal) -> Result<(), ValidationError> {
+    if discount_percent < &BigDecimal::from(0) || discount_percent >= &BigDecimal::from(100) {
+        return Err(ValidationError::new("range")
+            .with_message("must be at least 0 and less than 100".into()));
+    }
+
+    if discount_percent.normalized().fractional_digit_count() > 2 {
+        return Err(
+            ValidationError::new("scale").with_message("must have at most 2 decimal places".into())
+        );
+    }
+
+    Ok(())
+}
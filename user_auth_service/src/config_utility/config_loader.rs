@@ -8,4 +8,6 @@ pub struct ConfigLoader {
     pub server_password: String,
     pub server_db_name: String,
     pub server_db_schema: String,
+    #[serde(default)]
+    pub require_if_match: bool,
 }
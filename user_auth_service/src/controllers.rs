@@ -1 +1,2 @@
+pub mod user_bulk_controller;
 pub mod user_controller;
@@ -0,0 +1,346 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    sync::Arc,
+};
+
+use axum::{
+    Json,
+    body::{Body, Bytes},
+    extract::{Query, State},
+    http::{HeaderMap, header},
+    response::{IntoResponse, Response},
+};
+use tracing::instrument;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    dtos::{
+        create_user_dto::CreateUserDto,
+        user_dto::UserDto,
+        user_import_report_dto::{UserImportReportDto, UserImportRowErrorDto},
+    },
+    models::{
+        app_error::{AppError, CustomResult},
+        app_state::AppState,
+        bulk_options::{BulkFormat, ExportOptions, ImportOptions},
+        cursor::{Cursor, PageCursor},
+        user::User,
+    },
+    traits::{to_cursor::ToCursor, to_dto::ToDto},
+};
+
+const IMPORT_BATCH_SIZE: usize = 500;
+const EXPORT_BATCH_SIZE: u32 = 1000;
+
+pub struct UserBulkController;
+
+impl UserBulkController {
+    fn format_from_content_type(headers: &HeaderMap) -> Option<BulkFormat> {
+        let content_type = headers.get(header::CONTENT_TYPE)?.to_str().ok()?;
+
+        match content_type.split(';').next()?.trim() {
+            "text/csv" => Some(BulkFormat::Csv),
+            "application/x-ndjson" | "application/ndjson" => Some(BulkFormat::Ndjson),
+            _ => None,
+        }
+    }
+
+    /// Rows are numbered by their line in the file, so blank lines skipped
+    /// by the parsers do not shift the numbers reported back.
+    fn parse_csv(body: &[u8]) -> Vec<(usize, Result<CreateUserDto, String>)> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(body);
+
+        let headers = match reader.headers() {
+            Ok(headers) => headers.clone(),
+            Err(err) => return vec![(1, Err(err.to_string()))],
+        };
+
+        reader
+            .records()
+            .map(|record| {
+                // Positions point at the start of what was read for a record,
+                // which includes the blank lines the reader skipped before it.
+                let line = |position: Option<&csv::Position>| {
+                    let Some(position) = position else {
+                        return 0;
+                    };
+                    let mut line = position.line() as usize;
+                    let mut rest = body.get(position.byte() as usize..).unwrap_or_default();
+                    while let Some(after) = rest
+                        .strip_prefix(b"\r\n")
+                        .or_else(|| rest.strip_prefix(b"\n"))
+                    {
+                        line += 1;
+                        rest = after;
+                    }
+                    line
+                };
+
+                match record {
+                    Ok(record) => (
+                        line(record.position()),
+                        record
+                            .deserialize(Some(&headers))
+                            .map_err(|err| err.to_string()),
+                    ),
+                    Err(err) => (line(err.position()), Err(err.to_string())),
+                }
+            })
+            .collect()
+    }
+
+    fn parse_ndjson(body: &[u8]) -> Vec<(usize, Result<CreateUserDto, String>)> {
+        body.split(|byte| *byte == b'\n')
+            .enumerate()
+            .filter(|(_, line)| !line.trim_ascii().is_empty())
+            .map(|(index, line)| {
+                (
+                    index + 1,
+                    serde_json::from_slice(line).map_err(|err| err.to_string()),
+                )
+            })
+            .collect()
+    }
+
+    fn encode_users(
+        format: BulkFormat,
+        users: &[User],
+        with_header: bool,
+    ) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        let users_dto: Vec<UserDto> = users.iter().map(|u| u.to_dto()).collect();
+
+        match format {
+            BulkFormat::Csv => {
+                let mut writer = csv::WriterBuilder::new()
+                    .has_headers(with_header)
+                    .from_writer(Vec::new());
+
+                for user_dto in users_dto {
+                    writer.serialize(user_dto)?;
+                }
+
+                Ok(writer.into_inner()?)
+            }
+            BulkFormat::Ndjson => {
+                let mut buffer = Vec::new();
+
+                for user_dto in users_dto {
+                    serde_json::to_writer(&mut buffer, &user_dto)?;
+                    buffer.push(b'\n');
+                }
+
+                Ok(buffer)
+            }
+        }
+    }
+
+    #[instrument(skip(app_state, headers, body))]
+    pub async fn import_users(
+        State(app_state): State<Arc<AppState>>,
+        Query(options): Query<ImportOptions>,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> CustomResult<Json<UserImportReportDto>> {
+        let format = options
+            .format
+            .or_else(|| Self::format_from_content_type(&headers))
+            .ok_or(AppError::RequestPayloadNotValid(String::from(
+                "Upload format must be csv or ndjson",
+            )))?;
+        let dry_run = options.dry_run.unwrap_or(false);
+
+        let rows = match format {
+            BulkFormat::Csv => Self::parse_csv(&body),
+            BulkFormat::Ndjson => Self::parse_ndjson(&body),
+        };
+        let total_rows = rows.len();
+
+        let mut errors = Vec::new();
+        let mut seen_usernames = HashSet::new();
+        let mut seen_emails = HashSet::new();
+        let mut pending: Vec<(usize, User)> = Vec::new();
+
+        for (row_number, row) in rows {
+            let create_user_dto = match row.and_then(|dto| {
+                dto.validate()
+                    .map(|_| dto)
+                    .map_err(|err| err.to_string())
+            }) {
+                Ok(dto) => dto,
+                Err(err) => {
+                    errors.push(UserImportRowErrorDto {
+                        row: row_number,
+                        errors: err,
+                    });
+                    continue;
+                }
+            };
+
+            let new_username = seen_usernames.insert(create_user_dto.username.clone());
+            let new_email = seen_emails.insert(create_user_dto.email.clone());
+            if !(new_username && new_email) {
+                errors.push(UserImportRowErrorDto {
+                    row: row_number,
+                    errors: String::from("Username or email is repeated in the upload"),
+                });
+                continue;
+            }
+
+            pending.push((
+                row_number,
+                User {
+                    id: Uuid::new_v4(),
+                    username: create_user_dto.username,
+                    email: create_user_dto.email,
+                    password_hash: create_user_dto.password_hash,
+                    role: create_user_dto.role,
+                    is_active: true,
+                    created_at: Some(chrono::Utc::now()),
+                    updated_at: Some(chrono::Utc::now()),
+                    version: 1,
+                },
+            ));
+        }
+
+        let user_repo = &app_state.user_repo;
+        let mut imported = 0;
+
+        while !pending.is_empty() {
+            let batch: Vec<(usize, User)> = pending
+                .drain(..IMPORT_BATCH_SIZE.min(pending.len()))
+                .collect();
+            let row_numbers: HashMap<Uuid, usize> =
+                batch.iter().map(|(row, user)| (user.id, *row)).collect();
+            let users = batch.into_iter().map(|(_, user)| user).collect();
+
+            match user_repo.create_many(users, dry_run).await {
+                Ok(created_users) => {
+                    imported += created_users.len();
+
+                    let created_ids: HashSet<Uuid> = created_users.iter().map(|u| u.id).collect();
+                    errors.extend(
+                        row_numbers
+                            .into_iter()
+                            .filter(|(id, _)| !created_ids.contains(id))
+                            .map(|(_, row)| UserImportRowErrorDto {
+                                row,
+                                errors: String::from("Username or email already exists"),
+                            }),
+                    );
+                }
+                Err(err) => {
+                    tracing::error!("user import batch failed: {}", err);
+
+                    errors.extend(row_numbers.into_values().map(|row| UserImportRowErrorDto {
+                        row,
+                        errors: String::from("User could not be created"),
+                    }));
+                }
+            }
+        }
+
+        errors.sort_by_key(|err| err.row);
+
+        Ok(Json(UserImportReportDto {
+            dry_run,
+            total_rows,
+            imported,
+            failed: errors.len(),
+            errors,
+        }))
+    }
+
+    #[instrument(skip(app_state))]
+    pub async fn export_users(
+        State(app_state): State<Arc<AppState>>,
+        Query(options): Query<ExportOptions>,
+    ) -> Response {
+        let format = options.format.unwrap_or(BulkFormat::Ndjson);
+        let user_repo = app_state.user_repo.clone();
+
+        // Walk the table in id order with keyset pagination so memory stays
+        // flat no matter how many users are exported.
+        let stream = futures::stream::unfold(
+            Some((None::<Cursor>, true)),
+            move |state| {
+                let user_repo = user_repo.clone();
+
+                async move {
+                    let (cursor, is_first) = state?;
+                    let page_cursor = cursor.map(PageCursor::After);
+
+                    match user_repo
+                        .read_all(None, EXPORT_BATCH_SIZE, 0, "id", page_cursor.as_ref())
+                        .await
+                    {
+                        Ok(users) => {
+                            let next_state = if users.len() < EXPORT_BATCH_SIZE as usize {
+                                None
+                            } else {
+                                users.last().map(|u| (Some(u.to_cursor("id")), false))
+                            };
+
+                            Some((Self::encode_users(format, &users, is_first), next_state))
+                        }
+                        Err(err) => Some((Err(err), None)),
+                    }
+                }
+            },
+        );
+
+        let (content_type, file_name) = match format {
+            BulkFormat::Csv => ("text/csv", "users.csv"),
+            BulkFormat::Ndjson => ("application/x-ndjson", "users.ndjson"),
+        };
+
+        (
+            [
+                (header::CONTENT_TYPE, content_type.to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", file_name),
+                ),
+            ],
+            Body::from_stream(stream),
+        )
+            .into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row_numbers(rows: &[(usize, Result<CreateUserDto, String>)]) -> Vec<(usize, bool)> {
+        rows.iter().map(|(row, result)| (*row, result.is_ok())).collect()
+    }
+
+    #[test]
+    fn ndjson_rows_keep_their_line_numbers_past_blank_lines() {
+        let body = b"{\"username\":\"ann\",\"email\":\"ann@example.com\",\"role\":\"user\",\"password_hash\":\"x\"}\n\
+                     \n   \n\
+                     not json\n\
+                     {\"username\":\"bob\",\"email\":\"bob@example.com\",\"role\":\"user\",\"password_hash\":\"x\"}\n";
+
+        let rows = UserBulkController::parse_ndjson(body);
+
+        assert_eq!(row_numbers(&rows), [(1, true), (4, false), (5, true)]);
+    }
+
+    #[test]
+    fn csv_rows_are_numbered_by_file_line() {
+        let body = b"username,email,role,password_hash\n\
+                     ann,ann@example.com,user,x\n\
+                     \n\
+                     bob,bob@example.com\n\
+                     cid,cid@example.com,user,x\n";
+
+        let rows = UserBulkController::parse_csv(body);
+
+        assert_eq!(row_numbers(&rows), [(2, true), (4, false), (5, true)]);
+    }
+}
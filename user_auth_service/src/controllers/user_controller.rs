@@ -1,9 +1,12 @@
-use std::sync::Arc;
+use std::{error::Error, sync::Arc};
 
 use axum::{
     Json,
     extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
 };
+use chrono::DateTime;
 use uuid::Uuid;
 
 use tracing::instrument;
@@ -16,15 +19,20 @@ use crate::{
     models::{
         app_error::{AppError, CustomResult},
         app_state::AppState,
+        cursor::{Cursor, PageCursor},
         paginated_response::PaginatedResponse,
         pagination::Pagination,
-        user::User,
+        user::{NULL_TIMESTAMP_KEY, User},
         validated_json::ValidatedJson,
     },
-    repos::repository_traits::Repository,
-    traits::to_dto::ToDto,
+    repos::{repository_traits::Repository, user_repo::SORT_COLUMNS},
+    traits::{to_cursor::ToCursor, to_dto::ToDto},
+    utility::etag::{etag, if_match_version, if_none_match},
 };
 
+/// Most users returned per page, whatever `limit` asks for.
+const MAX_PAGE_LIMIT: u32 = 100;
+
 pub struct UserController;
 
 impl UserController {
@@ -36,34 +44,132 @@ impl UserController {
         let existing_user = match user_repo.read(id).await {
             Ok(Some(user)) => user,
             Ok(_none) => return Err(AppError::UserNotFound),
-            Err(_) => return Err(AppError::DatabaseConnectionFailure),
+            Err(err) => return Err(AppError::from_read_error(err.as_ref())),
         };
 
         Ok(existing_user)
     }
 
+    /// Resolves the version a write must apply to, rejecting stale `If-Match` values.
+    fn expected_version(
+        headers: &HeaderMap,
+        require_if_match: bool,
+        existing_user: &User,
+    ) -> CustomResult<i64> {
+        match if_match_version(headers, require_if_match)? {
+            Some(version) if version != existing_user.version => Err(AppError::PreconditionFailed),
+            Some(version) => Ok(version),
+            None => Ok(existing_user.version),
+        }
+    }
+
+    /// A versioned write that matched no row lost a race with a concurrent edit.
+    fn is_version_conflict(err: &(dyn Error + Send + Sync + 'static)) -> bool {
+        matches!(err.downcast_ref::<sqlx::Error>(), Some(sqlx::Error::RowNotFound))
+    }
+
+    /// The SQL type of a sort key, rejecting keys users cannot be sorted by.
+    fn sort_column_type(order_by: &str) -> CustomResult<&'static str> {
+        SORT_COLUMNS
+            .iter()
+            .find(|(column, _)| *column == order_by)
+            .map(|(_, column_type)| *column_type)
+            .ok_or_else(|| {
+                AppError::RequestPayloadNotValid(format!("Users cannot be sorted by `{}`", order_by))
+            })
+    }
+
+    /// Decodes a cursor of the query, checking its value against the type of
+    /// the sort column before it reaches the database.
+    fn decode_cursor(token: &str, order_by: &str, column_type: &str) -> CustomResult<Cursor> {
+        let cursor = Cursor::decode(token)
+            .filter(|cursor| cursor.order_by == order_by)
+            .filter(|cursor| match column_type {
+                "timestamptz" => {
+                    cursor.value == NULL_TIMESTAMP_KEY
+                        || DateTime::parse_from_rfc3339(&cursor.value).is_ok()
+                }
+                "boolean" => cursor.value.parse::<bool>().is_ok(),
+                "uuid" => Uuid::parse_str(&cursor.value).is_ok(),
+                _ => true,
+            });
+
+        cursor.ok_or_else(|| {
+            AppError::RequestPayloadNotValid(String::from(
+                "Pagination cursor is not valid for this query",
+            ))
+        })
+    }
+
     #[instrument(skip(app_state))]
     pub async fn get_all_users(
         State(app_state): State<Arc<AppState>>,
         Query(pagination): Query<Pagination>,
     ) -> CustomResult<Json<PaginatedResponse<UserDto>>> {
-        let page = pagination.page.unwrap_or(1);
-        let limit = pagination.limit.unwrap_or(3);
-        let offset = (page - 1) * limit;
+        let page = pagination.page.unwrap_or(1).max(1);
+        let limit = pagination.limit.unwrap_or(3).clamp(1, MAX_PAGE_LIMIT);
+        let offset = (page - 1).saturating_mul(limit);
         let username = pagination.username.unwrap_or(String::from(""));
         let username_option = if username.is_empty() { None } else { Some(username) };
         let order_by = pagination.order_by.unwrap_or(String::from("id"));
+        let column_type = Self::sort_column_type(&order_by)?;
+
+        let page_cursor = match (pagination.after, pagination.before) {
+            (Some(_), Some(_)) => {
+                return Err(AppError::RequestPayloadNotValid(String::from(
+                    "Only one of `after` or `before` can be given",
+                )));
+            }
+            (Some(after), None) => Some(PageCursor::After(Self::decode_cursor(
+                &after,
+                &order_by,
+                column_type,
+            )?)),
+            (None, Some(before)) => Some(PageCursor::Before(Self::decode_cursor(
+                &before,
+                &order_by,
+                column_type,
+            )?)),
+            (None, None) => None,
+        };
 
         let user_repo = &app_state.user_repo;
 
+        // One extra row is fetched to find out whether another page exists.
         let users = user_repo
-            .read_all(username_option, limit, offset, &order_by)
+            .read_all(username_option, limit + 1, offset, &order_by, page_cursor.as_ref())
             .await;
         match users {
-            Ok(users) => {
+            Ok(mut users) => {
                 let total_count_result = user_repo.count_total().await;
                 let total_count: u64 = total_count_result.unwrap_or_default();
 
+                let has_more = users.len() > limit as usize;
+                if has_more {
+                    match page_cursor {
+                        Some(PageCursor::Before(_)) => {
+                            users.remove(0);
+                        }
+                        _ => users.truncate(limit as usize),
+                    }
+                }
+
+                let first_cursor = users.first().map(|u| u.to_cursor(&order_by).encode());
+                let last_cursor = users.last().map(|u| u.to_cursor(&order_by).encode());
+
+                let (prev_cursor, next_cursor) = match page_cursor {
+                    Some(PageCursor::Before(_)) => {
+                        (if has_more { first_cursor } else { None }, last_cursor)
+                    }
+                    Some(PageCursor::After(_)) => {
+                        (first_cursor, if has_more { last_cursor } else { None })
+                    }
+                    None => (
+                        if page > 1 { first_cursor } else { None },
+                        if has_more { last_cursor } else { None },
+                    ),
+                };
+
                 let users_dto = users.into_iter().map(|u| u.to_dto()).collect();
 
                 return Ok(Json(PaginatedResponse {
@@ -72,9 +178,11 @@ impl UserController {
                     page,
                     order_by,
                     total: total_count,
+                    next_cursor,
+                    prev_cursor,
                 }));
             }
-            Err(_) => Err(AppError::NoUsersFound),
+            Err(err) => Err(AppError::from_read_error(err.as_ref())),
         }
     }
 
@@ -82,15 +190,22 @@ impl UserController {
     pub async fn get_user_by_id(
         State(app_state): State<Arc<AppState>>,
         Path(id): Path<Uuid>,
-    ) -> CustomResult<Json<UserDto>> {
+        headers: HeaderMap,
+    ) -> CustomResult<Response> {
         let user_repo = &app_state.user_repo;
 
         let found_user = Self::check_if_user_exists(user_repo, id).await;
 
         match found_user {
             Ok(user) => {
-                let user_dto = user.to_dto();
-                Ok(Json(user_dto))
+                let etag_header = [(header::ETAG, etag(user.version))];
+
+                if if_none_match(&headers, user.version) {
+                    return Ok((StatusCode::NOT_MODIFIED, etag_header).into_response());
+                }
+
+                let user_dto: UserDto = user.to_dto();
+                Ok((etag_header, Json(user_dto)).into_response())
             }
             Err(err) => return Err(err),
         }
@@ -100,7 +215,7 @@ impl UserController {
     pub async fn create_user(
         State(app_state): State<Arc<AppState>>,
         ValidatedJson(create_user_dto): ValidatedJson<CreateUserDto>,
-    ) -> CustomResult<Json<UserDto>> {
+    ) -> CustomResult<Response> {
         let user_repo = &app_state.user_repo;
 
         let new_user = User {
@@ -112,16 +227,18 @@ impl UserController {
             is_active: true,
             created_at: Some(chrono::Utc::now()),
             updated_at: Some(chrono::Utc::now()),
+            version: 1,
         };
 
         let created_user = user_repo.create(new_user).await;
 
         match created_user {
             Ok(user) => {
-                let user_dto = user.to_dto();
-                Ok(Json(user_dto))
+                let user_dto: UserDto = user.to_dto();
+                Ok(([(header::ETAG, etag(user.version))], Json(user_dto)).into_response())
             }
-            Err(_) => Err(AppError::UserCouldNotBeCreated),
+            Err(err) => Err(AppError::from_unique_violation(err.as_ref())
+                .unwrap_or(AppError::UserCouldNotBeCreated)),
         }
     }
 
@@ -129,8 +246,9 @@ impl UserController {
     pub async fn update_user(
         State(app_state): State<Arc<AppState>>,
         Path(id): Path<Uuid>,
+        headers: HeaderMap,
         ValidatedJson(update_user_dto): ValidatedJson<UpdateUserDto>,
-    ) -> CustomResult<Json<UserDto>> {
+    ) -> CustomResult<Response> {
         let user_repo = &app_state.user_repo;
 
         let found_user = Self::check_if_user_exists(user_repo, id).await;
@@ -140,6 +258,9 @@ impl UserController {
             Err(err) => return Err(err),
         };
 
+        let version =
+            Self::expected_version(&headers, app_state.require_if_match, &existing_user)?;
+
         let update_user = User {
             id: existing_user.id,
             username: update_user_dto.username,
@@ -149,16 +270,21 @@ impl UserController {
             is_active: update_user_dto.is_active,
             created_at: existing_user.created_at,
             updated_at: Some(chrono::Utc::now()),
+            version,
         };
 
         let updated_user = user_repo.update(existing_user.id, update_user).await;
 
         match updated_user {
             Ok(user) => {
-                let user_dto = user.to_dto();
-                Ok(Json(user_dto))
+                let user_dto: UserDto = user.to_dto();
+                Ok(([(header::ETAG, etag(user.version))], Json(user_dto)).into_response())
             }
-            Err(_) => Err(AppError::UserCouldNotBeUpdated),
+            Err(err) if Self::is_version_conflict(err.as_ref()) => {
+                Err(AppError::PreconditionFailed)
+            }
+            Err(err) => Err(AppError::from_unique_violation(err.as_ref())
+                .unwrap_or(AppError::UserCouldNotBeUpdated)),
         }
     }
 
@@ -166,23 +292,30 @@ impl UserController {
     pub async fn delete_user(
         State(app_state): State<Arc<AppState>>,
         Path(id): Path<Uuid>,
+        headers: HeaderMap,
     ) -> CustomResult<Json<DeletedUserDto>> {
         let user_repo = &app_state.user_repo;
 
         let found_user = Self::check_if_user_exists(user_repo, id).await;
 
-        let _existing_user = match found_user {
+        let existing_user = match found_user {
             Ok(user) => user,
             Err(err) => return Err(err),
         };
 
-        let deleted_user = user_repo.delete(id).await;
+        let version =
+            Self::expected_version(&headers, app_state.require_if_match, &existing_user)?;
+
+        let deleted_user = user_repo.delete(id, version).await;
 
         match deleted_user {
             Ok(user) => {
                 let deleted_user_dto = user.to_dto();
                 Ok(Json(deleted_user_dto))
             }
+            Err(err) if Self::is_version_conflict(err.as_ref()) => {
+                Err(AppError::PreconditionFailed)
+            }
             Err(_) => Err(AppError::UserCouldNotBeDeleted),
         }
     }
@@ -191,27 +324,76 @@ impl UserController {
     pub async fn update_user_is_active(
         State(app_state): State<Arc<AppState>>,
         Path(id): Path<Uuid>,
+        headers: HeaderMap,
         Json(user_is_active_dto): Json<UserIsActiveDto>,
-    ) -> CustomResult<Json<UserDto>> {
+    ) -> CustomResult<Response> {
         let user_repo = &app_state.user_repo;
 
         let found_user = Self::check_if_user_exists(user_repo, id).await;
 
-        let _existing_user = match found_user {
+        let existing_user = match found_user {
             Ok(user) => user,
             Err(err) => return Err(err),
         };
 
+        let version =
+            Self::expected_version(&headers, app_state.require_if_match, &existing_user)?;
+
         let updated_user_is_active = user_repo
-            .update_is_active(id, user_is_active_dto.is_active)
+            .update_is_active(id, user_is_active_dto.is_active, version)
             .await;
 
         match updated_user_is_active {
             Ok(user) => {
-                let updated_user_is_active_dto = user.to_dto();
-                Ok(Json(updated_user_is_active_dto))
+                let updated_user_is_active_dto: UserDto = user.to_dto();
+                Ok((
+                    [(header::ETAG, etag(user.version))],
+                    Json(updated_user_is_active_dto),
+                )
+                    .into_response())
+            }
+            Err(err) if Self::is_version_conflict(err.as_ref()) => {
+                Err(AppError::PreconditionFailed)
             }
             Err(_) => Err(AppError::UserCouldNotBeUpdated),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(order_by: &str, value: &str) -> String {
+        Cursor {
+            order_by: order_by.to_string(),
+            value: value.to_string(),
+            id: Uuid::new_v4(),
+        }
+        .encode()
+    }
+
+    #[test]
+    fn unknown_sort_keys_are_rejected() {
+        assert_eq!(UserController::sort_column_type("email").unwrap(), "text");
+        assert!(matches!(
+            UserController::sort_column_type("password_hash"),
+            Err(AppError::RequestPayloadNotValid(_))
+        ));
+    }
+
+    #[test]
+    fn cursor_values_must_match_the_column_type() {
+        let decode = |token: &str, order_by: &str| {
+            let column_type = UserController::sort_column_type(order_by).unwrap();
+            UserController::decode_cursor(token, order_by, column_type)
+        };
+
+        assert!(decode(&token("created_at", "2026-01-02T03:04:05Z"), "created_at").is_ok());
+        assert!(decode(&token("created_at", NULL_TIMESTAMP_KEY), "created_at").is_ok());
+        assert!(decode(&token("created_at", "yesterday"), "created_at").is_err());
+        assert!(decode(&token("is_active", "maybe"), "is_active").is_err());
+        assert!(decode(&token("username", "anything"), "username").is_ok());
+        assert!(decode(&token("username", "alice"), "email").is_err());
+    }
+}
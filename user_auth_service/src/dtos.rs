@@ -2,4 +2,5 @@ pub mod create_user_dto;
 pub mod deleted_user_dto;
 pub mod update_user_dto;
 pub mod user_dto;
+pub mod user_import_report_dto;
 pub mod user_is_active_dto;
@@ -11,4 +11,5 @@ pub struct UserDto {
     pub is_active: bool,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
+    pub version: i64,
 }
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserImportRowErrorDto {
+    /// Line of the row in the uploaded file, starting at 1.
+    pub row: usize,
+    pub errors: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserImportReportDto {
+    pub dry_run: bool,
+    pub total_rows: usize,
+    pub imported: usize,
+    pub failed: usize,
+    pub errors: Vec<UserImportRowErrorDto>,
+}
@@ -11,6 +11,7 @@ mod utility;
 use axum::{
     Router,
     extract::{Query, State},
+    middleware,
     routing::get,
 };
 use sqlx::PgPool;
@@ -23,8 +24,11 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use config_utility::load_config::load_config;
 use seeds::user_seed::seeding_users_data;
 
-use crate::{models::app_state::AppState, routes::user_routes::user_routes};
-use utility::password_hasher::hash_password;
+use crate::{
+    models::app_state::AppState,
+    routes::{admin_routes::admin_routes, user_routes::user_routes},
+};
+use utility::{password_hasher::hash_password, request_id::propagate_request_id};
 
 #[tokio::main]
 async fn main() {
@@ -73,13 +77,16 @@ async fn main() {
     let shared_state = Arc::new(AppState {
         user_repo,
         db_pool: pg_pool.clone(),
+        require_if_match: config.require_if_match,
     });
 
     let app = Router::new()
         .route("/health", get(health_check))
         .route("/passwordhash", get(generate_password_hash))
         .nest("/users", user_routes())
+        .nest("/admin", admin_routes())
         .with_state(shared_state)
+        .layer(middleware::from_fn(propagate_request_id))
         .layer(TraceLayer::new_for_http());
 
     let listener = TcpListener::bind("0.0.0.0:8080").await.unwrap();
@@ -1,5 +1,7 @@
 pub mod app_error;
 pub mod app_state;
+pub mod bulk_options;
+pub mod cursor;
 pub mod paginated_response;
 pub mod pagination;
 pub mod user;
@@ -1,87 +1,169 @@
 use axum::{
     Json,
-    http::StatusCode,
+    http::{StatusCode, header},
     response::{IntoResponse, Response},
 };
 use serde::Serialize;
+use serde_json::Value;
 use std::error::Error;
 use std::fmt::{self, Display, Formatter};
+use validator::ValidationErrors;
+
+use crate::utility::request_id::current_request_id;
 
 #[derive(Debug, Default, Serialize)]
 pub enum AppError {
     #[default]
     DatabaseConnectionFailure,
-    NoUsersFound,
     UserNotFound,
+    UserAlreadyExists(String),
     RequestPayloadNotValid(String),
+    ValidationFailed(ValidationErrors),
     UserCouldNotBeCreated,
     UserCouldNotBeUpdated,
     UserCouldNotBeDeleted,
+    PreconditionFailed,
+    PreconditionRequired,
+    InternalServerError,
+}
+
+impl AppError {
+    /// Maps a unique constraint violation on `users` to a conflict error.
+    pub fn from_unique_violation(err: &(dyn Error + Send + Sync + 'static)) -> Option<AppError> {
+        let Some(sqlx::Error::Database(db_err)) = err.downcast_ref::<sqlx::Error>() else {
+            return None;
+        };
+
+        if !db_err.is_unique_violation() {
+            return None;
+        }
+
+        let detail = match db_err.constraint() {
+            Some(constraint) if constraint.contains("email") => "A user with this email already exists",
+            Some(constraint) if constraint.contains("username") => {
+                "A user with this username already exists"
+            }
+            _ => "User already exists",
+        };
+
+        Some(AppError::UserAlreadyExists(detail.to_string()))
+    }
+
+    /// Maps a failed read: only an unreachable database is a 503, and values
+    /// the database could not convert (class 22, data exception) came from
+    /// the request.
+    pub fn from_read_error(err: &(dyn Error + Send + Sync + 'static)) -> AppError {
+        match err.downcast_ref::<sqlx::Error>() {
+            Some(
+                sqlx::Error::PoolTimedOut
+                | sqlx::Error::PoolClosed
+                | sqlx::Error::Io(_)
+                | sqlx::Error::Tls(_),
+            ) => AppError::DatabaseConnectionFailure,
+            Some(sqlx::Error::Database(db_err))
+                if db_err.code().is_some_and(|code| code.starts_with("22")) =>
+            {
+                AppError::RequestPayloadNotValid(db_err.message().to_string())
+            }
+            _ => {
+                tracing::error!(error = %err, "failed to read users");
+                AppError::InternalServerError
+            }
+        }
+    }
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        let (status_code, body) = match self {
+        let (status_code, code, detail, errors) = match self {
+            AppError::DatabaseConnectionFailure => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "DatabaseConnectionFailure",
+                "Could not connect to database".to_string(),
+                None,
+            ),
             AppError::UserNotFound => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                AppErrorResponse {
-                    code: String::from("UserNotFound"),
-                    cause: "User not found".to_string(),
-                    ..Default::default()
-                },
+                StatusCode::NOT_FOUND,
+                "UserNotFound",
+                "User not found".to_string(),
+                None,
             ),
-            AppError::NoUsersFound => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                AppErrorResponse {
-                    code: String::from("NoUsersFound"),
-                    cause: "No users found in database".to_string(),
-                    ..Default::default()
-                },
+            AppError::UserAlreadyExists(detail) => {
+                (StatusCode::CONFLICT, "UserAlreadyExists", detail, None)
+            }
+            AppError::RequestPayloadNotValid(err_string) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "RequestPayloadNotValidUnprocessableEntity",
+                err_string,
+                None,
+            ),
+            AppError::ValidationFailed(validation_errors) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "ValidationFailed",
+                "Request payload failed validation".to_string(),
+                serde_json::to_value(validation_errors.field_errors()).ok(),
             ),
             AppError::UserCouldNotBeCreated => (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                AppErrorResponse {
-                    code: String::from("UserCouldNotBeCreated"),
-                    cause: "User could not be created".to_string(),
-                    ..Default::default()
-                },
+                "UserCouldNotBeCreated",
+                "User could not be created".to_string(),
+                None,
             ),
             AppError::UserCouldNotBeUpdated => (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                AppErrorResponse {
-                    code: String::from("UserCouldNotBeUpdated"),
-                    cause: "User could not be updated".to_string(),
-                    ..Default::default()
-                },
+                "UserCouldNotBeUpdated",
+                "User could not be updated".to_string(),
+                None,
             ),
             AppError::UserCouldNotBeDeleted => (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                AppErrorResponse {
-                    code: String::from("UserCouldNotBeDeleted"),
-                    cause: "User could not be deleted".to_string(),
-                    ..Default::default()
-                },
+                "UserCouldNotBeDeleted",
+                "User could not be deleted".to_string(),
+                None,
             ),
-            AppError::RequestPayloadNotValid(err_string) => (
-                StatusCode::UNPROCESSABLE_ENTITY,
-                AppErrorResponse {
-                    code: String::from("RequestPayloadNotValidUnprocessableEntity"),
-                    cause: err_string,
-                    ..Default::default()
-                },
+            AppError::PreconditionFailed => (
+                StatusCode::PRECONDITION_FAILED,
+                "PreconditionFailed",
+                "User was modified by another request".to_string(),
+                None,
+            ),
+            AppError::PreconditionRequired => (
+                StatusCode::PRECONDITION_REQUIRED,
+                "PreconditionRequired",
+                "If-Match header is required".to_string(),
+                None,
             ),
-            _ => (
+            AppError::InternalServerError => (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                AppErrorResponse {
-                    code: String::from("DatabaseConnectionFailure"),
-                    cause: "Could not connect to database".to_string(),
-                    ..Default::default()
-                },
+                "InternalServerError",
+                "Internal Server Error".to_string(),
+                None,
             ),
         };
 
-        let serialized_json_body = Json(body);
-        (status_code, serialized_json_body).into_response()
+        let body = ProblemDetails {
+            problem_type: String::from("about:blank"),
+            title: status_code
+                .canonical_reason()
+                .unwrap_or_default()
+                .to_string(),
+            status: status_code.as_u16(),
+            detail,
+            code: code.to_string(),
+            request_id: current_request_id(),
+            errors,
+        };
+
+        if status_code.is_server_error() {
+            tracing::error!(code = %body.code, request_id = ?body.request_id, "{}", body.detail);
+        }
+
+        (
+            status_code,
+            [(header::CONTENT_TYPE, "application/problem+json")],
+            Json(body),
+        )
+            .into_response()
     }
 }
 
@@ -93,11 +175,19 @@ impl Display for AppError {
 
 impl Error for AppError {}
 
-#[derive(Default, Serialize)]
-pub struct AppErrorResponse {
+/// RFC 7807 problem details body returned for every error.
+#[derive(Serialize)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
     pub code: String,
-    pub cause: String,
-    pub stacktrace: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Value>,
 }
 
 pub type CustomResult<T> = Result<T, AppError>;
@@ -9,4 +9,5 @@ use crate::repos::repository_traits::Repository;
 pub struct AppState {
     pub db_pool: Pool<Postgres>,
     pub user_repo: Arc<dyn Repository<User, Uuid> + Send + Sync>,
+    pub require_if_match: bool,
 }
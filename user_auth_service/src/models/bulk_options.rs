@@ -0,0 +1,19 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkFormat {
+    Csv,
+    Ndjson,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ImportOptions {
+    pub format: Option<BulkFormat>,
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ExportOptions {
+    pub format: Option<BulkFormat>,
+}
@@ -0,0 +1,59 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Opaque keyset pagination cursor.
+///
+/// Holds the sort column, the value of that column and the id of the row the
+/// page boundary sits on. Clients only ever see the base64url encoded form.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cursor {
+    pub order_by: String,
+    pub value: String,
+    pub id: Uuid,
+}
+
+/// Which side of the cursor a page should be read from.
+#[derive(Debug, Clone)]
+pub enum PageCursor {
+    After(Cursor),
+    Before(Cursor),
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    pub fn decode(token: &str) -> Option<Self> {
+        let json = URL_SAFE_NO_PAD.decode(token).ok()?;
+        serde_json::from_slice(&json).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_round_trips() {
+        let cursor = Cursor {
+            order_by: "email".to_string(),
+            value: "alice@example.com".to_string(),
+            id: Uuid::new_v4(),
+        };
+
+        let decoded = Cursor::decode(&cursor.encode()).unwrap();
+
+        assert_eq!(decoded.order_by, cursor.order_by);
+        assert_eq!(decoded.value, cursor.value);
+        assert_eq!(decoded.id, cursor.id);
+    }
+
+    #[test]
+    fn decode_rejects_garbage() {
+        assert!(Cursor::decode("not a cursor").is_none());
+        assert!(Cursor::decode(&URL_SAFE_NO_PAD.encode(b"{}")).is_none());
+    }
+}
@@ -7,4 +7,6 @@ pub struct PaginatedResponse<T> {
     pub limit: u32,
     pub total: u64,
     pub order_by: String,
+    pub next_cursor: Option<String>,
+    pub prev_cursor: Option<String>,
 }
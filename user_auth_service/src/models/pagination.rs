@@ -6,4 +6,6 @@ pub struct Pagination {
     pub page: Option<u32>,
     pub limit: Option<u32>,
     pub order_by: Option<String>,
+    pub after: Option<String>,
+    pub before: Option<String>,
 }
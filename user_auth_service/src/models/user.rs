@@ -1,11 +1,12 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
 use crate::{
     dtos::{deleted_user_dto::DeletedUserDto, user_dto::UserDto},
-    traits::to_dto::ToDto,
+    models::cursor::Cursor,
+    traits::{to_cursor::ToCursor, to_dto::ToDto},
 };
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
@@ -18,6 +19,7 @@ pub struct User {
     pub is_active: bool,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
+    pub version: i64,
 }
 
 impl ToDto<UserDto> for User {
@@ -30,6 +32,7 @@ impl ToDto<UserDto> for User {
             is_active: self.is_active,
             created_at: self.created_at,
             updated_at: self.updated_at,
+            version: self.version,
         }
     }
 }
@@ -43,3 +46,79 @@ impl ToDto<DeletedUserDto> for User {
         }
     }
 }
+
+/// Cursor value of a `NULL` timestamp. Timestamp columns are sorted as
+/// `COALESCE(column, '-infinity')`, so users without one come first and can be
+/// paged past like any other.
+pub const NULL_TIMESTAMP_KEY: &str = "-infinity";
+
+impl ToCursor for User {
+    fn to_cursor(&self, order_by: &str) -> Cursor {
+        let timestamp = |value: Option<DateTime<Utc>>| {
+            value
+                .map(|v| v.to_rfc3339_opts(SecondsFormat::Micros, true))
+                .unwrap_or_else(|| NULL_TIMESTAMP_KEY.to_string())
+        };
+
+        let value = match order_by {
+            "username" => self.username.clone(),
+            "email" => self.email.clone(),
+            "role" => self.role.clone(),
+            "is_active" => self.is_active.to_string(),
+            "created_at" => timestamp(self.created_at),
+            "updated_at" => timestamp(self.updated_at),
+            _ => self.id.to_string(),
+        };
+
+        Cursor {
+            order_by: order_by.to_string(),
+            value,
+            id: self.id,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(created_at: Option<DateTime<Utc>>) -> User {
+        User {
+            id: Uuid::new_v4(),
+            username: "alice".to_string(),
+            email: "alice@example.com".to_string(),
+            password_hash: String::new(),
+            role: "user".to_string(),
+            is_active: true,
+            created_at,
+            updated_at: None,
+            version: 1,
+        }
+    }
+
+    #[test]
+    fn cursor_encodes_timestamps_with_microseconds() {
+        let created_at = "2026-01-02T03:04:05.123456Z".parse().unwrap();
+
+        let cursor = user(Some(created_at)).to_cursor("created_at");
+
+        assert_eq!(cursor.value, "2026-01-02T03:04:05.123456Z");
+    }
+
+    #[test]
+    fn cursor_encodes_null_timestamps_as_sort_key() {
+        let cursor = user(None).to_cursor("updated_at");
+
+        assert_eq!(cursor.value, NULL_TIMESTAMP_KEY);
+    }
+
+    #[test]
+    fn cursor_falls_back_to_id() {
+        let user = user(None);
+
+        let cursor = user.to_cursor("unknown");
+
+        assert_eq!(cursor.value, user.id.to_string());
+        assert_eq!(cursor.id, user.id);
+    }
+}
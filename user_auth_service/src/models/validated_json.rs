@@ -36,7 +36,7 @@ where
 
         value
             .validate()
-            .map_err(|err| AppError::RequestPayloadNotValid(err.to_string()))?;
+            .map_err(AppError::ValidationFailed)?;
 
         Ok(ValidatedJson(value))
     }
@@ -1,10 +1,20 @@
 use async_trait::async_trait;
 use std::error::Error;
 
+use crate::models::cursor::PageCursor;
+
 // Core Traits for CRUD operations
 #[async_trait]
 pub trait Create<T> {
     async fn create(&self, entity: T) -> Result<T, Box<dyn Error + Send + Sync>>;
+    /// Inserts a batch in one transaction, skipping rows that hit a unique
+    /// constraint, and returns the rows that were written. A dry run rolls the
+    /// transaction back.
+    async fn create_many(
+        &self,
+        entities: Vec<T>,
+        dry_run: bool,
+    ) -> Result<Vec<T>, Box<dyn Error + Send + Sync>>;
 }
 
 #[async_trait]
@@ -16,6 +26,7 @@ pub trait Read<T, ID> {
         limit: u32,
         offset: u32,
         order_by: &str,
+        cursor: Option<&PageCursor>,
     ) -> Result<Vec<T>, Box<dyn Error + Send + Sync>>;
     async fn count_total(&self) -> Result<u64, Box<dyn Error + Send + Sync>>;
 }
@@ -27,12 +38,13 @@ pub trait Update<T, ID> {
         &self,
         id: ID,
         is_active: bool,
+        version: i64,
     ) -> Result<T, Box<dyn Error + Send + Sync>>;
 }
 
 #[async_trait]
 pub trait Delete<T, ID> {
-    async fn delete(&self, id: ID) -> Result<T, Box<dyn Error + Send + Sync>>;
+    async fn delete(&self, id: ID, version: i64) -> Result<T, Box<dyn Error + Send + Sync>>;
 }
 
 // Composite Repository Trait
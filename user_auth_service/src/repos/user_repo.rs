@@ -5,7 +5,21 @@ use tracing::instrument;
 use uuid::Uuid;
 
 use super::repository_traits::{Create, Delete, Read, Repository, Update};
-use crate::models::user::User;
+use crate::models::{
+    cursor::PageCursor,
+    user::{NULL_TIMESTAMP_KEY, User},
+};
+
+/// Columns users can be sorted by, with their SQL types.
+pub const SORT_COLUMNS: [(&str, &str); 7] = [
+    ("id", "uuid"),
+    ("username", "text"),
+    ("email", "text"),
+    ("role", "text"),
+    ("is_active", "boolean"),
+    ("created_at", "timestamptz"),
+    ("updated_at", "timestamptz"),
+];
 
 pub struct UserRepo {
     pub pool: PgPool,
@@ -20,7 +34,7 @@ impl Read<User, Uuid> for UserRepo {
         let rec = sqlx::query_as!(
             User,
             r#"
-            SELECT id, username, email, password_hash, role, is_active, created_at, updated_at
+            SELECT id, username, email, password_hash, role, is_active, created_at, updated_at, version
             FROM users
             WHERE id = $1
             "#,
@@ -39,41 +53,84 @@ impl Read<User, Uuid> for UserRepo {
         limit: u32,
         offset: u32,
         order_by: &str,
+        cursor: Option<&PageCursor>,
     ) -> Result<Vec<User>, Box<dyn Error + Send + Sync>> {
-        let allowed_columns = [
-            "id",
-            "username",
-            "email",
-            "role",
-            "is_active",
-            "created_at",
-            "updated_at",
-        ];
-        let order_by_clause = if allowed_columns.contains(&order_by) {
-            order_by
+        let (order_by_column, column_type) = SORT_COLUMNS
+            .iter()
+            .find(|(column, _)| *column == order_by)
+            .copied()
+            .unwrap_or(("id", "uuid")); // default to id
+
+        // Nullable timestamps sort and compare as the cursor encodes them.
+        let order_by_clause = if column_type == "timestamptz" {
+            format!(
+                "COALESCE({}, '{}'::timestamptz)",
+                order_by_column, NULL_TIMESTAMP_KEY
+            )
         } else {
-            "id" // default to id
+            order_by_column.to_string()
         };
 
         let mut query_builder: QueryBuilder<sqlx::Postgres> = QueryBuilder::new(
-            "SELECT id, username, email, password_hash, role, is_active, created_at, updated_at FROM users",
+            "SELECT id, username, email, password_hash, role, is_active, created_at, updated_at, version FROM users",
         );
 
+        let mut has_where = false;
+
         if let Some(name_word) = name {
             query_builder.push(" WHERE username LIKE ");
             query_builder.push_bind(format!("%{}%", name_word));
+            has_where = true;
         }
 
-        query_builder.push(format!(" ORDER BY {} ", order_by_clause));
+        // Keyset pagination walks backwards for `before` cursors and the rows
+        // are flipped into the requested order once fetched.
+        let is_backwards = matches!(cursor, Some(PageCursor::Before(_)));
+        let direction = if is_backwards { "DESC" } else { "ASC" };
+
+        if let Some(page_cursor) = cursor {
+            let (cursor, comparator) = match page_cursor {
+                PageCursor::After(cursor) => (cursor, ">"),
+                PageCursor::Before(cursor) => (cursor, "<"),
+            };
+
+            query_builder.push(if has_where { " AND " } else { " WHERE " });
+
+            if order_by_clause == "id" {
+                query_builder.push(format!("id {} ", comparator));
+                query_builder.push_bind(cursor.id);
+            } else {
+                query_builder.push(format!("({}, id) {} (CAST(", order_by_clause, comparator));
+                query_builder.push_bind(cursor.value.clone());
+                query_builder.push(format!(" AS {}), ", column_type));
+                query_builder.push_bind(cursor.id);
+                query_builder.push(")");
+            }
+        }
+
+        if order_by_clause == "id" {
+            query_builder.push(format!(" ORDER BY id {} ", direction));
+        } else {
+            query_builder.push(format!(
+                " ORDER BY {} {}, id {} ",
+                order_by_clause, direction, direction
+            ));
+        }
 
         query_builder.push(" LIMIT ");
         query_builder.push_bind(limit as i64);
 
-        query_builder.push(" OFFSET ");
-        query_builder.push_bind(offset as i64);
+        if cursor.is_none() {
+            query_builder.push(" OFFSET ");
+            query_builder.push_bind(offset as i64);
+        }
 
         let query = query_builder.build_query_as();
-        let recs = query.fetch_all(&self.pool).await?;
+        let mut recs: Vec<User> = query.fetch_all(&self.pool).await?;
+
+        if is_backwards {
+            recs.reverse();
+        }
 
         Ok(recs)
     }
@@ -97,7 +154,7 @@ impl Create<User> for UserRepo {
                     r#"
                     INSERT INTO users (id, username, email, password_hash, role, is_active, created_at, updated_at)
                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-                    RETURNING id, username, email, password_hash, role, is_active, created_at, updated_at
+                    RETURNING id, username, email, password_hash, role, is_active, created_at, updated_at, version
                     "#,
                     entity.id,
                     entity.username,
@@ -113,6 +170,48 @@ impl Create<User> for UserRepo {
 
         Ok(rec)
     }
+
+    #[instrument(skip(self, entities))]
+    async fn create_many(
+        &self,
+        entities: Vec<User>,
+        dry_run: bool,
+    ) -> Result<Vec<User>, Box<dyn Error + Send + Sync>> {
+        if entities.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let mut query_builder: QueryBuilder<sqlx::Postgres> = QueryBuilder::new(
+            "INSERT INTO users (id, username, email, password_hash, role, is_active, created_at, updated_at) ",
+        );
+
+        query_builder.push_values(entities, |mut row, entity| {
+            row.push_bind(entity.id)
+                .push_bind(entity.username)
+                .push_bind(entity.email)
+                .push_bind(entity.password_hash)
+                .push_bind(entity.role)
+                .push_bind(entity.is_active)
+                .push_bind(entity.created_at)
+                .push_bind(entity.updated_at);
+        });
+
+        query_builder.push(
+            " ON CONFLICT DO NOTHING RETURNING id, username, email, password_hash, role, is_active, created_at, updated_at, version",
+        );
+
+        let recs: Vec<User> = query_builder.build_query_as().fetch_all(&mut *tx).await?;
+
+        if dry_run {
+            tx.rollback().await?;
+        } else {
+            tx.commit().await?;
+        }
+
+        Ok(recs)
+    }
 }
 
 #[async_trait]
@@ -127,16 +226,18 @@ impl Update<User, Uuid> for UserRepo {
                     email = $3,
                     role = $4,
                     is_active = $5,
-                    updated_at = $6
-            WHERE id = $1
-            RETURNING id, username, email, password_hash, role, is_active, created_at, updated_at
+                    updated_at = $6,
+                    version = version + 1
+            WHERE id = $1 AND version = $7
+            RETURNING id, username, email, password_hash, role, is_active, created_at, updated_at, version
             "#,
             id,
             entity.username,
             entity.email,
             entity.role,
             entity.is_active,
-            entity.updated_at
+            entity.updated_at,
+            entity.version
         )
         .fetch_one(&self.pool)
         .await?;
@@ -149,19 +250,22 @@ impl Update<User, Uuid> for UserRepo {
         &self,
         id: Uuid,
         is_active: bool,
+        version: i64,
     ) -> Result<User, Box<dyn Error + Send + Sync>> {
         let rec = sqlx::query_as!(
             User,
             r#"
             UPDATE users
                 SET is_active = $2,
-                    updated_at = $3
-            WHERE id = $1
-            RETURNING id, username, email, password_hash, role, is_active, created_at, updated_at
+                    updated_at = $3,
+                    version = version + 1
+            WHERE id = $1 AND version = $4
+            RETURNING id, username, email, password_hash, role, is_active, created_at, updated_at, version
             "#,
             id,
             is_active,
-            Some(chrono::Utc::now())
+            Some(chrono::Utc::now()),
+            version
         )
         .fetch_one(&self.pool)
         .await?;
@@ -173,15 +277,16 @@ impl Update<User, Uuid> for UserRepo {
 #[async_trait]
 impl Delete<User, Uuid> for UserRepo {
     #[instrument(skip(self))]
-    async fn delete(&self, id: Uuid) -> Result<User, Box<dyn Error + Send + Sync>> {
+    async fn delete(&self, id: Uuid, version: i64) -> Result<User, Box<dyn Error + Send + Sync>> {
         let rec = sqlx::query_as!(
             User,
             r#"
             DELETE FROM users
-            WHERE id = $1
-            RETURNING id, username, email, password_hash, role, is_active, created_at, updated_at
+            WHERE id = $1 AND version = $2
+            RETURNING id, username, email, password_hash, role, is_active, created_at, updated_at, version
             "#,
             id,
+            version,
         )
         .fetch_one(&self.pool)
         .await?;
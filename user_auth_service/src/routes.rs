@@ -1 +1,2 @@
+pub mod admin_routes;
 pub mod user_routes;
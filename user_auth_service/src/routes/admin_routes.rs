@@ -0,0 +1,20 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    extract::DefaultBodyLimit,
+    routing::{get, post},
+};
+
+use crate::{controllers::user_bulk_controller::UserBulkController, models::app_state::AppState};
+
+const IMPORT_BODY_LIMIT: usize = 64 * 1024 * 1024;
+
+pub fn admin_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/users/import",
+            post(UserBulkController::import_users).layer(DefaultBodyLimit::max(IMPORT_BODY_LIMIT)),
+        )
+        .route("/users/export", get(UserBulkController::export_users))
+}
@@ -33,6 +33,7 @@ pub async fn seeding_users_data(pg_pool: &PgPool) -> Result<(), Box<dyn std::err
                 is_active: true,
                 created_at: Some(chrono::Utc::now()),
                 updated_at: Some(chrono::Utc::now()),
+                version: 1,
             },
             User {
                 id: Uuid::new_v4(),
@@ -43,6 +44,7 @@ pub async fn seeding_users_data(pg_pool: &PgPool) -> Result<(), Box<dyn std::err
                 is_active: true,
                 created_at: Some(chrono::Utc::now()),
                 updated_at: Some(chrono::Utc::now()),
+                version: 1,
             },
         ];
 
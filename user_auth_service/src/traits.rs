@@ -1 +1,2 @@
+pub mod to_cursor;
 pub mod to_dto;
@@ -1 +1,3 @@
+pub mod etag;
 pub mod password_hasher;
+pub mod request_id;
@@ -0,0 +1,49 @@
+use axum::http::{HeaderMap, header};
+
+use crate::models::app_error::AppError;
+
+/// Builds the strong entity tag for a row version.
+pub fn etag(version: i64) -> String {
+    format!("\"{}\"", version)
+}
+
+/// Reads the row version a write is conditioned on from the `If-Match` header.
+///
+/// Returns `Ok(None)` for `If-Match: *` and, unless `require` is set, when the
+/// header is missing. Only a single strong entity tag is accepted.
+pub fn if_match_version(headers: &HeaderMap, require: bool) -> Result<Option<i64>, AppError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return if require {
+            Err(AppError::PreconditionRequired)
+        } else {
+            Ok(None)
+        };
+    };
+
+    let value = value.to_str().map_err(|_| AppError::PreconditionFailed)?.trim();
+
+    if value == "*" {
+        return Ok(None);
+    }
+
+    value
+        .strip_prefix('"')
+        .and_then(|tag| tag.strip_suffix('"'))
+        .and_then(|tag| tag.parse::<i64>().ok())
+        .map(Some)
+        .ok_or(AppError::PreconditionFailed)
+}
+
+/// Whether the `If-None-Match` header matches the current row version, using
+/// weak comparison as required for conditional reads.
+pub fn if_none_match(headers: &HeaderMap, version: i64) -> bool {
+    let current = etag(version);
+
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == current)
+}